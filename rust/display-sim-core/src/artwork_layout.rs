@@ -0,0 +1,428 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::general_types::Size2D;
+use app_util::{AppError, AppResult};
+use std::collections::HashMap;
+
+/// A rectangle in layout units, with Y growing downwards.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayoutBounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for LayoutBounds {
+    fn default() -> Self {
+        LayoutBounds {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        }
+    }
+}
+
+impl LayoutBounds {
+    fn center(&self) -> [f32; 2] {
+        [self.x + self.width * 0.5, self.y + self.height * 0.5]
+    }
+
+    fn union(&self, other: &LayoutBounds) -> LayoutBounds {
+        let left = self.x.min(other.x);
+        let top = self.y.min(other.y);
+        let right = (self.x + self.width).max(other.x + other.width);
+        let bottom = (self.y + self.height).max(other.y + other.height);
+        LayoutBounds {
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArtworkLayer {
+    /// Image path, relative to the layout file.
+    pub file: String,
+    pub bounds: LayoutBounds,
+    /// Bezels are composited over the screen, backdrops under it.
+    pub over_screen: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArtworkLayout {
+    pub screen: LayoutBounds,
+    pub layers: Vec<ArtworkLayer>,
+}
+
+/// An artwork layer placed in world space. `layer` indexes
+/// `ArtworkLayout::layers`, and also the images loaded for them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArtworkQuad {
+    pub layer: usize,
+    pub center: [f32; 2],
+    pub size: [f32; 2],
+    pub over_screen: bool,
+}
+
+impl ArtworkLayout {
+    /// Reads the static artwork of a MAME `.lay` file: image elements and the
+    /// first view, with its screen and artwork bounds. Other elements are ignored.
+    pub fn parse(source: &str) -> AppResult<ArtworkLayout> {
+        let mut images: HashMap<String, String> = HashMap::new();
+        let mut current_element: Option<String> = None;
+        let mut views_seen = 0;
+        let mut in_first_view = false;
+        let mut screen: Option<LayoutBounds> = None;
+        // Items of the first view, in drawing order: (element, tag, bounds, after screen).
+        let mut items: Vec<(String, String, LayoutBounds, bool)> = Vec::new();
+        let mut current_item: Option<usize> = None;
+        let mut current_screen = false;
+        let mut depth = 0;
+
+        for tag in parse_tags(source)? {
+            if tag.closing {
+                depth -= 1;
+                match tag.name {
+                    "element" if depth == 1 => {
+                        current_element = None;
+                        current_item = None;
+                    }
+                    "view" if depth == 1 => in_first_view = false,
+                    _ if depth == 2 && in_first_view => {
+                        current_item = None;
+                        current_screen = false;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            match (depth, tag.name) {
+                (1, "element") => current_element = tag.attribute("name").map(str::to_string),
+                (_, "image") if depth >= 2 && current_element.is_some() && !in_first_view => {
+                    if let (Some(element), Some(file)) = (&current_element, tag.attribute("file")) {
+                        images.entry(element.clone()).or_insert_with(|| file.to_string());
+                    }
+                }
+                (1, "view") => {
+                    views_seen += 1;
+                    in_first_view = views_seen == 1 && !tag.self_closing;
+                }
+                (2, "screen") if in_first_view && screen.is_none() => {
+                    screen = Some(LayoutBounds::default());
+                    current_screen = true;
+                }
+                (2, name) if in_first_view => {
+                    let element = tag.attribute("ref").or_else(|| tag.attribute("element"));
+                    if let Some(element) = element {
+                        items.push((element.to_string(), name.to_string(), LayoutBounds::default(), screen.is_some()));
+                        current_item = Some(items.len() - 1);
+                    }
+                }
+                (3, "bounds") if in_first_view => {
+                    let bounds = tag.bounds()?;
+                    if current_screen {
+                        screen = Some(bounds);
+                    } else if let Some(index) = current_item {
+                        items[index].2 = bounds;
+                    }
+                }
+                _ => {}
+            }
+            if !tag.self_closing {
+                depth += 1;
+            } else if depth == 2 && in_first_view {
+                current_item = None;
+                current_screen = false;
+            }
+        }
+
+        if views_seen == 0 {
+            return Err(AppError::new("artwork layout has no view".into()));
+        }
+        let screen = screen.ok_or_else(|| AppError::new("artwork layout view has no screen".into()))?;
+        if screen.width <= 0.0 || screen.height <= 0.0 {
+            return Err(AppError::new("artwork layout screen has an empty area".into()));
+        }
+        let layers = items
+            .into_iter()
+            .filter_map(|(element, tag, bounds, after_screen)| {
+                let file = images.get(&element)?.clone();
+                let over_screen = match tag.as_str() {
+                    "backdrop" => false,
+                    // Version 2 layouts draw every item as a plain element, in order.
+                    "element" => after_screen,
+                    _ => true,
+                };
+                Some(ArtworkLayer { file, bounds, over_screen })
+            })
+            .collect();
+        Ok(ArtworkLayout { screen, layers })
+    }
+
+    /// Union of the screen and every artwork layer.
+    pub fn view_bounds(&self) -> LayoutBounds {
+        self.layers.iter().fold(self.screen, |bounds, layer| bounds.union(&layer.bounds))
+    }
+
+    /// Places every layer in world space, given the world size of the
+    /// simulated screen, which is centered at the origin.
+    pub fn place(&self, screen_size: Size2D<f32>) -> Vec<ArtworkQuad> {
+        self.layers
+            .iter()
+            .enumerate()
+            .map(|(layer, artwork)| {
+                let rect = self.to_world(&artwork.bounds, screen_size);
+                ArtworkQuad {
+                    layer,
+                    center: rect.0,
+                    size: rect.1,
+                    over_screen: artwork.over_screen,
+                }
+            })
+            .collect()
+    }
+
+    /// Center of the whole artwork in world space, and how much further the
+    /// camera has to move back to fit it instead of the screen alone.
+    pub fn framing(&self, screen_size: Size2D<f32>) -> ([f32; 2], f32) {
+        let view = self.view_bounds();
+        let (center, _) = self.to_world(&view, screen_size);
+        let scale = (view.width / self.screen.width).max(view.height / self.screen.height).max(1.0);
+        (center, scale)
+    }
+
+    fn to_world(&self, bounds: &LayoutBounds, screen_size: Size2D<f32>) -> ([f32; 2], [f32; 2]) {
+        let scale_x = screen_size.width / self.screen.width;
+        let scale_y = screen_size.height / self.screen.height;
+        let screen_center = self.screen.center();
+        let center = bounds.center();
+        (
+            [(center[0] - screen_center[0]) * scale_x, (screen_center[1] - center[1]) * scale_y],
+            [bounds.width * scale_x, bounds.height * scale_y],
+        )
+    }
+}
+
+struct Tag<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, String)>,
+    closing: bool,
+    self_closing: bool,
+}
+
+impl<'a> Tag<'a> {
+    fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.iter().find(|(k, _)| *k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn number(&self, key: &str) -> AppResult<Option<f32>> {
+        match self.attribute(key) {
+            None => Ok(None),
+            Some(value) => value
+                .trim()
+                .parse::<f32>()
+                .map(Some)
+                .map_err(|_| AppError::new(format!("artwork layout bounds attribute '{}' is not a number: '{}'", key, value))),
+        }
+    }
+
+    fn bounds(&self) -> AppResult<LayoutBounds> {
+        if let (Some(left), Some(top), Some(right), Some(bottom)) = (self.number("left")?, self.number("top")?, self.number("right")?, self.number("bottom")?) {
+            return Ok(LayoutBounds {
+                x: left,
+                y: top,
+                width: right - left,
+                height: bottom - top,
+            });
+        }
+        Ok(LayoutBounds {
+            x: self.number("x")?.unwrap_or(0.0),
+            y: self.number("y")?.unwrap_or(0.0),
+            width: self.number("width")?.unwrap_or(1.0),
+            height: self.number("height")?.unwrap_or(1.0),
+        })
+    }
+}
+
+fn parse_tags(source: &str) -> AppResult<Vec<Tag<'_>>> {
+    let mut tags = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let terminator = if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<?") {
+            "?>"
+        } else {
+            ">"
+        };
+        let end = rest
+            .find(terminator)
+            .ok_or_else(|| AppError::new("artwork layout has an unterminated tag".into()))?;
+        let inner = &rest[1..end];
+        rest = &rest[end + terminator.len()..];
+        if terminator != ">" || inner.starts_with('!') {
+            continue;
+        }
+        let closing = inner.starts_with('/');
+        let self_closing = inner.ends_with('/');
+        let inner = inner.trim_start_matches('/').trim_end_matches('/').trim();
+        let name_end = inner.find(char::is_whitespace).unwrap_or(inner.len());
+        tags.push(Tag {
+            name: &inner[..name_end],
+            attributes: parse_attributes(&inner[name_end..])?,
+            closing,
+            self_closing,
+        });
+    }
+    Ok(tags)
+}
+
+fn parse_attributes(mut source: &str) -> AppResult<Vec<(&str, String)>> {
+    let mut attributes = Vec::new();
+    loop {
+        source = source.trim_start();
+        if source.is_empty() {
+            return Ok(attributes);
+        }
+        let equals = source.find('=').ok_or_else(|| AppError::new("artwork layout attribute has no value".into()))?;
+        let key = source[..equals].trim();
+        source = source[equals + 1..].trim_start();
+        let quote = source.chars().next().filter(|c| *c == '"' || *c == '\'');
+        let quote = quote.ok_or_else(|| AppError::new(format!("artwork layout attribute '{}' is not quoted", key)))?;
+        let end = source[1..]
+            .find(quote)
+            .ok_or_else(|| AppError::new(format!("artwork layout attribute '{}' is not closed", key)))?
+            + 1;
+        attributes.push((key, unescape(&source[1..end])));
+        source = &source[end + 1..];
+    }
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY_LAYOUT: &str = r#"<?xml version="1.0"?>
+<mamelayout version="2">
+    <!-- <view name="Commented"/> -->
+    <element name="frame"><image file="bezel.png"/></element>
+    <element name="stars"><image file="backdrop.png"/></element>
+    <element name="score"><text string="00"/></element>
+    <view name="Bezel Artwork">
+        <bezel element="frame"><bounds x="0" y="0" width="4" height="4"/></bezel>
+        <screen index="0"><bounds left="1" top="1" right="3" bottom="2.5"/></screen>
+        <backdrop element="stars"><bounds x="1" y="1" width="2" height="1.5"/></backdrop>
+        <bezel element="score"><bounds x="0" y="0" width="1" height="1"/></bezel>
+    </view>
+    <view name="Screen Only"><screen index="0"><bounds x="0" y="0" width="4" height="3"/></screen></view>
+</mamelayout>"#;
+
+    #[test]
+    fn legacy_layouts_keep_backdrops_under_and_bezels_over_the_screen() {
+        let layout = ArtworkLayout::parse(LEGACY_LAYOUT).unwrap();
+
+        assert_eq!(
+            layout.screen,
+            LayoutBounds {
+                x: 1.0,
+                y: 1.0,
+                width: 2.0,
+                height: 1.5
+            }
+        );
+        assert_eq!(
+            layout.layers,
+            vec![
+                ArtworkLayer {
+                    file: "bezel.png".into(),
+                    bounds: LayoutBounds {
+                        x: 0.0,
+                        y: 0.0,
+                        width: 4.0,
+                        height: 4.0
+                    },
+                    over_screen: true,
+                },
+                ArtworkLayer {
+                    file: "backdrop.png".into(),
+                    bounds: LayoutBounds {
+                        x: 1.0,
+                        y: 1.0,
+                        width: 2.0,
+                        height: 1.5
+                    },
+                    over_screen: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn element_items_are_drawn_in_order_around_the_screen() {
+        let layout = ArtworkLayout::parse(
+            r#"<mamelayout version="2">
+                <element name="back"><image file="a&amp;b.png"/></element>
+                <element name="front"><image file='front.png'/></element>
+                <view name="Default">
+                    <element ref="back"/>
+                    <screen index="0"><bounds x="0" y="0" width="4" height="3"/></screen>
+                    <element ref="front"><bounds x="-1" y="-1" width="6" height="5"/></element>
+                </view>
+            </mamelayout>"#,
+        )
+        .unwrap();
+
+        let layers: Vec<_> = layout.layers.iter().map(|layer| (layer.file.as_str(), layer.over_screen)).collect();
+        assert_eq!(layers, vec![("a&b.png", false), ("front.png", true)]);
+    }
+
+    #[test]
+    fn layers_are_placed_relative_to_the_world_screen() {
+        let layout = ArtworkLayout::parse(LEGACY_LAYOUT).unwrap();
+        let screen_size = Size2D { width: 320.0, height: 240.0 };
+
+        let quads = layout.place(screen_size);
+
+        // The bezel spans twice the screen width and 8/3 of its height, and
+        // its center sits 0.25 layout units below the screen center.
+        assert_eq!(quads[0].size, [640.0, 640.0]);
+        assert_eq!(quads[0].center, [0.0, -40.0]);
+        assert_eq!(quads[1].size, [320.0, 240.0]);
+        assert_eq!(quads[1].center, [0.0, 0.0]);
+        assert_eq!(layout.framing(screen_size), ([0.0, -40.0], 4.0 / 1.5));
+    }
+
+    #[test]
+    fn layouts_without_a_screen_are_rejected() {
+        assert!(ArtworkLayout::parse("<mamelayout><view name=\"x\"></view></mamelayout>").is_err());
+        assert!(ArtworkLayout::parse("<mamelayout></mamelayout>").is_err());
+        assert!(ArtworkLayout::parse("<mamelayout><view><screen><bounds x=\"a\"/></screen></view></mamelayout>").is_err());
+    }
+}
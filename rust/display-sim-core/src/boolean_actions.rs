@@ -411,6 +411,8 @@ fn handle_action(input: &mut Input, action: BooleanAction, pressed: Pressed) {
         BooleanAction::Control => input.control = pressed,
         BooleanAction::Alt => input.alt = pressed,
         BooleanAction::Screenshot => input.screenshot.input = pressed,
+        BooleanAction::Power => input.power.input = pressed,
+        BooleanAction::Degauss => input.degauss.input = pressed,
         BooleanAction::ResetPosition => input.reset_position = pressed,
         BooleanAction::ResetFilters => input.reset_filters = pressed,
         BooleanAction::InputFocused => input.input_focused = pressed,
@@ -463,6 +465,8 @@ fn to_boolean_action(boolean_action: &str) -> Option<BooleanAction> {
         "control" => Some(BooleanAction::Control),
         "alt" => Some(BooleanAction::Alt),
         "f4" | "capture-framebuffer" => Some(BooleanAction::Screenshot),
+        "f2" | "power-toggle" => Some(BooleanAction::Power),
+        "f3" | "degauss" => Some(BooleanAction::Degauss),
         "reset-camera" => Some(BooleanAction::ResetPosition),
         "reset-filters" => Some(BooleanAction::ResetFilters),
         "input_focused" => Some(BooleanAction::InputFocused),
@@ -528,23 +532,48 @@ mod test_trigger_hotkey_action {
             custom-scaling-aspect-ratio-x-dec custom-scaling-aspect-ratio-x-inc
             custom-scaling-aspect-ratio-y-dec custom-scaling-aspect-ratio-y-inc
             pixel-width-dec pixel-width-inc internal-resolution-dec internal-resolution-inc
+            scan-output-dec scan-output-inc display-refresh-dec display-refresh-inc source-refresh-dec source-refresh-inc
             blur-level-dec blur-level-inc color-gamma-dec color-gamma-inc color-noise-dec color-noise-inc
+            signal-type-dec signal-type-inc signal-standard-dec signal-standard-inc signal-crosstalk-dec signal-crosstalk-inc
+            tape-speed-dec tape-speed-inc tape-noise-dec tape-noise-inc tape-tracking-dec tape-tracking-inc tape-dropouts-dec tape-dropouts-inc
+            rf-snow-dec rf-snow-inc hum-bar-strength-dec hum-bar-strength-inc hum-bar-frequency-dec hum-bar-frequency-inc
+            ghost-strength-dec ghost-strength-inc ghost-offset-dec ghost-offset-inc vertical-roll-dec vertical-roll-inc
+            convergence-red-x-dec convergence-red-x-inc convergence-red-y-dec convergence-red-y-inc
+            convergence-blue-x-dec convergence-blue-x-inc convergence-blue-y-dec convergence-blue-y-inc convergence-radial-dec convergence-radial-inc
             pixel-brightness-dec pixel-brightness-inc pixel-contrast-dec pixel-contrast-inc
-            screen-curvature-dec screen-curvature-inc pixel-horizontal-gap-dec pixel-horizontal-gap-inc
+            screen-curvature-dec screen-curvature-inc curvature-horizontal-dec curvature-horizontal-inc curvature-vertical-dec curvature-vertical-inc
+            barrel-distortion-dec barrel-distortion-inc screen-keystone-dec screen-keystone-inc screen-trapezoid-dec screen-trapezoid-inc screen-tilt-dec screen-tilt-inc
+            screen-corner-radius-dec screen-corner-radius-inc screen-edge-softness-dec screen-edge-softness-inc vignette-strength-dec vignette-strength-inc vignette-shape-dec vignette-shape-inc
+            pixel-horizontal-gap-dec pixel-horizontal-gap-inc
             pixel-vertical-gap-dec pixel-vertical-gap-inc vertical-lpp-dec vertical-lpp-inc
-            horizontal-lpp-dec horizontal-lpp-inc color-representation-dec color-representation-inc
+            horizontal-lpp-dec horizontal-lpp-inc scan-mode-dec scan-mode-inc
+            interlace-flicker-dec interlace-flicker-inc interlace-combing-dec interlace-combing-inc
+            color-representation-dec color-representation-inc
             pixel-geometry-dec pixel-geometry-inc pixel-shadow-shape-dec pixel-shadow-shape-inc
             pixel-shadow-height-dec pixel-shadow-height-inc texture-interpolation-dec texture-interpolation-inc
             backlight-percent-dec backlight-percent-inc reset-filters
+            source-primaries-dec source-primaries-inc display-primaries-dec display-primaries-inc white-point-dec white-point-inc color-temperature-dec color-temperature-inc
+            lut-stage-dec lut-stage-inc lut-interpolation-dec lut-interpolation-inc lut-strength-dec lut-strength-inc
+            source-transfer-dec source-transfer-inc display-transfer-dec display-transfer-inc transfer-power-dec transfer-power-inc
+            tv-black-level-dec tv-black-level-inc tv-picture-dec tv-picture-inc tv-color-dec tv-color-inc tv-tint-dec tv-tint-inc tv-sharpness-dec tv-sharpness-inc
+            color-vision-dec color-vision-inc color-vision-severity-dec color-vision-severity-inc
+            source-depth-dec source-depth-inc source-dither-dec source-dither-inc
+            dither-blend-dec dither-blend-inc dither-tolerance-dec dither-tolerance-inc dither-contrast-dec dither-contrast-inc
+            tube-uniformity-dec tube-uniformity-inc phosphor-wear-dec phosphor-wear-inc color-drift-dec color-drift-inc burn-in-speed-dec burn-in-speed-inc
+            monochrome-luma-dec monochrome-luma-inc phosphor-type-dec phosphor-type-inc phosphor-bloom-dec phosphor-bloom-inc
+            lcd-palette-dec lcd-palette-inc lcd-response-rise-dec lcd-response-rise-inc lcd-response-fall-dec lcd-response-fall-inc
+            reflective-panel-dec reflective-panel-inc ambient-light-dec ambient-light-inc ambient-elevation-dec ambient-elevation-inc ambient-azimuth-dec ambient-azimuth-inc
+            epaper-gray-levels-dec epaper-gray-levels-inc epaper-ghosting-dec epaper-ghosting-inc
+            vector-beam-width-dec vector-beam-width-inc vector-hot-spot-dec vector-hot-spot-inc vector-persistence-dec vector-persistence-inc vector-bloom-dec vector-bloom-inc
             camera-movement-mode-dec camera-movement-mode-inc w a s d q e
             arrowup arrowdown arrowleft arrowright + - camera-zoom-dec camera-zoom-inc reset-camera
             move-speed-dec move-speed-inc pixel-speed-dec pixel-speed-inc reset-speeds
-            capture-framebuffer quit-simulation
+            power-toggle degauss capture-framebuffer quit-simulation
         ";
         let mut input = Input::default();
         let mut resources = Resources::default();
 
-        assert_eq!(panel_actions.split_ascii_whitespace().count(), 71);
+        assert_eq!(panel_actions.split_ascii_whitespace().count(), 225);
         for key in panel_actions.split_ascii_whitespace() {
             assert_eq!(
                 trigger_hotkey_action(&mut input, &mut resources, key, Pressed::Yes),
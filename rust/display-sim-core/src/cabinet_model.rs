@@ -0,0 +1,246 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::general_types::Size2D;
+use app_util::{AppError, AppResult};
+
+const DEFAULT_COLOR: [f32; 3] = [0.2, 0.2, 0.2];
+
+/// Floats per vertex in `CabinetModel::vertices`: position, normal and color.
+pub const CABINET_VERTEX_STRIDE: usize = 9;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CabinetModel {
+    /// Triangle list in model space, `CABINET_VERTEX_STRIDE` floats per vertex.
+    pub vertices: Vec<f32>,
+    pub screen_center: [f32; 3],
+    pub screen_size: [f32; 2],
+}
+
+/// Model to world transform: `world = model * scale + translation`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CabinetPlacement {
+    pub scale: f32,
+    pub translation: [f32; 3],
+}
+
+impl CabinetModel {
+    /// Reads a Wavefront OBJ. Faces of the group or object named like "screen"
+    /// are not drawn; they mark where the picture goes.
+    pub fn parse_obj(source: &str) -> AppResult<CabinetModel> {
+        let mut positions: Vec<([f32; 3], [f32; 3])> = Vec::new();
+        let mut normals: Vec<[f32; 3]> = Vec::new();
+        let mut vertices = Vec::new();
+        let mut in_screen = false;
+        let mut screen_min = [f32::MAX; 3];
+        let mut screen_max = [f32::MIN; 3];
+
+        for (line_index, line) in source.lines().enumerate() {
+            let line_number = line_index + 1;
+            let mut words = line.split_whitespace();
+            let values: Vec<&str>;
+            match words.next() {
+                Some("v") => {
+                    values = words.collect();
+                    let numbers = parse_numbers(&values, line_number)?;
+                    if numbers.len() < 3 {
+                        return Err(obj_error(line_number, "vertex needs 3 coordinates"));
+                    }
+                    let color = if numbers.len() >= 6 {
+                        [numbers[3], numbers[4], numbers[5]]
+                    } else {
+                        DEFAULT_COLOR
+                    };
+                    positions.push(([numbers[0], numbers[1], numbers[2]], color));
+                }
+                Some("vn") => {
+                    values = words.collect();
+                    let numbers = parse_numbers(&values, line_number)?;
+                    if numbers.len() < 3 {
+                        return Err(obj_error(line_number, "normal needs 3 coordinates"));
+                    }
+                    normals.push([numbers[0], numbers[1], numbers[2]]);
+                }
+                Some("o") | Some("g") => {
+                    in_screen = words.any(|name| name.to_lowercase().contains("screen"));
+                }
+                Some("f") => {
+                    let mut corners = Vec::new();
+                    for corner in words {
+                        let mut indices = corner.split('/');
+                        let position = resolve_index(indices.next(), positions.len(), line_number)?
+                            .ok_or_else(|| obj_error(line_number, "face corner without a vertex"))?;
+                        let _texture = indices.next();
+                        let normal = resolve_index(indices.next(), normals.len(), line_number)?;
+                        corners.push((position, normal));
+                    }
+                    if corners.len() < 3 {
+                        return Err(obj_error(line_number, "face needs 3 corners"));
+                    }
+                    if in_screen {
+                        for (position, _) in &corners {
+                            let point = positions[*position].0;
+                            for axis in 0..3 {
+                                screen_min[axis] = screen_min[axis].min(point[axis]);
+                                screen_max[axis] = screen_max[axis].max(point[axis]);
+                            }
+                        }
+                        continue;
+                    }
+                    // Faces are convex polygons, so a fan triangulates them.
+                    for i in 1..corners.len() - 1 {
+                        let triangle = [corners[0], corners[i], corners[i + 1]];
+                        let flat_normal = face_normal(positions[triangle[0].0].0, positions[triangle[1].0].0, positions[triangle[2].0].0);
+                        for (position, normal) in triangle.iter() {
+                            let (point, color) = positions[*position];
+                            let normal = normal.map(|index| normals[index]).unwrap_or(flat_normal);
+                            vertices.extend_from_slice(&point);
+                            vertices.extend_from_slice(&normal);
+                            vertices.extend_from_slice(&color);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if screen_min[0] > screen_max[0] {
+            return Err(AppError::new("cabinet model has no 'screen' group".into()));
+        }
+        let screen_size = [screen_max[0] - screen_min[0], screen_max[1] - screen_min[1]];
+        if screen_size[0] <= 0.0 || screen_size[1] <= 0.0 {
+            return Err(AppError::new("cabinet model screen has an empty area".into()));
+        }
+        Ok(CabinetModel {
+            vertices,
+            screen_center: [(screen_min[0] + screen_max[0]) * 0.5, (screen_min[1] + screen_max[1]) * 0.5, screen_max[2]],
+            screen_size,
+        })
+    }
+
+    /// Scales the model until its screen contains the whole simulated
+    /// screen, and centers both screens at the origin.
+    pub fn placement(&self, screen_size: Size2D<f32>) -> CabinetPlacement {
+        let scale = (screen_size.width / self.screen_size[0]).max(screen_size.height / self.screen_size[1]);
+        CabinetPlacement {
+            scale,
+            translation: [-self.screen_center[0] * scale, -self.screen_center[1] * scale, -self.screen_center[2] * scale],
+        }
+    }
+}
+
+fn parse_numbers(values: &[&str], line_number: usize) -> AppResult<Vec<f32>> {
+    values
+        .iter()
+        .map(|value| {
+            value
+                .parse::<f32>()
+                .map_err(|_| obj_error(line_number, &format!("'{}' is not a number", value)))
+        })
+        .collect()
+}
+
+/// OBJ indices start at 1, and negative ones count back from the last element.
+fn resolve_index(index: Option<&str>, len: usize, line_number: usize) -> AppResult<Option<usize>> {
+    let index = match index {
+        None | Some("") => return Ok(None),
+        Some(index) => index
+            .parse::<i64>()
+            .map_err(|_| obj_error(line_number, &format!("'{}' is not an index", index)))?,
+    };
+    let resolved = if index < 0 { len as i64 + index } else { index - 1 };
+    if resolved < 0 || resolved >= len as i64 {
+        return Err(obj_error(line_number, &format!("index {} is out of range", index)));
+    }
+    Ok(Some(resolved as usize))
+}
+
+fn face_normal(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> [f32; 3] {
+    let normal = glm::cross(
+        &glm::vec3(b[0] - a[0], b[1] - a[1], b[2] - a[2]),
+        &glm::vec3(c[0] - a[0], c[1] - a[1], c[2] - a[2]),
+    );
+    let length = glm::length(&normal);
+    if length == 0.0 {
+        return [0.0, 0.0, 1.0];
+    }
+    [normal.x / length, normal.y / length, normal.z / length]
+}
+
+fn obj_error(line_number: usize, message: &str) -> AppError {
+    AppError::new(format!("cabinet model line {}: {}", line_number, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 4x4 front panel with a 2x1.5 screen hole, which sits 0.5 units
+    // above the panel center and slightly behind it.
+    const CABINET: &str = "
+# front panel
+o Cabinet
+v -2 -2 0 1 0 0
+v 2 -2 0 1 0 0
+v 2 2 0 1 0 0
+v -2 2 0
+vn 0 0 1
+f 1//1 2//1 3//1 4//1
+g Screen_Glass
+v -1 0 -0.1
+v 1 0 -0.1
+v 1 1.5 -0.1
+v -1 1.5 -0.1
+f -4 -3 -2 -1
+";
+
+    #[test]
+    fn screen_faces_are_measured_and_not_drawn() {
+        let model = CabinetModel::parse_obj(CABINET).unwrap();
+
+        // The quad of the panel becomes two triangles.
+        assert_eq!(model.vertices.len(), 6 * CABINET_VERTEX_STRIDE);
+        assert_eq!(&model.vertices[0..9], &[-2.0, -2.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(&model.vertices[2 * CABINET_VERTEX_STRIDE + 6..3 * CABINET_VERTEX_STRIDE], &[1.0, 0.0, 0.0]);
+        assert_eq!(&model.vertices[6 * CABINET_VERTEX_STRIDE - 3..], &DEFAULT_COLOR);
+        assert_eq!(model.screen_center, [0.0, 0.75, -0.1]);
+        assert_eq!(model.screen_size, [2.0, 1.5]);
+    }
+
+    #[test]
+    fn the_model_screen_wraps_the_simulated_screen() {
+        let model = CabinetModel::parse_obj(CABINET).unwrap();
+
+        let placement = model.placement(Size2D { width: 320.0, height: 200.0 });
+
+        assert_eq!(placement.scale, 160.0);
+        assert_eq!(placement.translation, [0.0, -120.0, 16.0]);
+    }
+
+    #[test]
+    fn faces_without_normals_get_a_flat_one() {
+        let model = CabinetModel::parse_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\no screen\nf 1 2 3\n").unwrap();
+
+        assert_eq!(&model.vertices[3..6], &[0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn broken_models_are_rejected() {
+        assert!(CabinetModel::parse_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").is_err());
+        assert!(CabinetModel::parse_obj("v 0 0 0\nv 1 0 0\nf 1 2 3\n").is_err());
+        assert!(CabinetModel::parse_obj("v 0 zero 0\n").is_err());
+        assert!(CabinetModel::parse_obj("v 0 0 0\ng screen\nf 1 1 1\n").is_err());
+    }
+}
@@ -0,0 +1,204 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::lut_interpolation::LutInterpolationOptions;
+use crate::ui_controller::lut_stage::LutStageOptions;
+use app_util::{AppError, AppResult};
+
+/// Grading tools export 17 to 65 points per side, but any cube with two or
+/// more interpolates correctly, and tiny ones are handy for hand-written grades.
+pub const LUT_MIN_SIZE: usize = 2;
+pub const LUT_MAX_SIZE: usize = 65;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorLut {
+    pub size: usize,
+    pub domain_min: [f32; 3],
+    pub domain_max: [f32; 3],
+    /// Output colors with red changing fastest, then green, then blue.
+    pub table: Vec<[f32; 3]>,
+}
+
+impl ColorLut {
+    /// Reads a 3D LUT in the Adobe/Resolve `.cube` format.
+    pub fn parse(source: &str) -> AppResult<ColorLut> {
+        let mut size = None;
+        let mut domain_min = [0.0, 0.0, 0.0];
+        let mut domain_max = [1.0, 1.0, 1.0];
+        let mut table = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+            let values: Vec<&str> = words.collect();
+            match keyword {
+                "TITLE" => {}
+                "LUT_1D_SIZE" => return Err(cube_error(line_number, "1D LUTs are not supported, only LUT_3D_SIZE")),
+                "LUT_3D_SIZE" => {
+                    let value = values
+                        .first()
+                        .and_then(|value| value.parse::<usize>().ok())
+                        .ok_or_else(|| cube_error(line_number, "LUT_3D_SIZE needs a whole number"))?;
+                    if !(LUT_MIN_SIZE..=LUT_MAX_SIZE).contains(&value) {
+                        return Err(cube_error(
+                            line_number,
+                            &format!("LUT_3D_SIZE {} is outside {}..{}", value, LUT_MIN_SIZE, LUT_MAX_SIZE),
+                        ));
+                    }
+                    size = Some(value);
+                }
+                "DOMAIN_MIN" => domain_min = parse_triplet(&values, line_number)?,
+                "DOMAIN_MAX" => domain_max = parse_triplet(&values, line_number)?,
+                _ if keyword.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.') => {
+                    if size.is_none() {
+                        return Err(cube_error(line_number, "table data before LUT_3D_SIZE"));
+                    }
+                    let mut triplet = vec![keyword];
+                    triplet.extend(values);
+                    table.push(parse_triplet(&triplet, line_number)?);
+                }
+                _ => return Err(cube_error(line_number, &format!("unknown keyword '{}'", keyword))),
+            }
+        }
+        let size = size.ok_or_else(|| AppError::new("LUT file has no LUT_3D_SIZE".into()))?;
+        let expected = size * size * size;
+        if table.len() != expected {
+            return Err(AppError::new(format!(
+                "LUT file has {} table entries, LUT_3D_SIZE {} needs {}",
+                table.len(),
+                size,
+                expected
+            )));
+        }
+        if (0..3).any(|channel| domain_max[channel] <= domain_min[channel]) {
+            return Err(AppError::new("LUT file DOMAIN_MAX has to be above DOMAIN_MIN".into()));
+        }
+        Ok(ColorLut {
+            size,
+            domain_min,
+            domain_max,
+            table,
+        })
+    }
+
+    /// Blue slices laid side by side in rows, so that even a 65³ table fits
+    /// in the texture sizes every WebGL2 implementation supports.
+    pub fn atlas_tiles(&self) -> usize {
+        (self.size as f32).sqrt().ceil() as usize
+    }
+
+    /// RGBA texels of the atlas, `atlas_tiles() * size` on each side.
+    pub fn atlas(&self) -> Vec<f32> {
+        let tiles = self.atlas_tiles();
+        let width = tiles * self.size;
+        let mut texels = vec![0.0; width * width * 4];
+        for (index, color) in self.table.iter().enumerate() {
+            let red = index % self.size;
+            let green = (index / self.size) % self.size;
+            let blue = index / (self.size * self.size);
+            let x = red + (blue % tiles) * self.size;
+            let y = green + (blue / tiles) * self.size;
+            let texel = (y * width + x) * 4;
+            texels[texel..texel + 3].copy_from_slice(color);
+            texels[texel + 3] = 1.0;
+        }
+        texels
+    }
+}
+
+/// Where and how the loaded table is applied to the pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LutGrading {
+    pub stage: LutStageOptions,
+    pub interpolation: LutInterpolationOptions,
+    pub strength: f32,
+}
+
+fn parse_triplet(values: &[&str], line_number: usize) -> AppResult<[f32; 3]> {
+    if values.len() != 3 {
+        return Err(cube_error(line_number, &format!("expected 3 numbers, found {}", values.len())));
+    }
+    let mut triplet = [0.0; 3];
+    for (component, value) in triplet.iter_mut().zip(values) {
+        *component = value
+            .parse::<f32>()
+            .map_err(|_| cube_error(line_number, &format!("'{}' is not a number", value)))?;
+    }
+    Ok(triplet)
+}
+
+fn cube_error(line_number: usize, message: &str) -> AppError {
+    AppError::new(format!("LUT file line {}: {}", line_number, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVERT: &str = "
+TITLE \"invert\"
+# red changes fastest
+LUT_3D_SIZE 2
+1 1 1
+0 1 1
+1 0 1
+0 0 1
+1 1 0
+0 1 0
+1 0 0
+0 0 0
+";
+
+    #[test]
+    fn parses_a_cube_file_in_red_major_order() -> AppResult<()> {
+        let lut = ColorLut::parse(INVERT)?;
+
+        assert_eq!(lut.size, 2);
+        assert_eq!(lut.domain_max, [1.0, 1.0, 1.0]);
+        assert_eq!(lut.table[1], [0.0, 1.0, 1.0]);
+        assert_eq!(lut.table[7], [0.0, 0.0, 0.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_files_report_the_line() {
+        let truncated = INVERT.replace("0 0 0\n", "");
+        let bad_number = INVERT.replace("0 1 0", "0 one 0");
+
+        assert_eq!(
+            ColorLut::parse(&truncated).unwrap_err().to_string(),
+            "LUT file has 7 table entries, LUT_3D_SIZE 2 needs 8"
+        );
+        assert_eq!(ColorLut::parse(&bad_number).unwrap_err().to_string(), "LUT file line 10: 'one' is not a number");
+        assert!(ColorLut::parse("LUT_3D_SIZE 129").unwrap_err().to_string().contains("outside"));
+    }
+
+    #[test]
+    fn atlas_tiles_blue_slices() -> AppResult<()> {
+        let lut = ColorLut::parse(INVERT)?;
+
+        let atlas = lut.atlas();
+
+        // Two tiles per row: the blue=1 slice starts at x=2.
+        assert_eq!(lut.atlas_tiles(), 2);
+        assert_eq!(atlas.len(), 4 * 4 * 4);
+        assert_eq!(&atlas[2 * 4..3 * 4], &[1.0, 1.0, 0.0, 1.0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::color_vision::ColorVisionOptions;
+
+// Dichromat matrices of Machado, Oliveira and Fernandes (2009), on linear RGB.
+const PROTANOPIA: [[f32; 3]; 3] = [
+    [0.152_286, 1.052_583, -0.204_868],
+    [0.114_503, 0.786_281, 0.099_216],
+    [-0.003_882, -0.048_116, 1.051_998],
+];
+const DEUTERANOPIA: [[f32; 3]; 3] = [
+    [0.367_322, 0.860_646, -0.227_968],
+    [0.280_085, 0.672_501, 0.047_413],
+    [-0.011_820, 0.042_940, 0.968_881],
+];
+const TRITANOPIA: [[f32; 3]; 3] = [
+    [1.255_528, -0.076_749, -0.178_779],
+    [-0.078_411, 0.930_809, 0.147_602],
+    [0.004_733, 0.691_367, 0.303_900],
+];
+/// Rod monochromacy sees nothing but luminance.
+const ACHROMATOPSIA: [[f32; 3]; 3] = [[0.2126, 0.7152, 0.0722], [0.2126, 0.7152, 0.0722], [0.2126, 0.7152, 0.0722]];
+const IDENTITY: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorVisionFilter {
+    pub kind: ColorVisionOptions,
+    /// 0 is normal vision, 1 the complete loss of the cone type.
+    pub severity: f32,
+}
+
+impl ColorVisionFilter {
+    pub fn new(kind: ColorVisionOptions, severity: f32) -> Option<ColorVisionFilter> {
+        if kind == ColorVisionOptions::Normal || severity <= 0.0 {
+            return None;
+        }
+        Some(ColorVisionFilter { kind, severity })
+    }
+
+    /// Columns are the output colors of the red, green and blue inputs, like
+    /// the RGB calibration.
+    ///
+    /// Anomalous trichromacy blends towards the dichromat matrix, which
+    /// stays within a few percent of the intermediate tables of the paper.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        let full = match self.kind {
+            ColorVisionOptions::Normal => IDENTITY,
+            ColorVisionOptions::Protan => PROTANOPIA,
+            ColorVisionOptions::Deutan => DEUTERANOPIA,
+            ColorVisionOptions::Tritan => TRITANOPIA,
+            ColorVisionOptions::Achromat => ACHROMATOPSIA,
+        };
+        let severity = self.severity.clamp(0.0, 1.0);
+        let mut columns = [[0.0; 3]; 3];
+        for (row, (full_row, identity_row)) in full.iter().zip(IDENTITY.iter()).enumerate() {
+            for column in 0..3 {
+                columns[column][row] = identity_row[column] + (full_row[column] - identity_row[column]) * severity;
+            }
+        }
+        columns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(matrix: [[f32; 3]; 3], rgb: [f32; 3]) -> [f32; 3] {
+        let mut result = [0.0; 3];
+        for (input, column) in rgb.iter().zip(matrix.iter()) {
+            for row in 0..3 {
+                result[row] += input * column[row];
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn normal_vision_and_zero_severity_do_nothing() {
+        assert_eq!(ColorVisionFilter::new(ColorVisionOptions::Normal, 1.0), None);
+        assert_eq!(ColorVisionFilter::new(ColorVisionOptions::Protan, 0.0), None);
+    }
+
+    #[test]
+    fn every_deficiency_keeps_white_white() {
+        for kind in [
+            ColorVisionOptions::Protan,
+            ColorVisionOptions::Deutan,
+            ColorVisionOptions::Tritan,
+            ColorVisionOptions::Achromat,
+        ] {
+            for severity in [0.3, 1.0] {
+                let white = apply(ColorVisionFilter { kind, severity }.matrix(), [1.0, 1.0, 1.0]);
+                assert!(white.iter().all(|channel| (channel - 1.0).abs() < 1e-3), "{:?} {} {:?}", kind, severity, white);
+            }
+        }
+    }
+
+    #[test]
+    fn protanopia_confuses_red_and_green() {
+        let protan = ColorVisionFilter {
+            kind: ColorVisionOptions::Protan,
+            severity: 1.0,
+        };
+        let red = apply(protan.matrix(), [1.0, 0.0, 0.0]);
+        let green = apply(protan.matrix(), [0.0, 1.0, 0.0]);
+        // Both end up on the same yellowish axis, red a lot darker.
+        assert!(red[0] < 0.2 && red[1] > 0.1);
+        assert!(green[0] > 1.0 && green[1] > 0.7);
+        let achromat = ColorVisionFilter {
+            kind: ColorVisionOptions::Achromat,
+            severity: 1.0,
+        };
+        assert_eq!(apply(achromat.matrix(), [0.0, 1.0, 0.0]), [0.7152, 0.7152, 0.7152]);
+    }
+}
@@ -0,0 +1,218 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::display_primaries::DisplayPrimariesOptions;
+use crate::ui_controller::source_primaries::SourcePrimariesOptions;
+use crate::ui_controller::white_point::WhitePointOptions;
+
+const D65: [f32; 2] = [0.3127, 0.3290];
+const D93: [f32; 2] = [0.2831, 0.2971];
+const ILLUMINANT_C: [f32; 2] = [0.3101, 0.3162];
+
+/// Chromaticities of the red, green and blue primaries and of the white point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorStandard {
+    pub primaries: [[f32; 2]; 3],
+    pub white: [f32; 2],
+}
+
+impl ColorStandard {
+    pub const SRGB: ColorStandard = ColorStandard {
+        primaries: [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]],
+        white: D65,
+    };
+    pub const SMPTE_C: ColorStandard = ColorStandard {
+        primaries: [[0.630, 0.340], [0.310, 0.595], [0.155, 0.070]],
+        white: D65,
+    };
+    pub const EBU: ColorStandard = ColorStandard {
+        primaries: [[0.64, 0.33], [0.29, 0.60], [0.15, 0.06]],
+        white: D65,
+    };
+    pub const NTSC_1953: ColorStandard = ColorStandard {
+        primaries: [[0.67, 0.33], [0.21, 0.71], [0.14, 0.08]],
+        white: ILLUMINANT_C,
+    };
+    pub const NTSC_J: ColorStandard = ColorStandard {
+        primaries: [[0.67, 0.33], [0.21, 0.71], [0.14, 0.08]],
+        white: D93,
+    };
+
+    fn rgb_to_xyz(&self) -> glm::Mat3 {
+        let [r, g, b] = self.primaries.map(xy_to_xyz);
+        let primaries = glm::mat3(r.x, g.x, b.x, r.y, g.y, b.y, r.z, g.z, b.z);
+        // Scale each primary so that full RGB adds up to the white point.
+        let scale = glm::inverse(&primaries) * xy_to_xyz(self.white);
+        primaries * glm::Mat3::from_diagonal(&scale)
+    }
+}
+
+impl From<SourcePrimariesOptions> for ColorStandard {
+    fn from(option: SourcePrimariesOptions) -> Self {
+        match option {
+            SourcePrimariesOptions::Srgb => ColorStandard::SRGB,
+            SourcePrimariesOptions::SmpteC => ColorStandard::SMPTE_C,
+            SourcePrimariesOptions::Ebu => ColorStandard::EBU,
+            SourcePrimariesOptions::Ntsc1953 => ColorStandard::NTSC_1953,
+            SourcePrimariesOptions::NtscJ => ColorStandard::NTSC_J,
+        }
+    }
+}
+
+impl From<DisplayPrimariesOptions> for ColorStandard {
+    fn from(option: DisplayPrimariesOptions) -> Self {
+        match option {
+            DisplayPrimariesOptions::Srgb => ColorStandard::SRGB,
+            DisplayPrimariesOptions::SmpteC => ColorStandard::SMPTE_C,
+            DisplayPrimariesOptions::Ebu => ColorStandard::EBU,
+            DisplayPrimariesOptions::Ntsc1953 => ColorStandard::NTSC_1953,
+            DisplayPrimariesOptions::NtscJ => ColorStandard::NTSC_J,
+        }
+    }
+}
+
+/// How the set draws a picture encoded for other primaries and white point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Colorimetry {
+    pub source: SourcePrimariesOptions,
+    pub display: DisplayPrimariesOptions,
+    pub white_point: WhitePointOptions,
+    pub temperature: f32,
+}
+
+impl Default for Colorimetry {
+    fn default() -> Self {
+        Colorimetry {
+            source: SourcePrimariesOptions::Srgb,
+            display: DisplayPrimariesOptions::Srgb,
+            white_point: WhitePointOptions::D65,
+            temperature: 6500.0,
+        }
+    }
+}
+
+impl Colorimetry {
+    pub fn white(&self) -> [f32; 2] {
+        match self.white_point {
+            WhitePointOptions::D65 => D65,
+            WhitePointOptions::D93 => D93,
+            WhitePointOptions::Custom => daylight_white(self.temperature),
+        }
+    }
+
+    /// Columns are the output colors of the red, green and blue inputs, as
+    /// the RGB calibration controllers expect them.
+    ///
+    /// The source white is adapted to the white of the set, and the display
+    /// primaries are taken at D65 like the screen the simulation is shown on,
+    /// so a D93 set keeps its blue tint.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        let source = ColorStandard::from(self.source);
+        let display = ColorStandard {
+            white: D65,
+            ..ColorStandard::from(self.display)
+        };
+        let matrix = glm::inverse(&display.rgb_to_xyz()) * bradford(source.white, self.white()) * source.rgb_to_xyz();
+        [
+            [matrix[(0, 0)], matrix[(1, 0)], matrix[(2, 0)]],
+            [matrix[(0, 1)], matrix[(1, 1)], matrix[(2, 1)]],
+            [matrix[(0, 2)], matrix[(1, 2)], matrix[(2, 2)]],
+        ]
+    }
+}
+
+fn xy_to_xyz(xy: [f32; 2]) -> glm::Vec3 {
+    glm::vec3(xy[0] / xy[1], 1.0, (1.0 - xy[0] - xy[1]) / xy[1])
+}
+
+/// Chromatic adaptation from one white to another in the Bradford cone space.
+fn bradford(from: [f32; 2], to: [f32; 2]) -> glm::Mat3 {
+    let cone = glm::mat3(0.8951, 0.2664, -0.1614, -0.7502, 1.7135, 0.0367, 0.0389, -0.0685, 1.0296);
+    let from = cone * xy_to_xyz(from);
+    let to = cone * xy_to_xyz(to);
+    let gain = glm::vec3(to.x / from.x, to.y / from.y, to.z / from.z);
+    glm::inverse(&cone) * glm::Mat3::from_diagonal(&gain) * cone
+}
+
+/// Chromaticity of the CIE daylight illuminant at a correlated color temperature.
+fn daylight_white(kelvin: f32) -> [f32; 2] {
+    let t = f64::from(kelvin.clamp(4000.0, 25000.0));
+    let x = if t <= 7000.0 {
+        -4.6070e9 / t.powi(3) + 2.9678e6 / t.powi(2) + 0.09911e3 / t + 0.244063
+    } else {
+        -2.0064e9 / t.powi(3) + 1.9018e6 / t.powi(2) + 0.24748e3 / t + 0.237040
+    };
+    let y = -3.0 * x * x + 2.870 * x - 0.275;
+    [x as f32, y as f32]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(left: [[f32; 3]; 3], right: [[f32; 3]; 3]) {
+        for (left, right) in left.iter().flatten().zip(right.iter().flatten()) {
+            assert!((left - right).abs() < 0.001, "{:?} != {:?}", left, right);
+        }
+    }
+
+    fn apply(matrix: [[f32; 3]; 3], rgb: [f32; 3]) -> [f32; 3] {
+        let mut result = [0.0; 3];
+        for (column, input) in matrix.iter().zip(rgb.iter()) {
+            for (output, component) in result.iter_mut().zip(column.iter()) {
+                *output += component * input;
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn same_standard_at_its_own_white_is_identity() {
+        assert_close(Colorimetry::default().matrix(), [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+    }
+
+    #[test]
+    fn wide_source_primaries_are_more_saturated_on_srgb() {
+        let colorimetry = Colorimetry {
+            source: SourcePrimariesOptions::Ntsc1953,
+            white_point: WhitePointOptions::Custom,
+            temperature: 6774.0,
+            ..Colorimetry::default()
+        };
+
+        let green = apply(colorimetry.matrix(), [0.0, 1.0, 0.0]);
+
+        assert!(green[0] < 0.0 && green[2] < 0.0, "{:?}", green);
+    }
+
+    #[test]
+    fn d93_white_point_turns_white_blue() {
+        let colorimetry = Colorimetry {
+            white_point: WhitePointOptions::D93,
+            ..Colorimetry::default()
+        };
+
+        let white = apply(colorimetry.matrix(), [1.0, 1.0, 1.0]);
+
+        assert!(white[2] > white[1] && white[1] > white[0], "{:?}", white);
+    }
+
+    #[test]
+    fn daylight_locus_matches_d65() {
+        let white = daylight_white(6504.0);
+
+        assert!((white[0] - D65[0]).abs() < 0.001 && (white[1] - D65[1]).abs() < 0.001, "{:?}", white);
+    }
+}
@@ -0,0 +1,142 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::dither_blend::DitherBlendOptions;
+
+/// Blends the 1-pixel patterns games dithered for a tube to blur, and nothing else.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DitherBlendFilter {
+    pub checkerboard: bool,
+    pub vertical_stripes: bool,
+    /// Largest channel difference, from 0 to 1, between pixels that count as
+    /// the same color of a pattern.
+    pub tolerance: f32,
+    /// Smallest channel difference between the two colors of a pattern.
+    pub contrast: f32,
+}
+
+impl DitherBlendFilter {
+    pub fn new(patterns: DitherBlendOptions, tolerance: f32, contrast: f32) -> Option<DitherBlendFilter> {
+        let (checkerboard, vertical_stripes) = match patterns {
+            DitherBlendOptions::Off => return None,
+            DitherBlendOptions::Checkerboard => (true, false),
+            DitherBlendOptions::VerticalStripes => (false, true),
+            DitherBlendOptions::Both => (true, true),
+        };
+        Some(DitherBlendFilter {
+            checkerboard,
+            vertical_stripes,
+            tolerance,
+            contrast,
+        })
+    }
+
+    pub fn process(&self, frame: &[u8], width: u32, height: u32) -> Vec<u8> {
+        let (width, height) = (width as i32, height as i32);
+        let mut result = frame.to_vec();
+        let pixel = |x: i32, y: i32| -> [u8; 3] {
+            let index = ((y.clamp(0, height - 1) * width + x.clamp(0, width - 1)) * 4) as usize;
+            [frame[index], frame[index + 1], frame[index + 2]]
+        };
+        let tolerance = (self.tolerance * 255.0).round() as i32;
+        let contrast = (self.contrast * 255.0).round() as i32;
+        let same = |a: [u8; 3], b: [u8; 3]| distance(a, b) <= tolerance;
+        let differ = |a: [u8; 3], b: [u8; 3]| distance(a, b) > tolerance.max(contrast);
+        // Both patterns need at least two repetitions around the pixel.
+        for y in 2..height - 2 {
+            for x in 2..width - 2 {
+                let center = pixel(x, y);
+                let (left, right, up, down) = (pixel(x - 1, y), pixel(x + 1, y), pixel(x, y - 1), pixel(x, y + 1));
+                let horizontal = same(pixel(x - 2, y), center) && same(pixel(x + 2, y), center) && same(left, right) && differ(center, left);
+                let blended =
+                    if self.checkerboard && horizontal && same(up, down) && same(up, left) && same(pixel(x, y - 2), center) && same(pixel(x, y + 2), center) {
+                        Some(average(&[center, center, center, center, left, right, up, down]))
+                    } else if self.vertical_stripes && horizontal && (same(up, center) || same(down, center)) {
+                        Some(average(&[center, center, left, right]))
+                    } else {
+                        None
+                    };
+                if let Some(color) = blended {
+                    let index = ((y * width + x) * 4) as usize;
+                    result[index..index + 3].copy_from_slice(&color);
+                }
+            }
+        }
+        result
+    }
+}
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> i32 {
+    (0..3).map(|c| (i32::from(a[c]) - i32::from(b[c])).abs()).max().unwrap_or(0)
+}
+
+fn average(colors: &[[u8; 3]]) -> [u8; 3] {
+    let mut result = [0; 3];
+    for (c, channel) in result.iter_mut().enumerate() {
+        let sum: u32 = colors.iter().map(|color| u32::from(color[c])).sum();
+        *channel = ((sum + colors.len() as u32 / 2) / colors.len() as u32) as u8;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_from(width: u32, height: u32, color: impl Fn(u32, u32) -> u8) -> Vec<u8> {
+        let mut frame = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let value = color(x, y);
+                frame.extend_from_slice(&[value, value, value, 255]);
+            }
+        }
+        frame
+    }
+
+    fn red_at(frame: &[u8], width: u32, x: u32, y: u32) -> u8 {
+        frame[((y * width + x) * 4) as usize]
+    }
+
+    #[test]
+    fn checkerboard_blends_into_its_average() {
+        let filter = DitherBlendFilter::new(DitherBlendOptions::Checkerboard, 0.05, 0.1).unwrap();
+        let frame = frame_from(8, 8, |x, y| if (x + y) % 2 == 0 { 0 } else { 200 });
+
+        let result = filter.process(&frame, 8, 8);
+
+        assert_eq!(red_at(&result, 8, 3, 3), 100);
+        assert_eq!(red_at(&result, 8, 4, 3), 100);
+    }
+
+    #[test]
+    fn vertical_stripes_blend_only_when_enabled() {
+        let frame = frame_from(8, 8, |x, _| if x % 2 == 0 { 40 } else { 240 });
+
+        let stripes = DitherBlendFilter::new(DitherBlendOptions::VerticalStripes, 0.05, 0.1).unwrap();
+        assert_eq!(red_at(&stripes.process(&frame, 8, 8), 8, 4, 4), 140);
+
+        let checkerboard = DitherBlendFilter::new(DitherBlendOptions::Checkerboard, 0.05, 0.1).unwrap();
+        assert_eq!(red_at(&checkerboard.process(&frame, 8, 8), 8, 4, 4), 40);
+    }
+
+    #[test]
+    fn single_pixel_lines_stay_crisp() {
+        let filter = DitherBlendFilter::new(DitherBlendOptions::Both, 0.05, 0.1).unwrap();
+        let frame = frame_from(8, 8, |x, _| if x == 4 { 255 } else { 0 });
+
+        assert_eq!(filter.process(&frame, 8, 8), frame);
+    }
+}
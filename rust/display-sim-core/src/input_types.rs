@@ -90,6 +90,8 @@ pub struct Input {
     pub(crate) esc: BooleanButton,
     pub(crate) space: BooleanButton,
     pub(crate) screenshot: BooleanButton,
+    pub(crate) power: BooleanButton,
+    pub(crate) degauss: BooleanButton,
 
     // get_options_to_be_noned
     pub(crate) event_scaling_resolution_width: Option<f32>,
@@ -139,6 +141,8 @@ pub(crate) enum BooleanAction {
     Esc,
     Space,
     Screenshot,
+    Power,
+    Degauss,
     InputFocused,
     CanvasFocused,
     MouseClick,
@@ -0,0 +1,81 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::persistence::ResponseTimes;
+use crate::ui_controller::lcd_palette::LcdPaletteOptions;
+
+/// Colors of the four shades of a monochrome panel, from darkest to lightest.
+pub fn palette_shades(palette: LcdPaletteOptions) -> Option<[[u8; 3]; 4]> {
+    match palette {
+        LcdPaletteOptions::Off => None,
+        LcdPaletteOptions::DmgGreen => Some([[0x0F, 0x38, 0x0F], [0x30, 0x62, 0x30], [0x8B, 0xAC, 0x0F], [0x9B, 0xBC, 0x0F]]),
+        LcdPaletteOptions::PocketGray => Some([[0x22, 0x22, 0x1E], [0x5E, 0x5E, 0x55], [0xA1, 0xA1, 0x93], [0xC4, 0xC4, 0xB5]]),
+        LcdPaletteOptions::LightTeal => Some([[0x00, 0x2B, 0x3A], [0x00, 0x6B, 0x7A], [0x00, 0xB5, 0xB8], [0x3C, 0xEF, 0xD9]]),
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LcdPanel {
+    pub palette: Option<[[u8; 3]; 4]>,
+    pub response: Option<ResponseTimes>,
+}
+
+impl LcdPanel {
+    pub fn is_passthrough(&self) -> bool {
+        self.palette.is_none() && self.response.is_none()
+    }
+
+    /// Quantizes the luma of every pixel to one of the four palette shades.
+    pub fn map_palette(&self, frame: &[u8]) -> Vec<u8> {
+        let shades = match self.palette {
+            Some(shades) => shades,
+            None => return frame.to_vec(),
+        };
+        let mut result = frame.to_vec();
+        for pixel in result.chunks_exact_mut(4) {
+            let luma = 0.299 * f32::from(pixel[0]) + 0.587 * f32::from(pixel[1]) + 0.114 * f32::from(pixel[2]);
+            let shade = shades[(luma * 3.0 / 255.0).round().min(3.0) as usize];
+            pixel[..3].copy_from_slice(&shade);
+        }
+        result
+    }
+}
+
+/// Unlit panel background that shows through the gaps between cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PanelSubstrate {
+    pub color: [f32; 3],
+    pub size: [f32; 2],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_maps_luma_to_four_shades() {
+        let panel = LcdPanel {
+            palette: palette_shades(LcdPaletteOptions::DmgGreen),
+            response: None,
+        };
+
+        let frame = [0, 0, 0, 255, 255, 255, 255, 128, 90, 90, 90, 255];
+
+        assert_eq!(
+            panel.map_palette(&frame),
+            vec![0x0F, 0x38, 0x0F, 255, 0x9B, 0xBC, 0x0F, 128, 0x30, 0x62, 0x30, 255]
+        );
+    }
+}
@@ -20,15 +20,36 @@
 extern crate derive_new;
 
 pub mod app_events;
+pub mod artwork_layout;
 mod boolean_actions;
 mod boolean_button;
+pub mod cabinet_model;
 pub mod camera;
+pub mod color_lut;
+pub mod color_vision;
+pub mod colorimetry;
+pub mod dither_blend;
 mod field_changer;
 pub mod general_types;
 pub mod input_types;
+pub mod lcd_panel;
 mod math;
+pub mod monochrome;
+pub mod persistence;
+pub mod reflective_panel;
+pub mod rf_interference;
+pub mod scan_output;
+pub mod screen_geometry;
+pub mod signal_path;
 pub mod simulation_command;
 pub mod simulation_context;
 pub mod simulation_core_state;
 pub mod simulation_core_ticker;
+pub mod source_quantization;
+pub mod tape_playback;
+pub mod transfer_function;
+pub mod tube_aging;
+pub mod tube_lifecycle;
+pub mod tv_knobs;
 pub mod ui_controller;
+pub mod vector_display;
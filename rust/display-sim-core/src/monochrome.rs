@@ -0,0 +1,148 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::persistence::ResponseTimes;
+use crate::ui_controller::monochrome_luma::MonochromeLumaOptions;
+use crate::ui_controller::phosphor_type::PhosphorTypeOptions;
+
+/// Decay times a glow at full strength takes to fall under half a level,
+/// ln(2 * 255), after which it shows the same as no glow at all.
+const FADE_OUT_DECAYS: f32 = 6.25;
+
+/// Emission color at full excitation and the 1/e decay time in seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Phosphor {
+    pub color: [u8; 3],
+    pub decay: f32,
+}
+
+impl Phosphor {
+    pub fn new(kind: PhosphorTypeOptions) -> Phosphor {
+        match kind {
+            PhosphorTypeOptions::P1Green => Phosphor {
+                color: [0x33, 0xFF, 0x33],
+                decay: 0.025,
+            },
+            PhosphorTypeOptions::P3Amber => Phosphor {
+                color: [0xFF, 0xB0, 0x00],
+                decay: 0.04,
+            },
+            PhosphorTypeOptions::P4White => Phosphor {
+                color: [0xE6, 0xEB, 0xFF],
+                decay: 0.005,
+            },
+            PhosphorTypeOptions::P31Green => Phosphor {
+                color: [0x8C, 0xFF, 0x5A],
+                decay: 0.01,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Monochrome {
+    pub luma: [f32; 3],
+    pub phosphor: Phosphor,
+    pub dt: f32,
+}
+
+impl Monochrome {
+    pub fn new(luma: MonochromeLumaOptions, phosphor: PhosphorTypeOptions, dt: f32) -> Option<Monochrome> {
+        let luma = match luma {
+            MonochromeLumaOptions::Off => return None,
+            MonochromeLumaOptions::Rec601 => [0.299, 0.587, 0.114],
+            MonochromeLumaOptions::Rec709 => [0.2126, 0.7152, 0.0722],
+        };
+        Some(Monochrome {
+            luma,
+            phosphor: Phosphor::new(phosphor),
+            dt,
+        })
+    }
+
+    /// Phosphors light up almost instantly and fade slowly.
+    pub fn response(&self) -> ResponseTimes {
+        ResponseTimes {
+            rise: 0.0,
+            fall: self.phosphor.decay,
+            dt: self.dt,
+        }
+    }
+
+    pub fn process(&self, frame: &[u8]) -> Vec<u8> {
+        let mut result = frame.to_vec();
+        for pixel in result.chunks_exact_mut(4) {
+            let luma = (self.luma[0] * f32::from(pixel[0]) + self.luma[1] * f32::from(pixel[1]) + self.luma[2] * f32::from(pixel[2])) / 255.0;
+            for (channel, emission) in pixel[..3].iter_mut().zip(self.phosphor.color.iter()) {
+                *channel = (luma * f32::from(*emission)).round() as u8;
+            }
+        }
+        result
+    }
+}
+
+/// Seconds the afterglow of the last loaded picture still takes to fade out.
+#[derive(Default)]
+pub struct Afterglow {
+    remaining: f32,
+}
+
+impl Afterglow {
+    /// A new picture lights the phosphor again.
+    pub fn restart(&mut self, phosphor: &Phosphor) {
+        self.remaining = phosphor.decay * FADE_OUT_DECAYS;
+    }
+
+    /// Advances by `dt` seconds. Returns whether the glow was still fading.
+    pub fn fade(&mut self, dt: f32) -> bool {
+        let fading = self.remaining > 0.0;
+        self.remaining = (self.remaining - dt).max(0.0);
+        fading
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luma_is_drawn_in_the_phosphor_color() {
+        let monochrome = Monochrome::new(MonochromeLumaOptions::Rec601, PhosphorTypeOptions::P3Amber, 0.016).unwrap();
+
+        let frame = monochrome.process(&[255, 255, 255, 255, 0, 255, 0, 128]);
+
+        assert_eq!(frame, vec![0xFF, 0xB0, 0x00, 255, 150, 103, 0, 128]);
+    }
+
+    #[test]
+    fn luma_standards_weight_green_differently() {
+        let rec601 = Monochrome::new(MonochromeLumaOptions::Rec601, PhosphorTypeOptions::P4White, 0.016).unwrap();
+        let rec709 = Monochrome::new(MonochromeLumaOptions::Rec709, PhosphorTypeOptions::P4White, 0.016).unwrap();
+
+        assert!(rec709.process(&[0, 255, 0, 255])[1] > rec601.process(&[0, 255, 0, 255])[1]);
+        assert!(Monochrome::new(MonochromeLumaOptions::Off, PhosphorTypeOptions::P1Green, 0.016).is_none());
+    }
+
+    #[test]
+    fn afterglow_fades_out_after_a_few_decay_times() {
+        let mut afterglow = Afterglow::default();
+        assert!(!afterglow.fade(0.016));
+
+        afterglow.restart(&Phosphor::new(PhosphorTypeOptions::P1Green));
+        let ticks = std::iter::from_fn(|| Some(afterglow.fade(0.016))).take_while(|fading| *fading).count();
+
+        assert_eq!(ticks, 10);
+    }
+}
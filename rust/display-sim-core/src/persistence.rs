@@ -0,0 +1,104 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+/// How fast a cell follows its target, as 1/e time constants in seconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ResponseTimes {
+    /// Time to get brighter.
+    pub rise: f32,
+    /// Time to get darker.
+    pub fall: f32,
+    /// Seconds since the last update.
+    pub dt: f32,
+}
+
+/// What the screen is showing, kept between frames so that each subpixel
+/// moves toward the new picture at its own pace.
+#[derive(Default)]
+pub struct Persistence {
+    levels: Vec<f32>,
+}
+
+impl Persistence {
+    pub fn process(&mut self, frame: &[u8], response: &ResponseTimes) -> Vec<u8> {
+        if self.levels.len() != frame.len() {
+            self.levels = frame.iter().map(|value| f32::from(*value)).collect();
+        }
+        let rise = blend_factor(response.rise, response.dt);
+        let fall = blend_factor(response.fall, response.dt);
+        let mut result = Vec::with_capacity(frame.len());
+        for (index, (level, target)) in self.levels.iter_mut().zip(frame).enumerate() {
+            let target = f32::from(*target);
+            if index % 4 == 3 {
+                *level = target;
+            } else {
+                *level += (target - *level) * if target > *level { rise } else { fall };
+            }
+            result.push(level.round() as u8);
+        }
+        result
+    }
+
+    pub fn reset(&mut self) {
+        self.levels.clear();
+    }
+}
+
+fn blend_factor(time_constant: f32, dt: f32) -> f32 {
+    if time_constant <= 0.0 {
+        1.0
+    } else {
+        1.0 - (-dt / time_constant).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subpixels_brighten_and_darken_at_different_speeds() {
+        let mut persistence = Persistence::default();
+        let response = ResponseTimes {
+            rise: 0.01,
+            fall: 0.1,
+            dt: 0.01,
+        };
+        persistence.process(&[0, 255, 0, 255], &response);
+
+        let frame = persistence.process(&[255, 0, 0, 255], &response);
+
+        // One time constant covers 63% of a rise, and a tenth of one about 10% of a fall.
+        assert_eq!(frame, vec![161, 231, 0, 255]);
+    }
+
+    #[test]
+    fn instant_response_shows_the_frame() {
+        let mut persistence = Persistence::default();
+        persistence.process(&[10, 20, 30, 255], &ResponseTimes::default());
+
+        assert_eq!(
+            persistence.process(
+                &[200, 0, 30, 255],
+                &ResponseTimes {
+                    rise: 0.0,
+                    fall: 0.0,
+                    dt: 0.016
+                }
+            ),
+            vec![200, 0, 30, 255]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::reflective_panel::ReflectivePanelOptions;
+
+/// Seconds a partial refresh takes to drive the changed particles.
+const PARTIAL_REFRESH: f32 = 0.3;
+/// Seconds a full refresh takes, flashing black and white before the picture.
+const FULL_REFRESH: f32 = 0.9;
+/// Partial refreshes between full ones, which are the only that clear ghosting.
+const PARTIAL_REFRESHES_PER_FULL: u32 = 6;
+
+/// Panels that emit no light of their own and show what they reflect from the room.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReflectiveKind {
+    /// Matte paper that scatters light evenly.
+    EPaper,
+    /// A reflector behind the liquid crystal that favors the mirror direction.
+    ReflectiveLcd,
+    /// A reflective LCD that also lets a weak backlight through.
+    TransflectiveLcd,
+}
+
+/// Light of the room, consumed by the pixel shaders.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AmbientLighting {
+    pub kind: ReflectiveKind,
+    /// Unit vector pointing at the light, with Z coming out of the screen.
+    pub direction: [f32; 3],
+    pub intensity: f32,
+}
+
+impl AmbientLighting {
+    /// The light comes from `elevation` degrees above the plane of the screen, and
+    /// `azimuth` degrees clockwise from its top.
+    pub fn new(panel: ReflectivePanelOptions, intensity: f32, elevation: f32, azimuth: f32) -> Option<AmbientLighting> {
+        let kind = match panel {
+            ReflectivePanelOptions::Off => return None,
+            ReflectivePanelOptions::EPaper => ReflectiveKind::EPaper,
+            ReflectivePanelOptions::ReflectiveLcd => ReflectiveKind::ReflectiveLcd,
+            ReflectivePanelOptions::TransflectiveLcd => ReflectiveKind::TransflectiveLcd,
+        };
+        let (elevation, azimuth) = (elevation.to_radians(), azimuth.to_radians());
+        Some(AmbientLighting {
+            kind,
+            direction: [elevation.cos() * azimuth.sin(), elevation.cos() * azimuth.cos(), elevation.sin()],
+            intensity,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EPaper {
+    pub gray_levels: u32,
+    /// Part of the previous picture that a partial refresh leaves behind.
+    pub ghosting: f32,
+    pub dt: f32,
+}
+
+impl EPaper {
+    fn quantize(&self, pixel: &[u8]) -> f32 {
+        let luma = (0.299 * f32::from(pixel[0]) + 0.587 * f32::from(pixel[1]) + 0.114 * f32::from(pixel[2])) / 255.0;
+        let steps = self.gray_levels.max(2) as f32 - 1.0;
+        (luma * steps).round() / steps
+    }
+}
+
+/// What the particles show, kept between frames. A refresh in progress has to
+/// finish before the panel takes the next picture, so fast sources skip frames.
+#[derive(Default)]
+pub struct EPaperPanel {
+    shown: Vec<f32>,
+    from: Vec<f32>,
+    target: Vec<f32>,
+    progress: f32,
+    full: bool,
+    partial_refreshes: u32,
+}
+
+impl EPaperPanel {
+    pub fn process(&mut self, frame: &[u8], epaper: &EPaper) -> Vec<u8> {
+        let incoming: Vec<f32> = frame.chunks_exact(4).map(|pixel| epaper.quantize(pixel)).collect();
+        if self.shown.len() != incoming.len() {
+            self.shown = incoming.clone();
+            self.from = incoming.clone();
+            self.target = incoming;
+            self.progress = 1.0;
+        } else if self.progress >= 1.0 && incoming != self.target {
+            self.from = self.shown.clone();
+            self.target = incoming;
+            self.partial_refreshes += 1;
+            self.full = self.partial_refreshes > PARTIAL_REFRESHES_PER_FULL;
+            if self.full {
+                self.partial_refreshes = 0;
+            }
+            self.progress = 0.0;
+        }
+
+        if self.progress < 1.0 {
+            let duration = if self.full { FULL_REFRESH } else { PARTIAL_REFRESH };
+            self.progress = (self.progress + epaper.dt / duration).min(1.0);
+            let t = self.progress;
+            for ((shown, from), target) in self.shown.iter_mut().zip(&self.from).zip(&self.target) {
+                *shown = if self.full {
+                    // Every particle is driven to black, then white, then the picture.
+                    match t {
+                        t if t < 1.0 / 3.0 => from * (1.0 - t * 3.0),
+                        t if t < 2.0 / 3.0 => t * 3.0 - 1.0,
+                        t => 1.0 + (target - 1.0) * (t * 3.0 - 2.0),
+                    }
+                } else if (from - target).abs() > f32::EPSILON {
+                    // Only the particles that change move, and they never fully get there.
+                    let settled = target + epaper.ghosting * (from - target);
+                    from + (settled - from) * t
+                } else {
+                    *from
+                };
+            }
+        }
+
+        let mut result = frame.to_vec();
+        for (pixel, shown) in result.chunks_exact_mut(4).zip(&self.shown) {
+            let value = (shown * 255.0).round().clamp(0.0, 255.0) as u8;
+            pixel[..3].copy_from_slice(&[value; 3]);
+        }
+        result
+    }
+
+    /// Whether the particles are still moving, so that the frame has to be uploaded again.
+    pub fn is_refreshing(&self) -> bool {
+        self.progress < 1.0
+    }
+
+    pub fn reset(&mut self) {
+        *self = EPaperPanel::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPAPER: EPaper = EPaper {
+        gray_levels: 4,
+        ghosting: 0.2,
+        dt: 0.1,
+    };
+
+    fn gray(value: u8) -> Vec<u8> {
+        vec![value, value, value, 255]
+    }
+
+    fn settle(panel: &mut EPaperPanel, frame: &[u8]) -> Vec<u8> {
+        let mut result = panel.process(frame, &EPAPER);
+        while panel.is_refreshing() {
+            result = panel.process(frame, &EPAPER);
+        }
+        result
+    }
+
+    #[test]
+    fn light_over_the_screen_points_out_of_it() {
+        let light = AmbientLighting::new(ReflectivePanelOptions::ReflectiveLcd, 1.0, 90.0, 0.0).unwrap();
+        assert!((light.direction[2] - 1.0).abs() < 1e-6);
+        let light = AmbientLighting::new(ReflectivePanelOptions::EPaper, 1.0, 0.0, 0.0).unwrap();
+        assert!((light.direction[1] - 1.0).abs() < 1e-6);
+        assert_eq!(AmbientLighting::new(ReflectivePanelOptions::Off, 1.0, 45.0, 0.0), None);
+    }
+
+    #[test]
+    fn grays_are_quantized_to_the_panel_levels() {
+        let mut panel = EPaperPanel::default();
+        assert_eq!(panel.process(&gray(100), &EPAPER), gray(85));
+    }
+
+    #[test]
+    fn partial_refreshes_are_slow_and_leave_a_ghost() {
+        let mut panel = EPaperPanel::default();
+        panel.process(&gray(255), &EPAPER);
+        let halfway = panel.process(&gray(0), &EPAPER);
+        assert!(halfway[0] > 51 && halfway[0] < 255);
+        // A new picture waits until the current refresh ends.
+        panel.process(&gray(170), &EPAPER);
+        assert_eq!(settle(&mut panel, &gray(170)), gray(51));
+    }
+
+    #[test]
+    fn full_refreshes_clear_the_ghosting() {
+        let mut panel = EPaperPanel::default();
+        panel.process(&gray(0), &EPAPER);
+        for i in 0..PARTIAL_REFRESHES_PER_FULL {
+            let frame = gray(if i % 2 == 0 { 255 } else { 0 });
+            assert_ne!(settle(&mut panel, &frame), frame);
+        }
+        assert_eq!(settle(&mut panel, &gray(255)), gray(255));
+    }
+}
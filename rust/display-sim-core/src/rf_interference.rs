@@ -0,0 +1,182 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::simulation_context::RandomGenerator;
+
+const FIELDS_PER_SECOND: f64 = 60.0;
+// Lines of vertical blanking that become visible as a black bar while the
+// picture rolls, relative to the active height.
+const ROLL_BLANKING: f32 = 0.08;
+// Rows per second the picture slides at full sync loss, relative to the
+// active height.
+const ROLL_MAX_SPEED: f32 = 1.5;
+
+/// Controller values the receiver reads every tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RfSettings {
+    pub snow: f32,
+    pub hum_bar_strength: f32,
+    pub hum_bar_frequency: f32,
+    pub vertical_roll: f32,
+}
+
+/// Per-frame interference state consumed by the pixel shaders.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RfInterference {
+    pub snow: f32,
+    pub snow_seed: f32,
+    pub hum_bar_strength: f32,
+    pub hum_bar_phase: f32,
+    pub roll_offset: f32,
+    pub roll_blanking: f32,
+}
+
+/// Receiver state carried between ticks. Random draws happen once per field,
+/// so with a seeded generator the same clock replays the same interference.
+#[derive(Default)]
+pub struct RfReceiver {
+    field: Option<u64>,
+    snow_seed: f32,
+    roll_offset: f32,
+    roll_speed: f32,
+}
+
+impl RfReceiver {
+    pub fn update(&mut self, settings: RfSettings, time: f64, dt: f32, image_height: f32, rng: &dyn RandomGenerator) -> RfInterference {
+        let field = (time * 0.001 * FIELDS_PER_SECOND).floor() as u64;
+        if self.field != Some(field) {
+            self.field = Some(field);
+            self.snow_seed = rng.next();
+            // Losing sync makes the picture slip unevenly rather than glide.
+            self.roll_speed = settings.vertical_roll * settings.vertical_roll * ROLL_MAX_SPEED * (0.5 + rng.next());
+        }
+
+        let period = image_height * (1.0 + ROLL_BLANKING);
+        if settings.vertical_roll > 0.0 && period > 0.0 {
+            self.roll_offset = (self.roll_offset + self.roll_speed * image_height * dt) % period;
+        } else {
+            self.roll_offset = 0.0;
+        }
+
+        RfInterference {
+            snow: settings.snow,
+            snow_seed: self.snow_seed,
+            hum_bar_strength: settings.hum_bar_strength,
+            hum_bar_phase: (time * 0.001 * settings.hum_bar_frequency as f64).fract() as f32,
+            roll_offset: self.roll_offset,
+            roll_blanking: image_height * ROLL_BLANKING,
+        }
+    }
+}
+
+/// Multipath reception: a delayed, attenuated copy of the picture reflected
+/// off buildings arrives shortly after the direct signal.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MultipathGhost {
+    pub strength: f32,
+    pub offset: f32,
+}
+
+impl MultipathGhost {
+    pub fn is_passthrough(&self) -> bool {
+        self.strength <= 0.0 || self.offset <= 0.0
+    }
+
+    /// Adds the ghost to an RGBA8 frame, `offset` source pixels to the right.
+    /// The receiver's AGC keeps the overall level. Alpha is preserved.
+    pub fn process(&self, rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+        let mut output = rgba.to_vec();
+        if self.is_passthrough() || width == 0 || height == 0 {
+            return output;
+        }
+        let width = width as usize;
+        let whole = self.offset.floor() as usize;
+        let t = self.offset - self.offset.floor();
+        let gain = 1.0 / (1.0 + self.strength);
+        for (row, source) in output.chunks_exact_mut(width * 4).zip(rgba.chunks_exact(width * 4)) {
+            let delayed = |x: usize, channel: usize| -> f32 {
+                let read = |x: Option<usize>| x.map_or(0.0, |x| source[x * 4 + channel] as f32);
+                let a = x.checked_sub(whole);
+                let b = x.checked_sub(whole + 1);
+                read(a) * (1.0 - t) + read(b) * t
+            };
+            for x in 0..width {
+                for channel in 0..3 {
+                    let value = (source[x * 4 + channel] as f32 + self.strength * delayed(x, channel)) * gain;
+                    row[x * 4 + channel] = value.round().clamp(0.0, 255.0) as u8;
+                }
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation_context::SeededRandomGenerator;
+
+    fn settings(vertical_roll: f32) -> RfSettings {
+        RfSettings {
+            snow: 0.5,
+            hum_bar_strength: 0.5,
+            hum_bar_frequency: 0.5,
+            vertical_roll,
+        }
+    }
+
+    #[test]
+    fn the_same_clock_and_seed_replay_the_same_interference() {
+        let run = || {
+            let rng = SeededRandomGenerator::new(3);
+            let mut receiver = RfReceiver::default();
+            (0..6)
+                .map(|i| receiver.update(settings(0.5), i as f64 * 8.0, 0.008, 240.0, &rng))
+                .collect::<Vec<_>>()
+        };
+        let frames = run();
+        assert_eq!(frames, run());
+        assert_eq!(frames[0].snow_seed, frames[2].snow_seed);
+        assert_ne!(frames[0].snow_seed, frames[3].snow_seed);
+        assert!(frames.windows(2).all(|pair| pair[1].roll_offset > pair[0].roll_offset));
+    }
+
+    #[test]
+    fn hum_bars_roll_at_the_beat_frequency() {
+        let rng = SeededRandomGenerator::new(3);
+        let mut receiver = RfReceiver::default();
+        assert_eq!(receiver.update(settings(0.0), 500.0, 0.0, 240.0, &rng).hum_bar_phase, 0.25);
+        assert_eq!(receiver.update(settings(0.0), 2_000.0, 0.0, 240.0, &rng).hum_bar_phase, 0.0);
+    }
+
+    #[test]
+    fn regaining_sync_snaps_the_picture_back() {
+        let rng = SeededRandomGenerator::new(3);
+        let mut receiver = RfReceiver::default();
+        assert!(receiver.update(settings(1.0), 0.0, 0.1, 240.0, &rng).roll_offset > 0.0);
+        assert_eq!(receiver.update(settings(0.0), 17.0, 0.1, 240.0, &rng).roll_offset, 0.0);
+    }
+
+    #[test]
+    fn ghost_repeats_an_edge_to_the_right() {
+        let mut frame = [0, 0, 0, 255].repeat(16);
+        frame[4 * 4..4 * 4 + 3].copy_from_slice(&[200, 200, 200]);
+        let output = MultipathGhost { strength: 0.5, offset: 3.0 }.process(&frame, 16, 1);
+        assert_eq!(output[4 * 4], 133);
+        assert_eq!(output[7 * 4], 67);
+        assert_eq!(output[6 * 4], 0);
+        assert_eq!(output[7 * 4 + 3], 255);
+    }
+}
@@ -0,0 +1,120 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::scan_output::ScanOutputOptions;
+
+/// Frames a scanned line takes to fade to a third of its brightness.
+const PHOSPHOR_DECAY: f32 = 0.08;
+
+/// Per-refresh state consumed by the pixel shaders.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScanBand {
+    /// Where the beam ends this refresh, from the top (0) to the bottom (1) of the frame.
+    pub position: f32,
+    /// Lines lit at full strength, as a fraction of the frame.
+    pub width: f32,
+    /// How long, as a fraction of the frame, the lines left behind glow. Zero turns them off.
+    pub decay: f32,
+    /// Makes up for each line being lit only part of the time.
+    pub gain: f32,
+}
+
+/// Refresh counter carried between ticks, for displays that refresh several
+/// times per source frame.
+#[derive(Default)]
+pub struct ScanBeam {
+    refresh: u32,
+}
+
+impl ScanBeam {
+    pub fn update(&mut self, mode: ScanOutputOptions, display_refresh: f32, source_refresh: f32) -> Option<ScanBand> {
+        let refreshes = Self::refreshes_per_frame(display_refresh, source_refresh);
+        if mode == ScanOutputOptions::Off || refreshes <= 1 {
+            self.refresh = 0;
+            return None;
+        }
+        let current = self.refresh % refreshes;
+        self.refresh = (current + 1) % refreshes;
+        let n = refreshes as f32;
+        match mode {
+            ScanOutputOptions::Off => None,
+            ScanOutputOptions::RollingScan => {
+                // What a line gives off on the following refreshes while it fades, taken
+                // at the middle of the band.
+                let tail: f32 = (1..refreshes).map(|i| (-(i as f32 - 0.5) / (n * PHOSPHOR_DECAY)).exp()).sum();
+                Some(ScanBand {
+                    position: (current + 1) as f32 / n,
+                    width: 1.0 / n,
+                    decay: PHOSPHOR_DECAY,
+                    gain: n / (1.0 + tail),
+                })
+            }
+            ScanOutputOptions::BlackFrameInsertion => Some(if current == 0 {
+                ScanBand {
+                    position: 1.0,
+                    width: 1.0,
+                    decay: 0.0,
+                    gain: n,
+                }
+            } else {
+                ScanBand {
+                    position: 1.0,
+                    width: 0.0,
+                    decay: 0.0,
+                    gain: 0.0,
+                }
+            }),
+        }
+    }
+
+    fn refreshes_per_frame(display_refresh: f32, source_refresh: f32) -> u32 {
+        if source_refresh <= 0.0 {
+            return 1;
+        }
+        (display_refresh / source_refresh).round().max(1.0) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolling_scan_sweeps_the_frame_once_per_source_frame() {
+        let mut beam = ScanBeam::default();
+        let bands: Vec<_> = (0..8).map(|_| beam.update(ScanOutputOptions::RollingScan, 240.0, 60.0).unwrap()).collect();
+        let positions: Vec<_> = bands.iter().map(|band| band.position).collect();
+        assert_eq!(positions, [0.25, 0.5, 0.75, 1.0, 0.25, 0.5, 0.75, 1.0]);
+        assert!(bands.iter().all(|band| band.width == 0.25));
+        // The fading tail gives back part of the light lost to the dark refreshes.
+        assert!(bands[0].gain > 1.0 && bands[0].gain < 4.0);
+    }
+
+    #[test]
+    fn black_frame_insertion_lights_one_refresh_out_of_each_frame() {
+        let mut beam = ScanBeam::default();
+        let gains: Vec<_> = (0..6)
+            .map(|_| beam.update(ScanOutputOptions::BlackFrameInsertion, 180.0, 60.0).unwrap().gain)
+            .collect();
+        assert_eq!(gains, [3.0, 0.0, 0.0, 3.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn a_display_as_slow_as_the_source_shows_every_line_every_refresh() {
+        let mut beam = ScanBeam::default();
+        assert_eq!(beam.update(ScanOutputOptions::RollingScan, 60.0, 60.0), None);
+        assert_eq!(beam.update(ScanOutputOptions::Off, 240.0, 60.0), None);
+    }
+}
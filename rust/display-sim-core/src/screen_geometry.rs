@@ -0,0 +1,135 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::screen_curvature_kind::ScreenCurvatureKindOptions;
+
+/// Controller values that shape the tube.
+#[derive(Clone, Copy)]
+pub struct ScreenGeometrySettings {
+    pub kind: ScreenCurvatureKindOptions,
+    pub curvature_horizontal: f32,
+    pub curvature_vertical: f32,
+    pub barrel: f32,
+    pub keystone: f32,
+    pub trapezoid: f32,
+    pub tilt_degrees: f32,
+}
+
+/// Parameters of the screen deformation applied in the pixel vertex shader.
+/// Positions are normalized so that the corners of the image sit at a
+/// distance of 1 from its center.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScreenGeometry {
+    /// Depth of the tube face along X and Y. A cylindrical (Trinitron) tube
+    /// only curves horizontally.
+    pub curvature: [f32; 2],
+    pub cylindrical: bool,
+    /// Radial distortion: positive bulges the picture (barrel), negative
+    /// pulls the corners out (pincushion).
+    pub barrel: f32,
+    /// Top edge wider than the bottom one when positive.
+    pub keystone: f32,
+    /// Right edge taller than the left one when positive.
+    pub trapezoid: f32,
+    /// Rotation of the whole picture, in radians.
+    pub tilt: f32,
+}
+
+impl ScreenGeometry {
+    pub fn new(settings: ScreenGeometrySettings) -> Self {
+        let curvature = match settings.kind {
+            ScreenCurvatureKindOptions::Spherical => [settings.curvature_horizontal, settings.curvature_vertical],
+            ScreenCurvatureKindOptions::Cylindrical => [settings.curvature_horizontal, 0.0],
+            ScreenCurvatureKindOptions::Flat | ScreenCurvatureKindOptions::Pulse => [0.0, 0.0],
+        };
+        ScreenGeometry {
+            curvature,
+            cylindrical: matches!(settings.kind, ScreenCurvatureKindOptions::Cylindrical),
+            barrel: settings.barrel,
+            keystone: settings.keystone,
+            trapezoid: settings.trapezoid,
+            tilt: settings.tilt_degrees.to_radians(),
+        }
+    }
+
+    /// Whether the deformed grid leaves the z = 0 plane.
+    pub fn is_curved(&self) -> bool {
+        self.curvature[0] > 0.0 || self.curvature[1] > 0.0
+    }
+}
+
+/// Shape of the visible tube face, applied to every pixel from its position
+/// in the image, so it follows any deformation of the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScreenMask {
+    /// Radius of the corners, as a fraction of the shorter half side.
+    pub corner_radius: f32,
+    /// Width of the darkened border, as a fraction of the shorter half side.
+    pub edge_softness: f32,
+    pub vignette_strength: f32,
+    /// Superellipse exponent of the vignette: 2 is round, higher values get
+    /// closer to the rectangle of the screen.
+    pub vignette_exponent: f32,
+}
+
+impl ScreenMask {
+    pub fn new(corner_radius: f32, edge_softness: f32, vignette_strength: f32, vignette_shape: f32) -> Self {
+        ScreenMask {
+            corner_radius,
+            edge_softness,
+            vignette_strength,
+            vignette_exponent: 2.0 + vignette_shape * 6.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(kind: ScreenCurvatureKindOptions) -> ScreenGeometrySettings {
+        ScreenGeometrySettings {
+            kind,
+            curvature_horizontal: 0.3,
+            curvature_vertical: 0.15,
+            barrel: 0.0,
+            keystone: 0.0,
+            trapezoid: 0.0,
+            tilt_degrees: 90.0,
+        }
+    }
+
+    #[test]
+    fn tube_shape_selects_which_radii_apply() {
+        assert_eq!(ScreenGeometry::new(settings(ScreenCurvatureKindOptions::Spherical)).curvature, [0.3, 0.15]);
+        assert_eq!(ScreenGeometry::new(settings(ScreenCurvatureKindOptions::Cylindrical)).curvature, [0.3, 0.0]);
+        assert!(ScreenGeometry::new(settings(ScreenCurvatureKindOptions::Cylindrical)).cylindrical);
+        for kind in [ScreenCurvatureKindOptions::Flat, ScreenCurvatureKindOptions::Pulse] {
+            assert!(!ScreenGeometry::new(settings(kind)).is_curved());
+        }
+    }
+
+    #[test]
+    fn tilt_is_converted_to_radians() {
+        let geometry = ScreenGeometry::new(settings(ScreenCurvatureKindOptions::Flat));
+        assert_eq!(geometry.tilt, std::f32::consts::FRAC_PI_2);
+    }
+
+    #[test]
+    fn vignette_shape_goes_from_round_to_rectangular() {
+        assert_eq!(ScreenMask::new(0.0, 0.0, 0.5, 0.0).vignette_exponent, 2.0);
+        assert_eq!(ScreenMask::new(0.0, 0.0, 0.5, 1.0).vignette_exponent, 8.0);
+    }
+}
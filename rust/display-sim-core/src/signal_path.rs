@@ -0,0 +1,369 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::{signal_standard::SignalStandardOptions, signal_type::SignalTypeOptions};
+
+// Consoles of the 240p era clock roughly 256 active pixels at 5.37 MHz. Wider
+// sources are treated as the same scanline sampled at a higher pixel clock.
+const REFERENCE_WIDTH: f32 = 256.0;
+const REFERENCE_PIXEL_CLOCK_MHZ: f32 = 5.37;
+const NTSC_SUBCARRIER_MHZ: f32 = 3.579_545;
+const PAL_SUBCARRIER_MHZ: f32 = 4.433_619;
+const NTSC_FRAMES_PER_SECOND: f32 = 59.94;
+const PAL_FRAMES_PER_SECOND: f32 = 50.0;
+
+/// Analog connection the source frame travels through before reaching the
+/// screen. Everything but RGB is encoded to YIQ (NTSC) or YUV (PAL),
+/// band-limited and decoded back on the CPU, once per loaded frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SignalPath {
+    pub kind: SignalTypeOptions,
+    pub standard: SignalStandardOptions,
+    pub crosstalk: f32,
+    /// Frames sent so far, which set the phase of the subcarrier.
+    pub frame: u32,
+}
+
+impl Default for SignalPath {
+    fn default() -> Self {
+        SignalPath {
+            kind: SignalTypeOptions::Rgb,
+            standard: SignalStandardOptions::Ntsc,
+            crosstalk: 0.0,
+            frame: 0,
+        }
+    }
+}
+
+/// Counts the frames sent down the wire at the rate of the standard.
+#[derive(Default)]
+pub struct SignalClock {
+    elapsed: f32,
+    frame: u32,
+}
+
+impl SignalClock {
+    /// Advances by `dt` seconds. Returns the frame being sent once a new one starts.
+    pub fn advance(&mut self, dt: f32, standard: SignalStandardOptions) -> Option<u32> {
+        let rate = match standard {
+            SignalStandardOptions::Ntsc => NTSC_FRAMES_PER_SECOND,
+            SignalStandardOptions::Pal => PAL_FRAMES_PER_SECOND,
+        };
+        self.elapsed += dt;
+        if self.elapsed < 1.0 / rate {
+            return None;
+        }
+        let frames = (self.elapsed * rate).floor();
+        self.elapsed -= frames / rate;
+        self.frame = self.frame.wrapping_add(frames as u32);
+        Some(self.frame)
+    }
+}
+
+struct Bandwidth {
+    luma_mhz: f32,
+    chroma_mhz: [f32; 2],
+    crosstalk_gain: f32,
+}
+
+impl SignalPath {
+    pub fn is_passthrough(&self) -> bool {
+        matches!(self.kind, SignalTypeOptions::Rgb)
+    }
+
+    /// Whether chroma is modulated on a subcarrier that luma can see.
+    pub fn has_subcarrier(&self) -> bool {
+        matches!(self.kind, SignalTypeOptions::Composite | SignalTypeOptions::Rf)
+    }
+
+    fn bandwidth(&self) -> Bandwidth {
+        let pal = matches!(self.standard, SignalStandardOptions::Pal);
+        match self.kind {
+            SignalTypeOptions::Rgb | SignalTypeOptions::SVideo => Bandwidth {
+                luma_mhz: if pal { 5.5 } else { 5.0 },
+                chroma_mhz: [1.3, 1.3],
+                crosstalk_gain: 0.0,
+            },
+            SignalTypeOptions::Composite => Bandwidth {
+                luma_mhz: if pal { 5.0 } else { 4.2 },
+                chroma_mhz: if pal { [1.3, 1.3] } else { [1.3, 0.6] },
+                crosstalk_gain: 1.0,
+            },
+            SignalTypeOptions::Rf => Bandwidth {
+                luma_mhz: 3.0,
+                chroma_mhz: [0.6, 0.6],
+                crosstalk_gain: 1.5,
+            },
+        }
+    }
+
+    /// Runs an RGBA8 frame through the signal chain. Alpha is preserved.
+    pub fn process(&self, rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+        let mut output = rgba.to_vec();
+        if self.is_passthrough() || width == 0 || height == 0 {
+            return output;
+        }
+        let width = width as usize;
+        let pal = matches!(self.standard, SignalStandardOptions::Pal);
+        let bandwidth = self.bandwidth();
+        let pixel_clock = pixel_clock_mhz(width);
+        let subcarrier_per_pixel = if pal { PAL_SUBCARRIER_MHZ } else { NTSC_SUBCARRIER_MHZ } / pixel_clock;
+        // The subcarrier is close to the pixel rate of low resolution sources,
+        // so every pixel is split in enough samples to keep it under Nyquist.
+        let oversampling = (4.0 * subcarrier_per_pixel).ceil().max(1.0) as usize;
+        let samples = width * oversampling;
+        let subcarrier = subcarrier_per_pixel / oversampling as f32;
+        let sigma = |mhz: f32| bandwidth_sigma(width, mhz) * oversampling as f32;
+        let crosstalk = (self.crosstalk * bandwidth.crosstalk_gain).min(1.0);
+        // NTSC frames hold a whole number of lines plus half a subcarrier
+        // cycle, so the phase flips every frame and the dots crawl. PAL takes
+        // four frames to repeat.
+        let frame_phase = if pal {
+            std::f32::consts::FRAC_PI_2 * (self.frame % 4) as f32
+        } else {
+            std::f32::consts::PI * (self.frame % 2) as f32
+        };
+
+        let mut luma = vec![0.0; samples];
+        let mut chroma = [vec![0.0; samples], vec![0.0; samples]];
+        let mut previous_chroma: Option<[Vec<f32>; 2]> = None;
+        for (y, row) in output.chunks_exact_mut(width * 4).enumerate() {
+            for (x, pixel) in row.chunks_exact(4).enumerate() {
+                let [l, c1, c2] = encode(pal, [pixel[0], pixel[1], pixel[2]]);
+                for sample in x * oversampling..(x + 1) * oversampling {
+                    luma[sample] = l;
+                    chroma[0][sample] = c1;
+                    chroma[1][sample] = c2;
+                }
+            }
+            lowpass(&mut chroma[0], sigma(bandwidth.chroma_mhz[0]));
+            lowpass(&mut chroma[1], sigma(bandwidth.chroma_mhz[1]));
+
+            if self.has_subcarrier() {
+                // Composite shares one wire: chroma rides on the subcarrier,
+                // whose phase flips every line on NTSC. PAL flips V instead.
+                let line_phase = frame_phase + if pal { 0.0 } else { std::f32::consts::PI * (y % 2) as f32 };
+                let v_switch = if pal && y % 2 == 1 { -1.0 } else { 1.0 };
+                let carrier = |sample: usize| {
+                    let theta = std::f32::consts::TAU * subcarrier * sample as f32 + line_phase;
+                    (theta.cos(), theta.sin() * v_switch)
+                };
+                let signal = (0..samples)
+                    .map(|sample| {
+                        let (cos, sin) = carrier(sample);
+                        luma[sample] + chroma[0][sample] * cos + chroma[1][sample] * sin
+                    })
+                    .collect::<Vec<_>>();
+                // A notch one subcarrier period wide separates luma; whatever
+                // it leaves behind is demodulated as chroma (rainbowing), and
+                // crosstalk lets the carrier leak into luma (dot crawl).
+                luma = box_filter(&signal, 1.0 / subcarrier);
+                for sample in 0..samples {
+                    let (cos, sin) = carrier(sample);
+                    let carrier_part = signal[sample] - luma[sample];
+                    chroma[0][sample] = 2.0 * carrier_part * cos;
+                    chroma[1][sample] = 2.0 * carrier_part * sin;
+                    luma[sample] += crosstalk * carrier_part;
+                }
+                lowpass(&mut chroma[0], sigma(bandwidth.chroma_mhz[0]));
+                lowpass(&mut chroma[1], sigma(bandwidth.chroma_mhz[1]));
+            }
+            lowpass(&mut luma, sigma(bandwidth.luma_mhz));
+
+            if pal {
+                // The delay line averages chroma with the previous line, which
+                // cancels phase errors at the cost of vertical color detail.
+                let current = chroma.clone();
+                if let Some(previous) = &previous_chroma {
+                    for channel in 0..2 {
+                        for sample in 0..samples {
+                            chroma[channel][sample] = 0.5 * (current[channel][sample] + previous[channel][sample]);
+                        }
+                    }
+                }
+                previous_chroma = Some(current);
+            }
+
+            for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+                let range = x * oversampling..(x + 1) * oversampling;
+                let average = |values: &[f32]| values[range.clone()].iter().sum::<f32>() / oversampling as f32;
+                let rgb = decode(pal, [average(&luma), average(&chroma[0]), average(&chroma[1])]);
+                pixel[0] = rgb[0];
+                pixel[1] = rgb[1];
+                pixel[2] = rgb[2];
+            }
+        }
+        output
+    }
+}
+
+pub(crate) fn pixel_clock_mhz(width: usize) -> f32 {
+    REFERENCE_PIXEL_CLOCK_MHZ * width as f32 / REFERENCE_WIDTH
+}
+
+/// Gaussian sigma, in pixels, of a low-pass whose -3 dB point sits at `mhz`.
+pub(crate) fn bandwidth_sigma(width: usize, mhz: f32) -> f32 {
+    0.19 * pixel_clock_mhz(width) / mhz
+}
+
+pub(crate) fn encode(pal: bool, rgb: [u8; 3]) -> [f32; 3] {
+    let [r, g, b] = [rgb[0] as f32 / 255.0, rgb[1] as f32 / 255.0, rgb[2] as f32 / 255.0];
+    let y = 0.299 * r + 0.587 * g + 0.114 * b;
+    if pal {
+        [y, 0.492 * (b - y), 0.877 * (r - y)]
+    } else {
+        [y, 0.596 * r - 0.274 * g - 0.322 * b, 0.211 * r - 0.523 * g + 0.312 * b]
+    }
+}
+
+pub(crate) fn decode(pal: bool, yc: [f32; 3]) -> [u8; 3] {
+    let [y, c1, c2] = yc;
+    let rgb = if pal {
+        [y + 1.140 * c2, y - 0.395 * c1 - 0.581 * c2, y + 2.032 * c1]
+    } else {
+        [y + 0.956 * c1 + 0.621 * c2, y - 0.272 * c1 - 0.647 * c2, y - 1.106 * c1 + 1.703 * c2]
+    };
+    [to_u8(rgb[0]), to_u8(rgb[1]), to_u8(rgb[2])]
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+/// Gaussian low-pass approximated by three box passes of equal variance.
+pub(crate) fn lowpass(values: &mut Vec<f32>, sigma: f32) {
+    if sigma < 0.05 {
+        return;
+    }
+    for _ in 0..3 {
+        *values = box_filter(values, 2.0 * sigma);
+    }
+}
+
+/// Averages every sample over a window of fractional `length`, treating each
+/// sample as constant across its pixel and clamping at the edges.
+fn box_filter(values: &[f32], length: f32) -> Vec<f32> {
+    let n = values.len();
+    let mut prefix = vec![0.0; n + 1];
+    for (i, value) in values.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + value;
+    }
+    let integral = |t: f32| {
+        let u = t + 0.5;
+        if u <= 0.0 {
+            u * values[0]
+        } else if u >= n as f32 {
+            prefix[n] + (u - n as f32) * values[n - 1]
+        } else {
+            let k = u.floor() as usize;
+            prefix[k] + (u - k as f32) * values[k]
+        }
+    };
+    (0..n)
+        .map(|x| {
+            let center = x as f32;
+            (integral(center + length * 0.5) - integral(center - length * 0.5)) / length
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, rgb: [u8; 3]) -> Vec<u8> {
+        (0..width * height).flat_map(|_| [rgb[0], rgb[1], rgb[2], 255]).collect()
+    }
+
+    fn path(kind: SignalTypeOptions, standard: SignalStandardOptions) -> SignalPath {
+        SignalPath {
+            kind,
+            standard,
+            crosstalk: 0.5,
+            frame: 0,
+        }
+    }
+
+    #[test]
+    fn rgb_passes_the_frame_through_untouched() {
+        let frame = (0..16 * 4).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+        assert_eq!(path(SignalTypeOptions::Rgb, SignalStandardOptions::Ntsc).process(&frame, 4, 4), frame);
+    }
+
+    #[test]
+    fn flat_gray_survives_every_analog_path() {
+        let frame = solid(32, 4, [128, 128, 128]);
+        for kind in [SignalTypeOptions::SVideo, SignalTypeOptions::Composite, SignalTypeOptions::Rf] {
+            for standard in [SignalStandardOptions::Ntsc, SignalStandardOptions::Pal] {
+                let output = path(kind, standard).process(&frame, 32, 4);
+                assert!(
+                    output.iter().zip(&frame).all(|(a, b)| (*a as i32 - *b as i32).abs() <= 1),
+                    "{} {}",
+                    kind,
+                    standard
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn composite_bleeds_color_past_the_edges_of_a_bar() {
+        let mut frame = solid(256, 1, [0, 0, 0]);
+        for x in 100..140 {
+            frame[x * 4..x * 4 + 3].copy_from_slice(&[255, 0, 0]);
+        }
+        let rgb = path(SignalTypeOptions::Rgb, SignalStandardOptions::Ntsc).process(&frame, 256, 1);
+        let composite = path(SignalTypeOptions::Composite, SignalStandardOptions::Ntsc).process(&frame, 256, 1);
+        assert_eq!(rgb[141 * 4], 0);
+        assert!(composite[141 * 4] > 0, "{:?}", &composite[140 * 4..142 * 4]);
+        assert!(composite[120 * 4] > 128 && composite[120 * 4 + 1] < 128, "{:?}", &composite[120 * 4..121 * 4]);
+        assert_eq!(composite[120 * 4 + 3], 255);
+    }
+
+    #[test]
+    fn pal_delay_line_averages_chroma_across_lines() {
+        let mut frame = solid(16, 2, [255, 0, 0]);
+        frame[16 * 4..].copy_from_slice(&solid(16, 1, [0, 0, 255]));
+        let output = path(SignalTypeOptions::SVideo, SignalStandardOptions::Pal).process(&frame, 16, 2);
+        let second_line = &output[16 * 4 + 8 * 4..16 * 4 + 8 * 4 + 3];
+        assert!(second_line[0] > 0 && second_line[2] < 255, "{:?}", second_line);
+    }
+
+    #[test]
+    fn composite_dots_crawl_from_frame_to_frame() {
+        let mut frame = solid(64, 2, [0, 0, 0]);
+        for x in 20..40 {
+            frame[x * 4..x * 4 + 3].copy_from_slice(&[255, 0, 255]);
+        }
+        let at = |standard, frame_number| {
+            let signal = SignalPath {
+                frame: frame_number,
+                ..path(SignalTypeOptions::Composite, standard)
+            };
+            signal.process(&frame, 64, 2)
+        };
+        assert_ne!(at(SignalStandardOptions::Ntsc, 0), at(SignalStandardOptions::Ntsc, 1));
+        assert_eq!(at(SignalStandardOptions::Ntsc, 0), at(SignalStandardOptions::Ntsc, 2));
+        assert_eq!(at(SignalStandardOptions::Pal, 1), at(SignalStandardOptions::Pal, 5));
+    }
+
+    #[test]
+    fn signal_clock_sends_frames_at_the_rate_of_the_standard() {
+        let mut clock = SignalClock::default();
+        assert_eq!(clock.advance(0.01, SignalStandardOptions::Pal), None);
+        assert_eq!(clock.advance(0.01, SignalStandardOptions::Pal), Some(1));
+        assert_eq!(clock.advance(0.05, SignalStandardOptions::Pal), Some(3));
+    }
+}
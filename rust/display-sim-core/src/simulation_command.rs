@@ -106,6 +106,8 @@ pub enum SimulationCommand {
     CustomScalingAspectRatioY(f32),
     CustomScalingStretchNearest(bool),
     ViewportResize(u32, u32),
+    PowerToggle,
+    Degauss,
 }
 
 impl SimulationCommand {
@@ -380,6 +382,8 @@ mod tests {
             SimulationCommand::CustomScalingAspectRatioY(3.0),
             SimulationCommand::CustomScalingStretchNearest(true),
             SimulationCommand::ViewportResize(1_920, 1_080),
+            SimulationCommand::PowerToggle,
+            SimulationCommand::Degauss,
         ];
 
         let json = serde_json::to_string(&commands).unwrap();
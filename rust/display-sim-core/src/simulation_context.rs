@@ -15,6 +15,7 @@
 
 use crate::app_events::{AppEventDispatcher, FakeEventDispatcher};
 use derive_new::new;
+use std::cell::Cell;
 
 #[derive(new)]
 pub struct ConcreteSimulationContext<Dispatcher: AppEventDispatcher, Rnd: RandomGenerator> {
@@ -54,3 +55,30 @@ impl RandomGenerator for FakeRngGenerator {
         0.0
     }
 }
+
+/// Deterministic generator owned by the simulation (SplitMix64), so effects
+/// that consume it replay identically for the same seed on every platform.
+pub struct SeededRandomGenerator {
+    state: Cell<u64>,
+}
+
+impl SeededRandomGenerator {
+    pub fn new(seed: u64) -> Self {
+        SeededRandomGenerator { state: Cell::new(seed) }
+    }
+    pub fn reseed(&self, seed: u64) {
+        self.state.set(seed);
+    }
+}
+
+impl RandomGenerator for SeededRandomGenerator {
+    fn next(&self) -> f32 {
+        let state = self.state.get().wrapping_add(0x9E37_79B9_7F4A_7C15);
+        self.state.set(state);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
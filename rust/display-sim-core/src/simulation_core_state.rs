@@ -19,38 +19,139 @@ use arraygen::Arraygen;
 use enum_len_derive::EnumLen;
 use num_derive::{FromPrimitive, ToPrimitive};
 
+use crate::artwork_layout::{ArtworkLayout, ArtworkQuad};
+use crate::cabinet_model::{CabinetModel, CabinetPlacement};
 use crate::camera::CameraData;
+use crate::color_lut::{ColorLut, LutGrading};
+use crate::color_vision::ColorVisionFilter;
+use crate::colorimetry::Colorimetry;
+use crate::dither_blend::DitherBlendFilter;
 use crate::general_types::Size2D;
+use crate::lcd_panel::{LcdPanel, PanelSubstrate};
+use crate::monochrome::{Afterglow, Monochrome};
+use crate::reflective_panel::{AmbientLighting, EPaper};
+use crate::rf_interference::{MultipathGhost, RfInterference, RfReceiver};
+use crate::scan_output::{ScanBand, ScanBeam};
+use crate::screen_geometry::{ScreenGeometry, ScreenMask};
+use crate::signal_path::{SignalClock, SignalPath};
+use crate::simulation_context::SeededRandomGenerator;
+use crate::source_quantization::SourceQuantization;
+use crate::tape_playback::{TapeDeck, TapePlayback};
+use crate::transfer_function::LinearLight;
+use crate::tube_aging::{BurnInClock, BurnInMap, TubeAging};
+use crate::tube_lifecycle::{LifecycleEffect, TubeLifecycle};
+use crate::tv_knobs::TvKnobs;
 use crate::ui_controller::{
+    ambient_azimuth::AmbientAzimuth,
+    ambient_elevation::AmbientElevation,
+    ambient_light::AmbientLight,
     backlight_percent::BacklightPercent,
+    barrel_distortion::BarrelDistortion,
     blur_passes::BlurPasses,
     brightness_color::BrightnessColor,
+    burn_in_speed::BurnInSpeed,
     color_channels::{ColorChannels, ColorChannelsOptions},
+    color_drift::ColorDrift,
     color_gamma::ColorGamma,
     color_noise::ColorNoise,
+    color_temperature::ColorTemperature,
+    color_vision::{ColorVision, ColorVisionOptions},
+    color_vision_severity::ColorVisionSeverity,
+    convergence_blue_x::ConvergenceBlueX,
+    convergence_blue_y::ConvergenceBlueY,
+    convergence_radial::ConvergenceRadial,
+    convergence_red_x::ConvergenceRedX,
+    convergence_red_y::ConvergenceRedY,
     cur_pixel_horizontal_gap::CurPixelHorizontalGap,
     cur_pixel_spread::CurPixelSpread,
     cur_pixel_vertical_gap::CurPixelVerticalGap,
+    curvature_horizontal::CurvatureHorizontal,
+    curvature_vertical::CurvatureVertical,
+    display_primaries::{DisplayPrimaries, DisplayPrimariesOptions},
+    display_refresh::DisplayRefresh,
+    display_transfer::{DisplayTransfer, DisplayTransferOptions},
+    dither_blend::{DitherBlend, DitherBlendOptions},
+    dither_contrast::DitherContrast,
+    dither_tolerance::DitherTolerance,
+    epaper_ghosting::EpaperGhosting,
+    epaper_gray_levels::EpaperGrayLevels,
     extra_bright::ExtraBright,
     extra_contrast::ExtraContrast,
     filter_preset::{FilterPreset, FilterPresetOptions},
+    ghost_offset::GhostOffset,
+    ghost_strength::GhostStrength,
     horizontal_lpp::HorizontalLpp,
+    hum_bar_frequency::HumBarFrequency,
+    hum_bar_strength::HumBarStrength,
+    interlace_combing::InterlaceCombing,
+    interlace_flicker::InterlaceFlicker,
     internal_resolution::InternalResolution,
+    lcd_palette::{LcdPalette, LcdPaletteOptions},
+    lcd_response_fall::LcdResponseFall,
+    lcd_response_rise::LcdResponseRise,
+    lcd_substrate::LcdSubstrate,
     light_color::LightColor,
+    lut_interpolation::{LutInterpolation, LutInterpolationOptions},
+    lut_stage::{LutStage, LutStageOptions},
+    lut_strength::LutStrength,
+    monochrome_luma::{MonochromeLuma, MonochromeLumaOptions},
+    phosphor_bloom::PhosphorBloom,
+    phosphor_type::{PhosphorType, PhosphorTypeOptions},
+    phosphor_wear::PhosphorWear,
     pixel_geometry_kind::{PixelGeometryKind, PixelGeometryKindOptions},
     pixel_shadow_height::PixelShadowHeight,
-    pixel_shadow_shape_kind::{PixelShadowShapeKind, ShadowShape},
+    pixel_shadow_shape_kind::{PixelShadowShapeKind, ShadowShape, LCD_CELL_SHADOW},
+    reflective_panel::{ReflectivePanel, ReflectivePanelOptions},
+    rf_snow::RfSnow,
     rgb_calibration::{RgbBlueB, RgbBlueG, RgbBlueR, RgbGreenB, RgbGreenG, RgbGreenR, RgbRedB, RgbRedG, RgbRedR},
+    scan_mode::{ScanMode, ScanModeOptions},
+    scan_output::{ScanOutput, ScanOutputOptions},
+    screen_corner_radius::ScreenCornerRadius,
     screen_curvature_kind::{ScreenCurvatureKind, ScreenCurvatureKindOptions},
+    screen_edge_softness::ScreenEdgeSoftness,
+    screen_keystone::ScreenKeystone,
+    screen_tilt::ScreenTilt,
+    screen_trapezoid::ScreenTrapezoid,
+    signal_crosstalk::SignalCrosstalk,
+    signal_standard::{SignalStandard, SignalStandardOptions},
+    signal_type::{SignalType, SignalTypeOptions},
+    source_depth::{SourceDepth, SourceDepthOptions},
+    source_dither::{SourceDither, SourceDitherOptions},
+    source_primaries::{SourcePrimaries, SourcePrimariesOptions},
+    source_refresh::SourceRefresh,
+    source_transfer::{SourceTransfer, SourceTransferOptions},
+    tape_dropouts::TapeDropouts,
+    tape_noise::TapeNoise,
+    tape_speed::{TapeSpeed, TapeSpeedOptions},
+    tape_tracking::TapeTracking,
     texture_interpolation::{TextureInterpolation, TextureInterpolationOptions},
+    transfer_power::TransferPower,
+    tube_uniformity::TubeUniformity,
+    tv_black_level::TvBlackLevel,
+    tv_color::TvColor,
+    tv_picture::TvPicture,
+    tv_sharpness::TvSharpness,
+    tv_tint::TvTint,
+    vector_beam_width::VectorBeamWidth,
+    vector_bloom::VectorBloom,
+    vector_hot_spot::VectorHotSpot,
+    vector_persistence::VectorPersistence,
     vertical_lpp::VerticalLpp,
+    vertical_roll::VerticalRoll,
+    vignette_shape::VignetteShape,
+    vignette_strength::VignetteStrength,
+    white_point::{WhitePoint, WhitePointOptions},
     UiController,
 };
+use crate::vector_display::{VectorBeam, VectorScene};
 
 pub const PIXEL_MANIPULATION_BASE_SPEED: f32 = 20.0;
 pub const TURNING_BASE_SPEED: f32 = 3.0;
 pub const MOVEMENT_BASE_SPEED: f32 = 10.0;
 pub const MOVEMENT_SPEED_FACTOR: f32 = 50.0;
+pub const SIMULATION_RNG_SEED: u64 = 0x00D1_5A11;
+/// The most subpixels a layout draws separately, as in RGBW or PenTile.
+pub const MAX_COLOR_SPLITS: usize = 4;
 
 #[derive(Default, Clone)]
 pub struct VideoInputResources {
@@ -63,7 +164,26 @@ pub struct VideoInputResources {
     pub current_frame: usize,
     pub last_frame_change: f64,
     pub needs_buffer_data_load: bool,
+    /// The source is the same, but a slow panel keeps moving toward it.
+    pub needs_panel_settle: bool,
     pub drawing_activation: bool,
+    pub artwork: Option<ArtworkLayout>,
+    pub cabinet: Option<CabinetModel>,
+    pub color_lut: Option<ColorLut>,
+    /// Changes whenever `color_lut` does, so that the renderer uploads it again.
+    pub color_lut_generation: u32,
+    /// A worn tube to start from.
+    pub burn_in: Option<BurnInMap>,
+    /// Line segments for a vector monitor, drawn instead of the pixels.
+    pub vector: Option<VectorScene>,
+}
+
+impl VideoInputResources {
+    /// Replaces the LUT, or removes it with `None`.
+    pub fn set_color_lut(&mut self, color_lut: Option<ColorLut>) {
+        self.color_lut = color_lut;
+        self.color_lut_generation = self.color_lut_generation.wrapping_add(1);
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -103,6 +223,16 @@ pub struct Resources {
     pub resetted: bool,
     pub quit: bool,
     pub controller_events: HashMap<&'static str, (KeyEventKind, usize)>,
+    pub rng: SeededRandomGenerator,
+    pub signal_clock: SignalClock,
+    pub afterglow: Afterglow,
+    pub burn_in_clock: BurnInClock,
+    pub tape_deck: TapeDeck,
+    pub rf_receiver: RfReceiver,
+    pub tube_lifecycle: TubeLifecycle,
+    pub scan_beam: ScanBeam,
+    /// Colorimetry the RGB calibration was last written from.
+    pub colorimetry: Colorimetry,
 }
 
 impl Default for Resources {
@@ -148,6 +278,15 @@ impl Default for Resources {
                 map
             },
             main: Default::default(),
+            rng: SeededRandomGenerator::new(SIMULATION_RNG_SEED),
+            signal_clock: SignalClock::default(),
+            afterglow: Afterglow::default(),
+            burn_in_clock: BurnInClock::default(),
+            tape_deck: TapeDeck::default(),
+            rf_receiver: RfReceiver::default(),
+            tube_lifecycle: TubeLifecycle::default(),
+            scan_beam: ScanBeam::default(),
+            colorimetry: Colorimetry::default(),
             controllers,
         }
     }
@@ -167,6 +306,15 @@ impl Resources {
             last_second: now,
         };
         self.video = video_input;
+        // Every loaded input replays the same sequence of random effects.
+        self.rng.reseed(SIMULATION_RNG_SEED);
+        self.signal_clock = SignalClock::default();
+        self.afterglow = Afterglow::default();
+        self.burn_in_clock = BurnInClock::default();
+        self.tape_deck = TapeDeck::default();
+        self.rf_receiver = RfReceiver::default();
+        self.tube_lifecycle = TubeLifecycle::default();
+        self.scan_beam = ScanBeam::default();
         for controller in self.controllers.get_ui_controllers_mut().iter_mut() {
             controller.reset_inputs();
         }
@@ -255,6 +403,9 @@ pub struct Controllers {
     pub blur_passes: BlurPasses,
     pub vertical_lpp: VerticalLpp,
     pub horizontal_lpp: HorizontalLpp,
+    pub scan_mode: ScanMode,
+    pub interlace_flicker: InterlaceFlicker,
+    pub interlace_combing: InterlaceCombing,
     pub light_color: LightColor,
     pub brightness_color: BrightnessColor,
     pub extra_bright: ExtraBright,
@@ -266,8 +417,25 @@ pub struct Controllers {
     pub pixels_geometry_kind: PixelGeometryKind,
     pub color_channels: ColorChannels,
     pub screen_curvature_kind: ScreenCurvatureKind,
+    pub curvature_horizontal: CurvatureHorizontal,
+    pub curvature_vertical: CurvatureVertical,
+    pub barrel_distortion: BarrelDistortion,
+    pub screen_keystone: ScreenKeystone,
+    pub screen_trapezoid: ScreenTrapezoid,
+    pub screen_tilt: ScreenTilt,
+    pub screen_corner_radius: ScreenCornerRadius,
+    pub screen_edge_softness: ScreenEdgeSoftness,
+    pub vignette_strength: VignetteStrength,
+    pub vignette_shape: VignetteShape,
     pub pixel_shadow_shape_kind: PixelShadowShapeKind,
     pub backlight_percent: BacklightPercent,
+    pub lcd_palette: LcdPalette,
+    pub lcd_response_rise: LcdResponseRise,
+    pub lcd_response_fall: LcdResponseFall,
+    pub lcd_substrate: LcdSubstrate,
+    pub monochrome_luma: MonochromeLuma,
+    pub phosphor_type: PhosphorType,
+    pub phosphor_bloom: PhosphorBloom,
     pub rgb_red_r: RgbRedR,
     pub rgb_red_g: RgbRedG,
     pub rgb_red_b: RgbRedB,
@@ -278,7 +446,64 @@ pub struct Controllers {
     pub rgb_blue_g: RgbBlueG,
     pub rgb_blue_b: RgbBlueB,
     pub color_gamma: ColorGamma,
+    pub source_primaries: SourcePrimaries,
+    pub display_primaries: DisplayPrimaries,
+    pub white_point: WhitePoint,
+    pub color_temperature: ColorTemperature,
+    pub lut_stage: LutStage,
+    pub lut_interpolation: LutInterpolation,
+    pub lut_strength: LutStrength,
+    pub source_transfer: SourceTransfer,
+    pub display_transfer: DisplayTransfer,
+    pub transfer_power: TransferPower,
+    pub tv_black_level: TvBlackLevel,
+    pub tv_picture: TvPicture,
+    pub tv_color: TvColor,
+    pub tv_tint: TvTint,
+    pub tv_sharpness: TvSharpness,
+    pub color_vision: ColorVision,
+    pub color_vision_severity: ColorVisionSeverity,
+    pub source_depth: SourceDepth,
+    pub source_dither: SourceDither,
+    pub dither_blend: DitherBlend,
+    pub dither_tolerance: DitherTolerance,
+    pub dither_contrast: DitherContrast,
+    pub tube_uniformity: TubeUniformity,
+    pub phosphor_wear: PhosphorWear,
+    pub color_drift: ColorDrift,
+    pub burn_in_speed: BurnInSpeed,
+    pub vector_beam_width: VectorBeamWidth,
+    pub vector_hot_spot: VectorHotSpot,
+    pub vector_persistence: VectorPersistence,
+    pub vector_bloom: VectorBloom,
+    pub reflective_panel: ReflectivePanel,
+    pub ambient_light: AmbientLight,
+    pub ambient_elevation: AmbientElevation,
+    pub ambient_azimuth: AmbientAzimuth,
+    pub epaper_gray_levels: EpaperGrayLevels,
+    pub epaper_ghosting: EpaperGhosting,
+    pub scan_output: ScanOutput,
+    pub display_refresh: DisplayRefresh,
+    pub source_refresh: SourceRefresh,
     pub color_noise: ColorNoise,
+    pub convergence_red_x: ConvergenceRedX,
+    pub convergence_red_y: ConvergenceRedY,
+    pub convergence_blue_x: ConvergenceBlueX,
+    pub convergence_blue_y: ConvergenceBlueY,
+    pub convergence_radial: ConvergenceRadial,
+    pub signal_type: SignalType,
+    pub signal_standard: SignalStandard,
+    pub signal_crosstalk: SignalCrosstalk,
+    pub tape_speed: TapeSpeed,
+    pub tape_noise: TapeNoise,
+    pub tape_tracking: TapeTracking,
+    pub tape_dropouts: TapeDropouts,
+    pub rf_snow: RfSnow,
+    pub hum_bar_strength: HumBarStrength,
+    pub hum_bar_frequency: HumBarFrequency,
+    pub ghost_strength: GhostStrength,
+    pub ghost_offset: GhostOffset,
+    pub vertical_roll: VerticalRoll,
     pub preset_kind: FilterPreset,
 }
 
@@ -290,6 +515,9 @@ impl Default for Controllers {
             blur_passes: 0.into(),
             vertical_lpp: 1.into(),
             horizontal_lpp: 1.into(),
+            scan_mode: ScanModeOptions::Progressive.into(),
+            interlace_flicker: 0.5.into(),
+            interlace_combing: 0.0.into(),
             light_color: 0x00FF_FFFF.into(),
             brightness_color: 0x00FF_FFFF.into(),
             extra_bright: 0.0.into(),
@@ -302,7 +530,24 @@ impl Default for Controllers {
             pixel_shadow_shape_kind: ShadowShape { value: 0 }.into(),
             color_channels: ColorChannelsOptions::Combined.into(),
             screen_curvature_kind: ScreenCurvatureKindOptions::Flat.into(),
+            curvature_horizontal: 0.3.into(),
+            curvature_vertical: 0.3.into(),
+            barrel_distortion: 0.0.into(),
+            screen_keystone: 0.0.into(),
+            screen_trapezoid: 0.0.into(),
+            screen_tilt: 0.0.into(),
+            screen_corner_radius: 0.0.into(),
+            screen_edge_softness: 0.0.into(),
+            vignette_strength: 0.0.into(),
+            vignette_shape: 0.0.into(),
             backlight_percent: 0.0.into(),
+            lcd_palette: LcdPaletteOptions::Off.into(),
+            lcd_response_rise: 0.0.into(),
+            lcd_response_fall: 0.0.into(),
+            lcd_substrate: 0.into(),
+            monochrome_luma: MonochromeLumaOptions::Off.into(),
+            phosphor_type: PhosphorTypeOptions::P1Green.into(),
+            phosphor_bloom: 0.3.into(),
             rgb_red_r: 1.0.into(),
             rgb_red_g: 0.0.into(),
             rgb_red_b: 0.0.into(),
@@ -313,7 +558,64 @@ impl Default for Controllers {
             rgb_blue_g: 0.0.into(),
             rgb_blue_b: 1.0.into(),
             color_gamma: 1.0.into(),
+            source_primaries: SourcePrimariesOptions::Srgb.into(),
+            display_primaries: DisplayPrimariesOptions::Srgb.into(),
+            white_point: WhitePointOptions::D65.into(),
+            color_temperature: 6500.0.into(),
+            lut_stage: LutStageOptions::AfterGamma.into(),
+            lut_interpolation: LutInterpolationOptions::Tetrahedral.into(),
+            lut_strength: 1.0.into(),
+            source_transfer: SourceTransferOptions::Off.into(),
+            display_transfer: DisplayTransferOptions::Srgb.into(),
+            transfer_power: 2.2.into(),
+            tv_black_level: 0.0.into(),
+            tv_picture: 1.0.into(),
+            tv_color: 1.0.into(),
+            tv_tint: 0.0.into(),
+            tv_sharpness: 0.0.into(),
+            color_vision: ColorVisionOptions::Normal.into(),
+            color_vision_severity: 1.0.into(),
+            source_depth: SourceDepthOptions::Off.into(),
+            source_dither: SourceDitherOptions::Off.into(),
+            dither_blend: DitherBlendOptions::Off.into(),
+            dither_tolerance: 0.05.into(),
+            dither_contrast: 0.1.into(),
+            tube_uniformity: 0.0.into(),
+            phosphor_wear: 0.0.into(),
+            color_drift: 0.0.into(),
+            burn_in_speed: 0.0.into(),
+            vector_beam_width: 1.5.into(),
+            vector_hot_spot: 1.0.into(),
+            vector_persistence: 40.0.into(),
+            vector_bloom: 0.6.into(),
+            reflective_panel: ReflectivePanelOptions::Off.into(),
+            ambient_light: 1.0.into(),
+            ambient_elevation: 60.0.into(),
+            ambient_azimuth: 0.0.into(),
+            epaper_gray_levels: 16.0.into(),
+            epaper_ghosting: 0.1.into(),
+            scan_output: ScanOutputOptions::Off.into(),
+            display_refresh: 120.0.into(),
+            source_refresh: 60.0.into(),
             color_noise: 0.0.into(),
+            convergence_red_x: 0.0.into(),
+            convergence_red_y: 0.0.into(),
+            convergence_blue_x: 0.0.into(),
+            convergence_blue_y: 0.0.into(),
+            convergence_radial: 0.0.into(),
+            signal_type: SignalTypeOptions::Rgb.into(),
+            signal_standard: SignalStandardOptions::Ntsc.into(),
+            signal_crosstalk: 0.5.into(),
+            tape_speed: TapeSpeedOptions::Off.into(),
+            tape_noise: 0.3.into(),
+            tape_tracking: 0.2.into(),
+            tape_dropouts: 0.2.into(),
+            rf_snow: 0.0.into(),
+            hum_bar_strength: 0.0.into(),
+            hum_bar_frequency: 0.5.into(),
+            ghost_strength: 0.0.into(),
+            ghost_offset: 4.0.into(),
+            vertical_roll: 0.0.into(),
             preset_kind: FilterPresetOptions::Sharp1.into(),
         };
         controllers.preset_crt_aperture_grille_1();
@@ -328,6 +630,11 @@ impl Controllers {
             FilterPresetOptions::CrtApertureGrille1 => self.preset_crt_aperture_grille_1(),
             FilterPresetOptions::CrtShadowMask1 => self.preset_crt_shadow_mask_1(),
             FilterPresetOptions::CrtShadowMask2 => self.preset_crt_shadow_mask_2(),
+            FilterPresetOptions::LcdDmg => self.preset_lcd_dmg(),
+            FilterPresetOptions::LcdGba => self.preset_lcd_gba(),
+            FilterPresetOptions::LcdGameGear => self.preset_lcd_game_gear(),
+            FilterPresetOptions::VectorBlackWhite => self.preset_vector_black_white(),
+            FilterPresetOptions::VectorColor => self.preset_vector_color(),
             FilterPresetOptions::DemoFlight1 => self.preset_demo_1(),
             FilterPresetOptions::Custom => match previous_custom {
                 Some(_) => {}
@@ -341,6 +648,9 @@ impl Controllers {
         self.blur_passes = 0.into();
         self.vertical_lpp = 1.into();
         self.horizontal_lpp = 1.into();
+        self.scan_mode = ScanModeOptions::Progressive.into();
+        self.interlace_flicker = 0.5.into();
+        self.interlace_combing = 0.0.into();
         self.light_color = 0x00FF_FFFF.into();
         self.brightness_color = 0x00FF_FFFF.into();
         self.extra_bright = 0.0.into();
@@ -352,8 +662,27 @@ impl Controllers {
         self.pixels_geometry_kind = PixelGeometryKindOptions::Squares.into();
         self.pixel_shadow_shape_kind = ShadowShape { value: 0 }.into();
         self.color_channels = ColorChannelsOptions::Combined.into();
-        self.screen_curvature_kind = ScreenCurvatureKindOptions::Flat.into();
+        self.reset_screen_geometry();
+        self.reset_screen_mask();
         self.backlight_percent.value = 0.0;
+        self.reset_lcd_panel();
+        self.reset_color_calibration();
+        self.reset_signal_path();
+        self.reset_tape_playback();
+        self.reset_rf_interference();
+        self.reset_convergence();
+        self.reset_monochrome();
+        self.reset_lut_grading();
+        self.reset_linear_light();
+        self.reset_color_vision();
+        self.reset_source_quantization();
+        self.reset_dither_blend();
+        self.reset_tube_aging();
+        self.reset_scan_output();
+        self.reset_reflective_panel();
+        self.reset_vector_beam();
+        self.reset_tv_knobs();
+        self.color_noise = 0.0.into();
         self.preset_kind = FilterPresetOptions::Sharp1.into();
     }
 
@@ -363,6 +692,9 @@ impl Controllers {
         self.blur_passes = 1.into();
         self.vertical_lpp = 3.into();
         self.horizontal_lpp = 1.into();
+        self.scan_mode = ScanModeOptions::Progressive.into();
+        self.interlace_flicker = 0.5.into();
+        self.interlace_combing = 0.0.into();
         self.light_color = 0x00FF_FFFF.into();
         self.brightness_color = 0x00FF_FFFF.into();
         self.extra_bright = 0.0.into();
@@ -374,8 +706,27 @@ impl Controllers {
         self.pixels_geometry_kind = PixelGeometryKindOptions::Squares.into();
         self.pixel_shadow_shape_kind = ShadowShape { value: 3 }.into();
         self.color_channels = ColorChannelsOptions::Combined.into();
-        self.screen_curvature_kind = ScreenCurvatureKindOptions::Flat.into();
+        self.reset_screen_geometry();
+        self.reset_screen_mask();
         self.backlight_percent.value = 0.5;
+        self.reset_lcd_panel();
+        self.reset_color_calibration();
+        self.reset_signal_path();
+        self.reset_tape_playback();
+        self.reset_rf_interference();
+        self.reset_convergence();
+        self.reset_monochrome();
+        self.reset_lut_grading();
+        self.reset_linear_light();
+        self.reset_color_vision();
+        self.reset_source_quantization();
+        self.reset_dither_blend();
+        self.reset_tube_aging();
+        self.reset_scan_output();
+        self.reset_reflective_panel();
+        self.reset_vector_beam();
+        self.reset_tv_knobs();
+        self.color_noise = 0.0.into();
         self.preset_kind = FilterPresetOptions::CrtApertureGrille1.into();
     }
 
@@ -385,6 +736,9 @@ impl Controllers {
         self.blur_passes = 2.into();
         self.vertical_lpp = 2.into();
         self.horizontal_lpp = 2.into();
+        self.scan_mode = ScanModeOptions::Progressive.into();
+        self.interlace_flicker = 0.5.into();
+        self.interlace_combing = 0.0.into();
         self.light_color = 0x00FF_FFFF.into();
         self.brightness_color = 0x00FF_FFFF.into();
         self.extra_bright = 0.05.into();
@@ -396,8 +750,27 @@ impl Controllers {
         self.pixels_geometry_kind = PixelGeometryKindOptions::Squares.into();
         self.pixel_shadow_shape_kind = ShadowShape { value: 3 }.into();
         self.color_channels = ColorChannelsOptions::Combined.into();
-        self.screen_curvature_kind = ScreenCurvatureKindOptions::Flat.into();
+        self.reset_screen_geometry();
+        self.reset_screen_mask();
         self.backlight_percent.value = 0.25;
+        self.reset_lcd_panel();
+        self.reset_color_calibration();
+        self.reset_signal_path();
+        self.reset_tape_playback();
+        self.reset_rf_interference();
+        self.reset_convergence();
+        self.reset_monochrome();
+        self.reset_lut_grading();
+        self.reset_linear_light();
+        self.reset_color_vision();
+        self.reset_source_quantization();
+        self.reset_dither_blend();
+        self.reset_tube_aging();
+        self.reset_scan_output();
+        self.reset_reflective_panel();
+        self.reset_vector_beam();
+        self.reset_tv_knobs();
+        self.color_noise = 0.0.into();
         self.preset_kind = FilterPresetOptions::CrtShadowMask1.into();
     }
 
@@ -407,6 +780,9 @@ impl Controllers {
         self.blur_passes = 2.into();
         self.vertical_lpp = 1.into();
         self.horizontal_lpp = 2.into();
+        self.scan_mode = ScanModeOptions::Progressive.into();
+        self.interlace_flicker = 0.5.into();
+        self.interlace_combing = 0.0.into();
         self.light_color = 0x00FF_FFFF.into();
         self.brightness_color = 0x00FF_FFFF.into();
         self.extra_bright = 0.05.into();
@@ -418,11 +794,330 @@ impl Controllers {
         self.pixels_geometry_kind = PixelGeometryKindOptions::Squares.into();
         self.pixel_shadow_shape_kind = ShadowShape { value: 3 }.into();
         self.color_channels = ColorChannelsOptions::Combined.into();
-        self.screen_curvature_kind = ScreenCurvatureKindOptions::Flat.into();
+        self.reset_screen_geometry();
+        self.reset_screen_mask();
         self.backlight_percent.value = 0.4;
+        self.reset_lcd_panel();
+        self.reset_color_calibration();
+        self.reset_signal_path();
+        self.reset_tape_playback();
+        self.reset_rf_interference();
+        self.reset_convergence();
+        self.reset_monochrome();
+        self.reset_lut_grading();
+        self.reset_linear_light();
+        self.reset_color_vision();
+        self.reset_source_quantization();
+        self.reset_dither_blend();
+        self.reset_tube_aging();
+        self.reset_scan_output();
+        self.reset_reflective_panel();
+        self.reset_vector_beam();
+        self.reset_tv_knobs();
+        self.color_noise = 0.0.into();
         self.preset_kind = FilterPresetOptions::CrtShadowMask2.into();
     }
 
+    pub fn preset_lcd_dmg(&mut self) {
+        self.preset_lcd_base();
+        self.cur_pixel_vertical_gap = 0.12.into();
+        self.cur_pixel_horizontal_gap = 0.12.into();
+        self.lcd_palette = LcdPaletteOptions::DmgGreen.into();
+        self.lcd_response_rise = 50.0.into();
+        self.lcd_response_fall = 100.0.into();
+        self.lcd_substrate = 0x00A0_AF6E.into();
+        self.preset_kind = FilterPresetOptions::LcdDmg.into();
+    }
+
+    pub fn preset_lcd_gba(&mut self) {
+        self.preset_lcd_base();
+        self.cur_pixel_vertical_gap = 0.2.into();
+        self.cur_pixel_horizontal_gap = 0.2.into();
+        self.lcd_response_rise = 15.0.into();
+        self.lcd_response_fall = 25.0.into();
+        self.lcd_substrate = 0x0030_302C.into();
+        // Unlit reflective panel: washed out primaries and a dark, low-gamma response.
+        self.rgb_red_r = 0.80.into();
+        self.rgb_red_g = 0.135.into();
+        self.rgb_red_b = 0.195.into();
+        self.rgb_green_r = 0.275.into();
+        self.rgb_green_g = 0.64.into();
+        self.rgb_green_b = 0.155.into();
+        self.rgb_blue_r = 0.0.into();
+        self.rgb_blue_g = 0.225.into();
+        self.rgb_blue_b = 0.65.into();
+        self.color_gamma = 1.5.into();
+        self.preset_kind = FilterPresetOptions::LcdGba.into();
+    }
+
+    pub fn preset_lcd_game_gear(&mut self) {
+        self.preset_lcd_base();
+        self.cur_pixel_vertical_gap = 0.15.into();
+        self.cur_pixel_horizontal_gap = 0.15.into();
+        self.lcd_response_rise = 80.0.into();
+        self.lcd_response_fall = 120.0.into();
+        self.lcd_substrate = 0x0010_1010.into();
+        self.color_gamma = 1.2.into();
+        self.backlight_percent.value = 0.2;
+        self.preset_kind = FilterPresetOptions::LcdGameGear.into();
+    }
+
+    fn preset_lcd_base(&mut self) {
+        self.internal_resolution = InternalResolution::default();
+        self.texture_interpolation = TextureInterpolationOptions::Linear.into();
+        self.blur_passes = 0.into();
+        self.vertical_lpp = 1.into();
+        self.horizontal_lpp = 1.into();
+        self.scan_mode = ScanModeOptions::Progressive.into();
+        self.interlace_flicker = 0.5.into();
+        self.interlace_combing = 0.0.into();
+        self.light_color = 0x00FF_FFFF.into();
+        self.brightness_color = 0x00FF_FFFF.into();
+        self.extra_bright = 0.0.into();
+        self.extra_contrast = 1.0.into();
+        self.cur_pixel_spread = 0.0.into();
+        self.pixel_shadow_height = 1.0.into();
+        self.pixels_geometry_kind = PixelGeometryKindOptions::Squares.into();
+        self.pixel_shadow_shape_kind = ShadowShape { value: LCD_CELL_SHADOW }.into();
+        self.color_channels = ColorChannelsOptions::Combined.into();
+        self.reset_screen_geometry();
+        self.reset_screen_mask();
+        self.backlight_percent.value = 0.0;
+        self.reset_lcd_panel();
+        self.reset_color_calibration();
+        self.reset_signal_path();
+        self.reset_tape_playback();
+        self.reset_rf_interference();
+        self.reset_convergence();
+        self.reset_monochrome();
+        self.reset_lut_grading();
+        self.reset_linear_light();
+        self.reset_color_vision();
+        self.reset_source_quantization();
+        self.reset_dither_blend();
+        self.reset_tube_aging();
+        self.reset_scan_output();
+        self.reset_reflective_panel();
+        self.reset_vector_beam();
+        self.reset_tv_knobs();
+        self.color_noise = 0.0.into();
+    }
+
+    /// Asteroids and Battlezone monitors: a fast white phosphor and a thin beam.
+    pub fn preset_vector_black_white(&mut self) {
+        self.preset_vector_base();
+        self.light_color = 0x00E8_F0FF.into();
+        self.vector_beam_width = 1.2.into();
+        self.vector_hot_spot = 1.5.into();
+        self.vector_persistence = 30.0.into();
+        self.vector_bloom = 0.5.into();
+        self.preset_kind = FilterPresetOptions::VectorBlackWhite.into();
+    }
+
+    /// Tempest and Star Wars color XY monitors, with a wider beam through the shadow mask.
+    pub fn preset_vector_color(&mut self) {
+        self.preset_vector_base();
+        self.vector_beam_width = 2.0.into();
+        self.vector_hot_spot = 0.8.into();
+        self.vector_persistence = 15.0.into();
+        self.vector_bloom = 0.8.into();
+        self.preset_kind = FilterPresetOptions::VectorColor.into();
+    }
+
+    fn preset_vector_base(&mut self) {
+        self.internal_resolution = InternalResolution::default();
+        self.texture_interpolation = TextureInterpolationOptions::Linear.into();
+        self.blur_passes = 0.into();
+        self.vertical_lpp = 1.into();
+        self.horizontal_lpp = 1.into();
+        self.scan_mode = ScanModeOptions::Progressive.into();
+        self.interlace_flicker = 0.5.into();
+        self.interlace_combing = 0.0.into();
+        self.light_color = 0x00FF_FFFF.into();
+        self.brightness_color = 0x00FF_FFFF.into();
+        self.extra_bright = 0.0.into();
+        self.extra_contrast = 1.0.into();
+        self.cur_pixel_vertical_gap = 0.0.into();
+        self.cur_pixel_horizontal_gap = 0.0.into();
+        self.cur_pixel_spread = 0.0.into();
+        self.pixel_shadow_height = 1.0.into();
+        self.pixels_geometry_kind = PixelGeometryKindOptions::Squares.into();
+        self.pixel_shadow_shape_kind = ShadowShape { value: 0 }.into();
+        self.color_channels = ColorChannelsOptions::Combined.into();
+        self.reset_screen_geometry();
+        self.reset_screen_mask();
+        self.backlight_percent.value = 0.0;
+        self.reset_lcd_panel();
+        self.reset_color_calibration();
+        self.reset_signal_path();
+        self.reset_tape_playback();
+        self.reset_rf_interference();
+        self.reset_convergence();
+        self.reset_monochrome();
+        self.reset_lut_grading();
+        self.reset_linear_light();
+        self.reset_color_vision();
+        self.reset_source_quantization();
+        self.reset_dither_blend();
+        self.reset_tube_aging();
+        self.reset_scan_output();
+        self.reset_reflective_panel();
+        self.reset_vector_beam();
+        self.reset_tv_knobs();
+        self.color_noise = 0.0.into();
+    }
+
+    pub fn colorimetry(&self) -> Colorimetry {
+        Colorimetry {
+            source: self.source_primaries.value,
+            display: self.display_primaries.value,
+            white_point: self.white_point.value,
+            temperature: self.color_temperature.value,
+        }
+    }
+
+    fn reset_color_calibration(&mut self) {
+        self.source_primaries = SourcePrimariesOptions::Srgb.into();
+        self.display_primaries = DisplayPrimariesOptions::Srgb.into();
+        self.white_point = WhitePointOptions::D65.into();
+        self.color_temperature = 6500.0.into();
+        self.rgb_red_r = 1.0.into();
+        self.rgb_red_g = 0.0.into();
+        self.rgb_red_b = 0.0.into();
+        self.rgb_green_r = 0.0.into();
+        self.rgb_green_g = 1.0.into();
+        self.rgb_green_b = 0.0.into();
+        self.rgb_blue_r = 0.0.into();
+        self.rgb_blue_g = 0.0.into();
+        self.rgb_blue_b = 1.0.into();
+        self.color_gamma = 1.0.into();
+    }
+
+    fn reset_signal_path(&mut self) {
+        self.signal_type = SignalTypeOptions::Rgb.into();
+        self.signal_standard = SignalStandardOptions::Ntsc.into();
+        self.signal_crosstalk = 0.5.into();
+    }
+
+    fn reset_tape_playback(&mut self) {
+        self.tape_speed = TapeSpeedOptions::Off.into();
+        self.tape_noise = 0.3.into();
+        self.tape_tracking = 0.2.into();
+        self.tape_dropouts = 0.2.into();
+    }
+
+    fn reset_rf_interference(&mut self) {
+        self.rf_snow = 0.0.into();
+        self.hum_bar_strength = 0.0.into();
+        self.hum_bar_frequency = 0.5.into();
+        self.ghost_strength = 0.0.into();
+        self.ghost_offset = 4.0.into();
+        self.vertical_roll = 0.0.into();
+    }
+
+    fn reset_convergence(&mut self) {
+        self.convergence_red_x = 0.0.into();
+        self.convergence_red_y = 0.0.into();
+        self.convergence_blue_x = 0.0.into();
+        self.convergence_blue_y = 0.0.into();
+        self.convergence_radial = 0.0.into();
+    }
+
+    fn reset_monochrome(&mut self) {
+        self.monochrome_luma = MonochromeLumaOptions::Off.into();
+        self.phosphor_type = PhosphorTypeOptions::P1Green.into();
+        self.phosphor_bloom = 0.3.into();
+    }
+
+    fn reset_lut_grading(&mut self) {
+        self.lut_stage = LutStageOptions::AfterGamma.into();
+        self.lut_interpolation = LutInterpolationOptions::Tetrahedral.into();
+        self.lut_strength = 1.0.into();
+    }
+
+    fn reset_linear_light(&mut self) {
+        self.source_transfer = SourceTransferOptions::Off.into();
+        self.display_transfer = DisplayTransferOptions::Srgb.into();
+        self.transfer_power = 2.2.into();
+    }
+
+    fn reset_color_vision(&mut self) {
+        self.color_vision = ColorVisionOptions::Normal.into();
+        self.color_vision_severity = 1.0.into();
+    }
+
+    fn reset_source_quantization(&mut self) {
+        self.source_depth = SourceDepthOptions::Off.into();
+        self.source_dither = SourceDitherOptions::Off.into();
+    }
+
+    fn reset_dither_blend(&mut self) {
+        self.dither_blend = DitherBlendOptions::Off.into();
+        self.dither_tolerance = 0.05.into();
+        self.dither_contrast = 0.1.into();
+    }
+
+    fn reset_tube_aging(&mut self) {
+        self.tube_uniformity = 0.0.into();
+        self.phosphor_wear = 0.0.into();
+        self.color_drift = 0.0.into();
+        self.burn_in_speed = 0.0.into();
+    }
+
+    fn reset_scan_output(&mut self) {
+        self.scan_output = ScanOutputOptions::Off.into();
+        self.display_refresh = 120.0.into();
+        self.source_refresh = 60.0.into();
+    }
+
+    fn reset_reflective_panel(&mut self) {
+        self.reflective_panel = ReflectivePanelOptions::Off.into();
+        self.ambient_light = 1.0.into();
+        self.ambient_elevation = 60.0.into();
+        self.ambient_azimuth = 0.0.into();
+        self.epaper_gray_levels = 16.0.into();
+        self.epaper_ghosting = 0.1.into();
+    }
+
+    fn reset_vector_beam(&mut self) {
+        self.vector_beam_width = 1.5.into();
+        self.vector_hot_spot = 1.0.into();
+        self.vector_persistence = 40.0.into();
+        self.vector_bloom = 0.6.into();
+    }
+
+    fn reset_screen_geometry(&mut self) {
+        self.screen_curvature_kind = ScreenCurvatureKindOptions::Flat.into();
+        self.curvature_horizontal = 0.3.into();
+        self.curvature_vertical = 0.3.into();
+        self.barrel_distortion = 0.0.into();
+        self.screen_keystone = 0.0.into();
+        self.screen_trapezoid = 0.0.into();
+        self.screen_tilt = 0.0.into();
+    }
+
+    fn reset_screen_mask(&mut self) {
+        self.screen_corner_radius = 0.0.into();
+        self.screen_edge_softness = 0.0.into();
+        self.vignette_strength = 0.0.into();
+        self.vignette_shape = 0.0.into();
+    }
+
+    fn reset_lcd_panel(&mut self) {
+        self.lcd_palette = LcdPaletteOptions::Off.into();
+        self.lcd_response_rise = 0.0.into();
+        self.lcd_response_fall = 0.0.into();
+        self.lcd_substrate = 0.into();
+    }
+
+    fn reset_tv_knobs(&mut self) {
+        self.tv_black_level = 0.0.into();
+        self.tv_picture = 1.0.into();
+        self.tv_color = 1.0.into();
+        self.tv_tint = 0.0.into();
+        self.tv_sharpness = 0.0.into();
+    }
+
     pub fn preset_demo_1(&mut self) {
         // Flight is a camera program, not a visual preset. Preserve the
         // active filter and geometry configuration so starting a flyby never
@@ -437,10 +1132,14 @@ impl Controllers {
 
 #[derive(Default)]
 pub struct ViewModel {
-    pub screen_curvature_factor: f32,
+    pub screen_geometry: ScreenGeometry,
+    pub screen_mask: ScreenMask,
     pub pixels_pulse: f32,
     pub color_splits: usize,
-    pub light_color: [[f32; 3]; 3],
+    pub color_splits_overlap: bool,
+    pub light_color: [[f32; 3]; MAX_COLOR_SPLITS],
+    /// The split lit by the common part of the three channels, if any.
+    pub white_subpixel: Option<usize>,
     pub light_color_background: [f32; 3],
     pub extra_light: [f32; 3],
     pub ambient_strength: f32,
@@ -448,16 +1147,46 @@ pub struct ViewModel {
     pub pixel_spread: [f32; 2],
     pub pixel_scale_base: [f32; 3],
     pub height_modifier_factor: f32,
-    pub pixel_scale_foreground: Vec<[[f32; 3]; 3]>,
-    pub pixel_offset_foreground: Vec<[[f32; 3]; 3]>,
+    pub pixel_scale_foreground: Vec<[[f32; 3]; MAX_COLOR_SPLITS]>,
+    pub pixel_offset_foreground: Vec<[[f32; 3]; MAX_COLOR_SPLITS]>,
     pub pixel_scale_background: Vec<[f32; 3]>,
     pub pixel_offset_background: Vec<[f32; 3]>,
+    pub convergence_radial: [f32; MAX_COLOR_SPLITS],
+    pub vertical_lpp: usize,
+    pub horizontal_lpp: usize,
+    pub interlace_field: f32,
+    pub interlace_flicker: f32,
+    pub interlace_combing: f32,
+    pub scan_band: Option<ScanBand>,
     pub rgb_red: [f32; 3],
     pub rgb_green: [f32; 3],
     pub rgb_blue: [f32; 3],
     pub color_gamma: f32,
     pub color_noise: f32,
+    pub lut_grading: Option<LutGrading>,
+    pub linear_light: Option<LinearLight>,
+    pub color_vision: Option<ColorVisionFilter>,
+    pub source_quantization: Option<SourceQuantization>,
+    pub dither_blend: Option<DitherBlendFilter>,
+    pub signal_path: SignalPath,
+    pub tv_knobs: TvKnobs,
+    pub tube_aging: TubeAging,
+    /// Hours of use, accelerated, that wear the tube at this tick. They
+    /// build up for a while before the burn-in map takes them.
+    pub burn_in_hours: f32,
+    pub tape_playback: Option<TapePlayback>,
+    pub ghost: MultipathGhost,
+    pub rf_interference: RfInterference,
+    pub lifecycle: LifecycleEffect,
+    pub monochrome: Option<Monochrome>,
+    pub lcd: LcdPanel,
+    pub lcd_substrate: Option<PanelSubstrate>,
+    pub ambient_lighting: Option<AmbientLighting>,
+    pub epaper: Option<EPaper>,
+    pub vector_beam: Option<VectorBeam>,
     pub showing_background: bool,
+    pub artwork: Vec<ArtworkQuad>,
+    pub cabinet: Option<CabinetPlacement>,
     pub time: f64,
 }
 
@@ -483,3 +1212,136 @@ impl std::fmt::Display for ScalingMethod {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_events::AppEventDispatcher;
+    use crate::camera::CameraLockMode;
+    use crate::simulation_context::make_fake_simulation_context;
+    use crate::ui_controller::UiController;
+    use app_util::AppResult;
+    use std::cell::RefCell;
+    use std::fmt::Display;
+
+    #[derive(Default)]
+    struct RecordingDispatcher {
+        events: RefCell<Vec<(&'static str, String)>>,
+    }
+
+    impl AppEventDispatcher for RecordingDispatcher {
+        fn enable_extra_messages(&self, _: bool) {}
+        fn are_extra_messages_enabled(&self) -> bool {
+            true
+        }
+        fn dispatch_log(&self, _: String) {}
+        fn dispatch_string_event(&self, event_id: &'static str, message: &str) {
+            self.events.borrow_mut().push((event_id, message.into()));
+        }
+        fn dispatch_camera_update(&self, _: &glm::Vec3, _: &glm::Vec3, _: &glm::Vec3) {}
+        fn dispatch_change_pixel_width(&self, _: f32) {}
+        fn dispatch_change_camera_zoom(&self, _: f32) {}
+        fn dispatch_change_pixel_speed(&self, _: f32) {}
+        fn dispatch_change_turning_speed(&self, _: f32) {}
+        fn dispatch_change_movement_speed(&self, _: f32) {}
+        fn dispatch_scaling_method(&self, _: ScalingMethod) {}
+        fn dispatch_scaling_resolution_width(&self, _: u32) {}
+        fn dispatch_scaling_resolution_height(&self, _: u32) {}
+        fn dispatch_scaling_aspect_ratio_x(&self, _: f32) {}
+        fn dispatch_scaling_aspect_ratio_y(&self, _: f32) {}
+        fn dispatch_custom_scaling_stretch_nearest(&self, _: bool) {}
+        fn dispatch_exiting_session(&self) {}
+        fn dispatch_toggle_info_panel(&self) {}
+        fn dispatch_fps(&self, _: f32) {}
+        fn dispatch_screenshot(&self, _: i32, _: i32, _: &mut [u8]) -> AppResult<()> {
+            Ok(())
+        }
+        fn dispatch_request_fullscreen(&self) {}
+        fn dispatch_request_pointer_lock(&self) {}
+        fn dispatch_exit_pointer_lock(&self) {}
+        fn dispatch_change_camera_movement_mode(&self, _: CameraLockMode) {}
+        fn dispatch_top_message(&self, _: &str) {}
+        fn dispatch_minimum_value(&self, _: &dyn Display) {}
+        fn dispatch_maximum_value(&self, _: &dyn Display) {}
+    }
+
+    fn snapshot(controllers: &Controllers) -> Vec<(&'static str, String)> {
+        let dispatcher = RecordingDispatcher::default();
+        for controller in controllers.get_ui_controllers().iter() {
+            controller.dispatch_event(&dispatcher);
+        }
+        dispatcher.events.into_inner()
+    }
+
+    fn press(controller: &mut dyn UiController, main: &MainState, increase: bool) -> bool {
+        let ctx = make_fake_simulation_context();
+        let mut read_key = |pressed| {
+            if increase {
+                controller.read_key_inc(pressed)
+            } else {
+                controller.read_key_dec(pressed)
+            }
+        };
+        read_key(true);
+        controller.pre_process_input();
+        let changed = controller.update(main, &ctx);
+        controller.post_process_input();
+        controller.read_key_inc(false);
+        controller.read_key_dec(false);
+        controller.pre_process_input();
+        controller.update(main, &ctx);
+        controller.post_process_input();
+        changed
+    }
+
+    /// Moves every controller away from its default value.
+    fn scrambled_controllers() -> Controllers {
+        let main = MainState {
+            dt: 1.0,
+            filter_speed: PIXEL_MANIPULATION_BASE_SPEED,
+            ..MainState::default()
+        };
+        let mut controllers = Controllers::default();
+        for controller in controllers.get_ui_controllers_mut().iter_mut() {
+            if !press(*controller, &main, true) {
+                press(*controller, &main, false);
+            }
+        }
+        controllers
+    }
+
+    #[test]
+    fn presets_set_every_controller() {
+        let scrambled = scrambled_controllers();
+        let default = snapshot(&Controllers::default());
+        let changed = snapshot(&scrambled).iter().zip(default.iter()).filter(|(a, b)| a != b).count();
+        assert!(
+            changed > default.len() * 3 / 4,
+            "only {} of {} controllers were scrambled",
+            changed,
+            default.len()
+        );
+
+        for preset in [
+            FilterPresetOptions::Sharp1,
+            FilterPresetOptions::CrtApertureGrille1,
+            FilterPresetOptions::CrtShadowMask1,
+            FilterPresetOptions::CrtShadowMask2,
+            FilterPresetOptions::LcdDmg,
+            FilterPresetOptions::LcdGba,
+            FilterPresetOptions::LcdGameGear,
+            FilterPresetOptions::VectorBlackWhite,
+            FilterPresetOptions::VectorColor,
+        ] {
+            let mut over_default = Controllers::default();
+            over_default.preset_factory(preset, &None);
+            let mut over_scrambled = scrambled_controllers();
+            over_scrambled.preset_factory(preset, &None);
+
+            let expected = snapshot(&over_default);
+            let actual = snapshot(&over_scrambled);
+            let leftovers = actual.iter().zip(expected.iter()).filter(|(a, b)| a != b).collect::<Vec<_>>();
+            assert!(leftovers.is_empty(), "{:?} leaves {:?}", preset, leftovers);
+        }
+    }
+}
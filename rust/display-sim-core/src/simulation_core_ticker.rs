@@ -15,24 +15,44 @@
 
 use crate::boolean_actions::{release_controller_hotkey_actions, trigger_hotkey_action, ActionUsed};
 use crate::camera::{CameraData, CameraDirection, CameraLockMode, CameraSystem};
+use crate::color_lut::LutGrading;
+use crate::color_vision::ColorVisionFilter;
+use crate::dither_blend::DitherBlendFilter;
 use crate::field_changer::FieldChanger;
 use crate::general_types::{get_3_f32color_from_int, Size2D};
 use crate::input_types::Input;
+use crate::lcd_panel::{palette_shades, LcdPanel, PanelSubstrate};
 use crate::math::gcd;
+use crate::monochrome::Monochrome;
+use crate::persistence::ResponseTimes;
+use crate::reflective_panel::{AmbientLighting, EPaper};
+use crate::rf_interference::{MultipathGhost, RfSettings};
+use crate::screen_geometry::{ScreenGeometry, ScreenGeometrySettings, ScreenMask};
+use crate::signal_path::SignalPath;
 use crate::simulation_command::{Pressed, SimulationCommand, SimulationCommandBus};
 use crate::simulation_context::SimulationContext;
 use crate::simulation_core_state::{
-    Controllers, InitialParameters, KeyEventKind, LatestCustomScalingChange, Resources, ScalingMethod, MOVEMENT_BASE_SPEED, MOVEMENT_SPEED_FACTOR,
-    PIXEL_MANIPULATION_BASE_SPEED, TURNING_BASE_SPEED,
+    Controllers, InitialParameters, KeyEventKind, LatestCustomScalingChange, Resources, ScalingMethod, MAX_COLOR_SPLITS, MOVEMENT_BASE_SPEED,
+    MOVEMENT_SPEED_FACTOR, PIXEL_MANIPULATION_BASE_SPEED, TURNING_BASE_SPEED,
 };
+use crate::source_quantization::SourceQuantization;
+use crate::tape_playback::TapeSettings;
+use crate::transfer_function::LinearLight;
+use crate::tube_aging::{BurnInClock, TubeAging};
+use crate::tv_knobs::TvKnobs;
 use crate::ui_controller::{
-    color_channels::ColorChannelsOptions,
+    color_channels::{ColorChannelsOptions, SubpixelChannel},
     filter_preset::{FilterPresetOptions, FILTER_PRESET_EVENT_TAG},
     internal_resolution::InternalResolution,
+    lut_stage::LutStageOptions,
     pixel_geometry_kind::PixelGeometryKindOptions,
+    reflective_panel::ReflectivePanelOptions,
+    scan_mode::ScanModeOptions,
     screen_curvature_kind::ScreenCurvatureKindOptions,
+    tape_speed::TapeSpeedOptions,
     UiController,
 };
+use crate::vector_display::VectorBeam;
 use app_util::{AppError, AppResult};
 use derive_new::new;
 
@@ -54,6 +74,9 @@ const FLIGHT_DEMO_OPENING_UP: [f32; 3] = [0.04, 0.26, 0.97];
 // This is where the historical direction intersects the image plane.
 const FLIGHT_DEMO_OPENING_LOOK_TARGET: [f32; 3] = [-57.676_666, -96.903_336, 0.0];
 const FLIGHT_DEMO_REFERENCE_IMAGE_SIZE: [f32; 2] = [256.0, 240.0];
+// Interlaced sources show one field (every other source row) per refresh of
+// a 60 Hz display; the field parity follows the animation clock.
+const INTERLACED_FIELDS_PER_SECOND: f64 = 60.0;
 const CUSTOM_PRESET_MESSAGE: &str = "Now you are in the Custom mode, you may change any filter value you want.";
 
 #[derive(new)]
@@ -165,6 +188,8 @@ impl<'a> SimulationCoreTicker<'a> {
                 SimulationCommand::CustomScalingAspectRatioY(width) => self.input.event_scaling_aspect_ratio_y = Some(width),
                 SimulationCommand::CustomScalingStretchNearest(flag) => self.input.event_custom_scaling_stretch_nearest = Some(flag),
                 SimulationCommand::ViewportResize(width, height) => self.input.event_viewport_resize = Some(Size2D { width, height }),
+                SimulationCommand::PowerToggle => self.res.tube_lifecycle.toggle_power(),
+                SimulationCommand::Degauss => self.res.tube_lifecycle.degauss(),
             };
         }
         self.commands.defer(deferred_keyboard_events);
@@ -242,6 +267,7 @@ impl<'a> SimulationUpdater<'a> {
         self.update_output_scaling();
         self.update_camera();
         self.update_screenshot();
+        self.update_tube_lifecycle();
         if self.res.controllers.preset_kind.value == FilterPresetOptions::DemoFlight1 {
             self.update_demo();
         }
@@ -271,6 +297,16 @@ impl<'a> SimulationUpdater<'a> {
         }
     }
 
+    fn update_tube_lifecycle(&mut self) {
+        if self.input.power.is_just_released() {
+            self.res.tube_lifecycle.toggle_power();
+        }
+        if self.input.degauss.is_just_released() {
+            self.res.tube_lifecycle.degauss();
+        }
+        self.res.main.render.lifecycle = self.res.tube_lifecycle.advance(self.res.main.dt);
+    }
+
     fn update_scaling(&mut self) {
         let ctx = &self.ctx;
         let input = &self.input;
@@ -376,6 +412,7 @@ impl<'a> SimulationUpdater<'a> {
 
     fn update_animation_buffer(&mut self) {
         self.res.video.needs_buffer_data_load = self.res.resetted;
+        self.res.video.needs_panel_settle = false;
         let next_frame_update = self.res.video.last_frame_change + 0.001 * f64::from(self.res.video.steps[self.res.video.current_frame].delay);
         if self.input.now >= next_frame_update {
             self.res.video.last_frame_change = next_frame_update;
@@ -431,6 +468,7 @@ impl<'a> SimulationUpdater<'a> {
         self.update_filter_presets_from_event()?;
         if self.input.reset_filters {
             self.res.controllers = Controllers::default();
+            self.res.colorimetry = self.res.controllers.colorimetry();
             self.change_frontend_input_values();
             self.ctx.dispatcher().dispatch_top_message("All filter options have been reset.");
             return Ok(());
@@ -445,6 +483,7 @@ impl<'a> SimulationUpdater<'a> {
         if self.res.controllers.internal_resolution.changed {
             self.res.scaling.scaling_initialized = false;
         }
+        self.update_colorimetry();
 
         if changed {
             if self.res.controllers.preset_kind.value != FilterPresetOptions::Custom
@@ -461,6 +500,41 @@ impl<'a> SimulationUpdater<'a> {
         Ok(())
     }
 
+    /// Colorimetry has no uniform of its own: a new choice is written into
+    /// the RGB calibration controllers, which stay editable afterwards.
+    fn update_colorimetry(&mut self) {
+        let controllers = &mut self.res.controllers;
+        let colorimetry = controllers.colorimetry();
+        if colorimetry == self.res.colorimetry {
+            return;
+        }
+        self.res.colorimetry = colorimetry;
+        let [red, green, blue] = colorimetry.matrix();
+        controllers.rgb_red_r = red[0].into();
+        controllers.rgb_red_g = red[1].into();
+        controllers.rgb_red_b = red[2].into();
+        controllers.rgb_green_r = green[0].into();
+        controllers.rgb_green_g = green[1].into();
+        controllers.rgb_green_b = green[2].into();
+        controllers.rgb_blue_r = blue[0].into();
+        controllers.rgb_blue_g = blue[1].into();
+        controllers.rgb_blue_b = blue[2].into();
+        let dispatcher = self.ctx.dispatcher();
+        for controller in [
+            &controllers.rgb_red_r as &dyn UiController,
+            &controllers.rgb_red_g,
+            &controllers.rgb_red_b,
+            &controllers.rgb_green_r,
+            &controllers.rgb_green_g,
+            &controllers.rgb_green_b,
+            &controllers.rgb_blue_r,
+            &controllers.rgb_blue_g,
+            &controllers.rgb_blue_b,
+        ] {
+            controller.dispatch_event(dispatcher);
+        }
+    }
+
     fn update_filter_presets_from_event(&mut self) -> AppResult<()> {
         if self.res.controllers.preset_kind.value == self.res.main.current_filter_preset {
             return Ok(());
@@ -486,6 +560,9 @@ impl<'a> SimulationUpdater<'a> {
         self.res
             .controllers
             .preset_factory(self.res.controllers.preset_kind.value, &self.res.saved_filters);
+        // Presets carry their own RGB calibration, which must not be
+        // overwritten as if their colorimetry had just been picked.
+        self.res.colorimetry = self.res.controllers.colorimetry();
         if self.res.controllers.preset_kind.value == FilterPresetOptions::DemoFlight1 {
             self.res.demo_1.needs_initialization = true;
         }
@@ -716,20 +793,44 @@ impl<'a> SimulationUpdater<'a> {
 
         self.update_output_filter_source_colors();
         self.update_output_filter_curvature();
+        self.update_output_filter_monochrome();
         self.update_output_filter_backlight();
+        self.update_output_filter_scan_mode();
+        self.update_output_filter_scan_output();
+        self.update_output_filter_source_quantization();
+        self.update_output_filter_dither_blend();
+        self.update_output_filter_signal_path();
+        self.update_output_filter_tv_knobs();
+        self.update_output_filter_tube_aging();
+        self.update_output_filter_tape_playback();
+        self.update_output_filter_rf_interference();
+        self.update_output_filter_lcd();
+        self.update_output_filter_reflective_panel();
+        self.update_output_vector_beam();
+        self.update_output_artwork();
+        self.update_output_cabinet();
+        // Every picture the phosphor receives glows for a while.
+        if self.res.video.needs_buffer_data_load {
+            if let Some(monochrome) = &self.res.main.render.monochrome {
+                self.res.afterglow.restart(&monochrome.phosphor);
+            }
+        }
 
         let output = &mut self.res.main.render;
         let controllers = &self.res.controllers;
 
         output.ambient_strength = match controllers.pixels_geometry_kind.value {
             PixelGeometryKindOptions::Squares => 1.0,
-            PixelGeometryKindOptions::Cubes => 0.5,
+            PixelGeometryKindOptions::Cubes | PixelGeometryKindOptions::Spheres | PixelGeometryKindOptions::Hexagons | PixelGeometryKindOptions::LedDiodes => {
+                0.5
+            }
         };
         // A flat square grid is one coplanar surface, so perspective cannot
-        // make its cells cross each other. Cubes and deformed square grids can
-        // overlap in screen space and therefore need a depth attachment.
-        output.pixel_have_depth = matches!(controllers.pixels_geometry_kind.value, PixelGeometryKindOptions::Cubes)
-            || !matches!(controllers.screen_curvature_kind.value, ScreenCurvatureKindOptions::Flat);
+        // make its cells cross each other. Solid pixels and deformed square
+        // grids can overlap in screen space and therefore need a depth attachment.
+        output.pixel_have_depth = !matches!(controllers.pixels_geometry_kind.value, PixelGeometryKindOptions::Squares)
+            || output.screen_geometry.is_curved()
+            || matches!(controllers.screen_curvature_kind.value, ScreenCurvatureKindOptions::Pulse);
         output.height_modifier_factor = 1.0 - controllers.pixel_shadow_height.value;
         output.time = self.input.now;
 
@@ -826,7 +927,7 @@ impl<'a> SimulationUpdater<'a> {
 
         self.res.scaling.pixel_width = pixel_width;
 
-        let z = {
+        let mut z = {
             let background_size = Size2D {
                 width: image_width as f32,
                 height: image_height as f32,
@@ -838,8 +939,15 @@ impl<'a> SimulationUpdater<'a> {
                 stretch,
             )
         };
+        let mut center = [0.0, 0.0];
+        if let Some(ref artwork) = self.res.video.artwork {
+            // Back away until the whole artwork fits where the screen alone did.
+            let (artwork_center, scale) = artwork.framing(self.world_screen_size());
+            center = artwork_center;
+            z *= scale;
+        }
         let mut camera = CameraData::new(MOVEMENT_BASE_SPEED * z / MOVEMENT_SPEED_FACTOR, TURNING_BASE_SPEED);
-        camera.set_position(glm::vec3(0.0, 0.0, z));
+        camera.set_position(glm::vec3(center[0], center[1], z));
         self.res.initial_parameters = InitialParameters {
             initial_position_z: z,
             initial_movement_speed: camera.movement_speed,
@@ -847,24 +955,64 @@ impl<'a> SimulationUpdater<'a> {
         self.res.camera = camera;
     }
 
+    fn update_output_artwork(&mut self) {
+        let screen_size = self.world_screen_size();
+        self.res.main.render.artwork = match self.res.video.artwork {
+            Some(ref artwork) => artwork.place(screen_size),
+            None => Vec::new(),
+        };
+    }
+
+    fn update_output_cabinet(&mut self) {
+        let screen_size = self.world_screen_size();
+        self.res.main.render.cabinet = self.res.video.cabinet.as_ref().map(|cabinet| cabinet.placement(screen_size));
+    }
+
+    /// World size of the simulated screen, which the artwork and cabinet screens map to.
+    fn world_screen_size(&self) -> Size2D<f32> {
+        Size2D {
+            width: self.res.video.image_size.width as f32 * self.res.scaling.pixel_width,
+            height: self.res.video.image_size.height as f32,
+        }
+    }
+
     fn update_output_filter_source_colors(&mut self) {
         let output = &mut self.res.main.render;
         let filters = &self.res.controllers;
 
+        // Misconverged beams have to be drawn one channel at a time, even
+        // when the channels share the same geometry. Panels have no beams.
+        let subpixels = filters.color_channels.value.subpixels();
+        let misconverged = subpixels.is_empty()
+            && (filters.convergence_red_x.value != 0.0
+                || filters.convergence_red_y.value != 0.0
+                || filters.convergence_blue_x.value != 0.0
+                || filters.convergence_blue_y.value != 0.0
+                || filters.convergence_radial.value != 0.0);
         output.color_splits = match filters.color_channels.value {
-            ColorChannelsOptions::Combined => 1,
+            ColorChannelsOptions::Combined if !misconverged => 1,
+            _ if !subpixels.is_empty() => subpixels.len(),
             _ => 3,
         };
+        output.color_splits_overlap = misconverged || matches!(filters.color_channels.value, ColorChannelsOptions::Overlapping);
         output.light_color_background = get_3_f32color_from_int(filters.light_color.value);
+        output.white_subpixel = None;
         for i in 0..output.color_splits {
             let mut light_color = output.light_color_background;
-            match filters.color_channels.value {
-                ColorChannelsOptions::Combined => {}
-                _ => {
-                    light_color[(i + 0) % 3] *= 1.0;
-                    light_color[(i + 1) % 3] = 0.0;
-                    light_color[(i + 2) % 3] = 0.0;
+            let channel = match subpixels.get(i).map(|subpixel| subpixel.channel) {
+                Some(SubpixelChannel::White) => {
+                    output.white_subpixel = Some(i);
+                    None
                 }
+                Some(SubpixelChannel::Red) => Some(0),
+                Some(SubpixelChannel::Green) => Some(1),
+                Some(SubpixelChannel::Blue) => Some(2),
+                None if output.color_splits > 1 => Some(i),
+                None => None,
+            };
+            if let Some(channel) = channel {
+                light_color[(channel + 1) % 3] = 0.0;
+                light_color[(channel + 2) % 3] = 0.0;
             }
             output.light_color[i] = light_color;
         }
@@ -883,18 +1031,37 @@ impl<'a> SimulationUpdater<'a> {
         output.rgb_blue[2] = filters.rgb_blue_b.into();
         output.color_gamma = filters.color_gamma.value;
         output.color_noise = filters.color_noise.value;
+        output.lut_grading = match (&self.res.video.color_lut, filters.lut_stage.value) {
+            (None, _) | (_, LutStageOptions::Off) => None,
+            (Some(_), stage) => Some(LutGrading {
+                stage,
+                interpolation: filters.lut_interpolation.value,
+                strength: filters.lut_strength.value,
+            }),
+        };
+        output.linear_light = LinearLight::new(filters.source_transfer.value, filters.display_transfer.value, filters.transfer_power.value);
+        output.color_vision = ColorVisionFilter::new(filters.color_vision.value, filters.color_vision_severity.value);
     }
 
     fn update_output_filter_curvature(&mut self) {
         let output = &mut self.res.main.render;
         let filters = &self.res.controllers;
 
-        output.screen_curvature_factor = match filters.screen_curvature_kind.value {
-            ScreenCurvatureKindOptions::Curved1 => 0.15,
-            ScreenCurvatureKindOptions::Curved2 => 0.3,
-            ScreenCurvatureKindOptions::Curved3 => 0.45,
-            _ => 0.0,
-        };
+        output.screen_geometry = ScreenGeometry::new(ScreenGeometrySettings {
+            kind: filters.screen_curvature_kind.value,
+            curvature_horizontal: filters.curvature_horizontal.value,
+            curvature_vertical: filters.curvature_vertical.value,
+            barrel: filters.barrel_distortion.value,
+            keystone: filters.screen_keystone.value,
+            trapezoid: filters.screen_trapezoid.value,
+            tilt_degrees: filters.screen_tilt.value,
+        });
+        output.screen_mask = ScreenMask::new(
+            filters.screen_corner_radius.value,
+            filters.screen_edge_softness.value,
+            filters.vignette_strength.value,
+            filters.vignette_shape.value,
+        );
 
         if let ScreenCurvatureKindOptions::Pulse = filters.screen_curvature_kind.value {
             output.pixels_pulse += self.res.main.dt * 0.3;
@@ -903,18 +1070,266 @@ impl<'a> SimulationUpdater<'a> {
         }
     }
 
+    fn update_output_filter_monochrome(&mut self) {
+        let filters = &self.res.controllers;
+        let monochrome = Monochrome::new(filters.monochrome_luma.value, filters.phosphor_type.value, self.res.main.dt);
+        // The afterglow keeps fading after the source stops changing, so the
+        // phosphor runs again over the same source until it has faded out.
+        let previous = &self.res.main.render.monochrome;
+        if monochrome.is_some() != previous.is_some() {
+            self.res.video.needs_buffer_data_load = true;
+        } else if let (Some(monochrome), Some(previous)) = (&monochrome, previous) {
+            if monochrome.phosphor != previous.phosphor {
+                self.res.afterglow.restart(&monochrome.phosphor);
+            }
+            if self.res.afterglow.fade(self.res.main.dt) {
+                self.res.video.needs_panel_settle = true;
+            }
+        }
+        self.res.main.render.monochrome = monochrome;
+    }
+
     fn update_output_filter_backlight(&mut self) {
         let output = &mut self.res.main.render;
         let filters = &self.res.controllers;
 
-        output.showing_background = filters.backlight_percent.value > 0.0;
-        let solid_color_weight = filters.backlight_percent.value;
+        // The blurred background is also how a phosphor glows around what it draws.
+        let bloom = if output.monochrome.is_some() { filters.phosphor_bloom.value } else { 0.0 };
+        let solid_color_weight = filters.backlight_percent.value + bloom;
+        output.showing_background = solid_color_weight > 0.0;
 
         for i in 0..3 {
             output.light_color_background[i] *= solid_color_weight;
         }
     }
 
+    fn update_output_filter_scan_mode(&mut self) {
+        let output = &mut self.res.main.render;
+        let filters = &self.res.controllers;
+
+        if let ScanModeOptions::Interlaced = filters.scan_mode.value {
+            let field = (self.input.now * 0.001 * INTERLACED_FIELDS_PER_SECOND).floor() as u64;
+            output.interlace_field = (field % 2) as f32;
+            output.interlace_flicker = filters.interlace_flicker.value;
+            output.interlace_combing = filters.interlace_combing.value;
+        } else {
+            output.interlace_field = 0.0;
+            output.interlace_flicker = 0.0;
+            output.interlace_combing = 0.0;
+        }
+    }
+
+    fn update_output_filter_scan_output(&mut self) {
+        let filters = &self.res.controllers;
+        self.res.main.render.scan_band = self
+            .res
+            .scan_beam
+            .update(filters.scan_output.value, filters.display_refresh.value, filters.source_refresh.value);
+    }
+
+    fn update_output_filter_source_quantization(&mut self) {
+        let filters = &self.res.controllers;
+        let source_quantization = SourceQuantization::new(filters.source_depth.value, filters.source_dither.value);
+        if source_quantization != self.res.main.render.source_quantization {
+            self.res.main.render.source_quantization = source_quantization;
+            self.res.video.needs_buffer_data_load = true;
+        }
+    }
+
+    fn update_output_filter_dither_blend(&mut self) {
+        let filters = &self.res.controllers;
+        let dither_blend = DitherBlendFilter::new(filters.dither_blend.value, filters.dither_tolerance.value, filters.dither_contrast.value);
+        if dither_blend != self.res.main.render.dither_blend {
+            self.res.main.render.dither_blend = dither_blend;
+            self.res.video.needs_buffer_data_load = true;
+        }
+    }
+
+    fn update_output_filter_signal_path(&mut self) {
+        let filters = &self.res.controllers;
+        let mut signal_path = SignalPath {
+            kind: filters.signal_type.value,
+            standard: filters.signal_standard.value,
+            crosstalk: filters.signal_crosstalk.value,
+            frame: self.res.main.render.signal_path.frame,
+        };
+        if signal_path.has_subcarrier() {
+            if let Some(frame) = self.res.signal_clock.advance(self.res.main.dt, signal_path.standard) {
+                signal_path.frame = frame;
+            }
+        }
+        // The signal chain runs on the CPU when the source is uploaded, so a
+        // change, or a new frame crawling the dots, needs it loaded again.
+        if signal_path != self.res.main.render.signal_path {
+            self.res.main.render.signal_path = signal_path;
+            self.res.video.needs_buffer_data_load = true;
+        }
+    }
+
+    fn update_output_filter_tv_knobs(&mut self) {
+        let filters = &self.res.controllers;
+        let tv_knobs = TvKnobs {
+            black_level: filters.tv_black_level.value,
+            picture: filters.tv_picture.value,
+            color: filters.tv_color.value,
+            tint: filters.tv_tint.value,
+            sharpness: filters.tv_sharpness.value,
+        };
+        if tv_knobs != self.res.main.render.tv_knobs {
+            self.res.main.render.tv_knobs = tv_knobs;
+            self.res.video.needs_buffer_data_load = true;
+        }
+    }
+
+    fn update_output_filter_tube_aging(&mut self) {
+        let filters = &self.res.controllers;
+        let tube_aging = TubeAging {
+            uniformity: filters.tube_uniformity.value,
+            phosphor_wear: filters.phosphor_wear.value,
+            color_drift: filters.color_drift.value,
+        };
+        self.res.main.render.tube_aging = tube_aging;
+        // The shader shows the aging as it is, only the burn-in map needs the
+        // source again, and only once in a while.
+        let burn_in_hours = filters.burn_in_speed.value * self.res.main.dt;
+        self.res.main.render.burn_in_hours = if burn_in_hours > 0.0 {
+            self.res.burn_in_clock.advance(self.res.main.dt, burn_in_hours).unwrap_or(0.0)
+        } else {
+            self.res.burn_in_clock = BurnInClock::default();
+            0.0
+        };
+        if self.res.main.render.burn_in_hours > 0.0 {
+            self.res.video.needs_buffer_data_load = true;
+        }
+    }
+
+    fn update_output_filter_tape_playback(&mut self) {
+        let filters = &self.res.controllers;
+        if let TapeSpeedOptions::Off = filters.tape_speed.value {
+            if self.res.main.render.tape_playback.take().is_some() {
+                self.res.video.needs_buffer_data_load = true;
+            }
+            return;
+        }
+        let settings = TapeSettings {
+            speed: filters.tape_speed.value,
+            noise: filters.tape_noise.value,
+            tracking: filters.tape_tracking.value,
+            dropouts: filters.tape_dropouts.value,
+        };
+        // Like the signal chain, playback runs on the CPU, so every new field
+        // uploads the frame again.
+        if let Some(playback) = self.res.tape_deck.advance(self.res.main.dt, settings, &self.res.rng) {
+            self.res.main.render.tape_playback = Some(playback);
+            self.res.video.needs_buffer_data_load = true;
+        }
+    }
+
+    fn update_output_filter_rf_interference(&mut self) {
+        let filters = &self.res.controllers;
+        let ghost = MultipathGhost {
+            strength: filters.ghost_strength.value,
+            offset: filters.ghost_offset.value,
+        };
+        if ghost != self.res.main.render.ghost {
+            self.res.main.render.ghost = ghost;
+            self.res.video.needs_buffer_data_load = true;
+        }
+        let settings = RfSettings {
+            snow: filters.rf_snow.value,
+            hum_bar_strength: filters.hum_bar_strength.value,
+            hum_bar_frequency: filters.hum_bar_frequency.value,
+            vertical_roll: filters.vertical_roll.value,
+        };
+        self.res.main.render.rf_interference = self.res.rf_receiver.update(
+            settings,
+            self.input.now,
+            self.res.main.dt,
+            self.res.video.image_size.height as f32,
+            &self.res.rng,
+        );
+    }
+
+    fn update_output_filter_lcd(&mut self) {
+        let filters = &self.res.controllers;
+        let response = if filters.lcd_response_rise.value > 0.0 || filters.lcd_response_fall.value > 0.0 {
+            Some(ResponseTimes {
+                rise: filters.lcd_response_rise.value * 0.001,
+                fall: filters.lcd_response_fall.value * 0.001,
+                dt: self.res.main.dt,
+            })
+        } else {
+            None
+        };
+        let lcd = LcdPanel {
+            palette: palette_shades(filters.lcd_palette.value),
+            response,
+        };
+        // Cells keep settling after the source stops changing, so a slow
+        // panel runs again over the same source on every tick.
+        let previous = &self.res.main.render.lcd;
+        if lcd.palette != previous.palette || lcd.response.is_some() != previous.response.is_some() {
+            self.res.video.needs_buffer_data_load = true;
+        } else if lcd.response.is_some() {
+            self.res.video.needs_panel_settle = true;
+        }
+        self.res.main.render.lcd = lcd;
+
+        let screen_size = self.world_screen_size();
+        self.res.main.render.lcd_substrate = match filters.lcd_substrate.value {
+            0 => None,
+            color => Some(PanelSubstrate {
+                color: get_3_f32color_from_int(color),
+                size: [screen_size.width, screen_size.height],
+            }),
+        };
+    }
+
+    fn update_output_filter_reflective_panel(&mut self) {
+        let filters = &self.res.controllers;
+        self.res.main.render.ambient_lighting = AmbientLighting::new(
+            filters.reflective_panel.value,
+            filters.ambient_light.value,
+            filters.ambient_elevation.value,
+            filters.ambient_azimuth.value,
+        );
+        let epaper = match filters.reflective_panel.value {
+            ReflectivePanelOptions::EPaper => Some(EPaper {
+                gray_levels: filters.epaper_gray_levels.value.round() as u32,
+                ghosting: filters.epaper_ghosting.value,
+                dt: self.res.main.dt,
+            }),
+            _ => None,
+        };
+        // Like a slow LCD, the particles keep moving after the source stops changing.
+        if epaper.is_some() != self.res.main.render.epaper.is_some() {
+            self.res.video.needs_buffer_data_load = true;
+        } else if epaper.is_some() {
+            self.res.video.needs_panel_settle = true;
+        }
+        self.res.main.render.epaper = epaper;
+    }
+
+    fn update_output_vector_beam(&mut self) {
+        let filters = &self.res.controllers;
+        let dt = self.res.main.dt;
+        let output = &mut self.res.main.render;
+        output.vector_beam = self.res.video.vector.as_ref().map(|_| {
+            VectorBeam::new(
+                filters.vector_beam_width.value,
+                filters.vector_hot_spot.value,
+                filters.vector_persistence.value,
+                filters.vector_bloom.value,
+                get_3_f32color_from_int(filters.light_color.value),
+                dt,
+            )
+        });
+        // The lines glow through the same blurred background as the pixel backlight.
+        if let Some(beam) = &output.vector_beam {
+            output.showing_background = beam.bloom > 0.0;
+        }
+    }
+
     fn update_output_pixel_scale_gap_offset(&mut self) {
         let output = &mut self.res.main.render;
         let filters = &self.res.controllers;
@@ -930,79 +1345,109 @@ impl<'a> SimulationUpdater<'a> {
             (filters.cur_pixel_vertical_gap.value + filters.cur_pixel_horizontal_gap.value) * 0.5 + 1.0,
         ];
 
-        let by_vertical_lpp = 1.0 / (filters.vertical_lpp.value as f32);
-        let by_horizontal_lpp = 1.0 / (filters.horizontal_lpp.value as f32);
-        let vl_offset_beginning = -(filters.vertical_lpp.value as f32 - 1.0) / 2.0;
-        let hl_offset_beginning = -(filters.horizontal_lpp.value as f32 - 1.0) / 2.0;
+        // Line doubling draws every source row as two scanlines, which is the
+        // same as doubling the horizontal line passes.
+        let lines_per_row = filters.scan_mode.value.lines_per_row();
+        let vertical_lpp = filters.vertical_lpp.value;
+        let horizontal_lpp = filters.horizontal_lpp.value * lines_per_row;
+        output.vertical_lpp = vertical_lpp;
+        output.horizontal_lpp = horizontal_lpp;
+
+        let radial = filters.convergence_radial.value;
+        let subpixels = filters.color_channels.value.subpixels();
+        output.convergence_radial = if output.color_splits > 1 && subpixels.is_empty() {
+            [radial, 0.0, -radial, 0.0]
+        } else {
+            [0.0; MAX_COLOR_SPLITS]
+        };
+
+        let by_vertical_lpp = 1.0 / (vertical_lpp as f32);
+        let by_horizontal_lpp = 1.0 / (horizontal_lpp as f32);
+        let vl_offset_beginning = -(vertical_lpp as f32 - 1.0) / 2.0;
+        let hl_offset_beginning = -(horizontal_lpp as f32 - 1.0) / 2.0;
 
-        let line_passes = filters.vertical_lpp.value * filters.horizontal_lpp.value;
+        let line_passes = vertical_lpp * horizontal_lpp;
         output.pixel_scale_background.resize_with(line_passes, Default::default);
         output.pixel_offset_background.resize_with(line_passes, Default::default);
-        for hl_idx in 0..filters.horizontal_lpp.value {
-            for vl_idx in 0..filters.vertical_lpp.value {
-                let pixel_offset = &mut output.pixel_offset_background[vl_idx * filters.horizontal_lpp.value + hl_idx];
-                let pixel_scale = &mut output.pixel_scale_background[vl_idx * filters.horizontal_lpp.value + hl_idx];
+        for hl_idx in 0..horizontal_lpp {
+            for vl_idx in 0..vertical_lpp {
+                let pixel_offset = &mut output.pixel_offset_background[vl_idx * horizontal_lpp + hl_idx];
+                let pixel_scale = &mut output.pixel_scale_background[vl_idx * horizontal_lpp + hl_idx];
 
                 *pixel_offset = [0.0, 0.0, 0.0];
                 *pixel_scale = [(0.0 + 1.0) / scaling.pixel_width, 0.0 + 1.0, (0.0 + 0.0) * 0.5 + 1.0];
-                if filters.vertical_lpp.value > 1 {
+                if vertical_lpp > 1 {
                     let vl_cur_offset = vl_offset_beginning + vl_idx as f32;
                     pixel_offset[0] = (pixel_offset[0] + vl_cur_offset * scaling.pixel_width) * by_vertical_lpp;
-                    pixel_scale[0] *= filters.vertical_lpp.value as f32;
+                    pixel_scale[0] *= vertical_lpp as f32;
                 }
-                if filters.horizontal_lpp.value > 1 {
+                if horizontal_lpp > 1 {
                     let hl_cur_offset = hl_offset_beginning + hl_idx as f32;
                     pixel_offset[1] = (pixel_offset[1] + hl_cur_offset) * by_horizontal_lpp;
-                    pixel_scale[1] *= filters.horizontal_lpp.value as f32;
+                    pixel_scale[1] *= horizontal_lpp as f32;
                 }
             }
         }
 
         output.pixel_scale_foreground.resize_with(line_passes, Default::default);
         output.pixel_offset_foreground.resize_with(line_passes, Default::default);
-        for hl_idx in 0..filters.horizontal_lpp.value {
-            for vl_idx in 0..filters.vertical_lpp.value {
+        for hl_idx in 0..horizontal_lpp {
+            for vl_idx in 0..vertical_lpp {
                 for color_idx in 0..output.color_splits {
-                    let pixel_offset = &mut output.pixel_offset_foreground[vl_idx * filters.horizontal_lpp.value + hl_idx][color_idx];
-                    let pixel_scale = &mut output.pixel_scale_foreground[vl_idx * filters.horizontal_lpp.value + hl_idx][color_idx];
+                    let pixel_offset = &mut output.pixel_offset_foreground[vl_idx * horizontal_lpp + hl_idx][color_idx];
+                    let pixel_scale = &mut output.pixel_scale_foreground[vl_idx * horizontal_lpp + hl_idx][color_idx];
                     *pixel_offset = [0.0, 0.0, 0.0];
                     *pixel_scale = [
                         (filters.cur_pixel_vertical_gap.value + 1.0) / scaling.pixel_width,
                         filters.cur_pixel_horizontal_gap.value + 1.0,
                         (filters.cur_pixel_vertical_gap.value + filters.cur_pixel_horizontal_gap.value) * 0.5 + 1.0,
                     ];
-                    if filters.vertical_lpp.value > 1 {
+                    if vertical_lpp > 1 {
                         let vl_cur_offset = vl_offset_beginning + vl_idx as f32;
                         pixel_offset[0] = (pixel_offset[0] + vl_cur_offset * scaling.pixel_width) * by_vertical_lpp;
-                        pixel_scale[0] *= filters.vertical_lpp.value as f32;
+                        pixel_scale[0] *= vertical_lpp as f32;
                     }
-                    if filters.horizontal_lpp.value > 1 {
+                    if horizontal_lpp > 1 {
                         let hl_cur_offset = hl_offset_beginning + hl_idx as f32;
                         pixel_offset[1] = (pixel_offset[1] + hl_cur_offset) * by_horizontal_lpp;
-                        pixel_scale[1] *= filters.horizontal_lpp.value as f32;
-                        if filters.horizontal_lpp.value % 2 == 0 && hl_idx % 2 == 1 {
+                        pixel_scale[1] *= horizontal_lpp as f32;
+                        if filters.horizontal_lpp.value % 2 == 0 && (hl_idx / lines_per_row) % 2 == 1 {
                             pixel_offset[0] += 0.5 * scaling.pixel_width * by_vertical_lpp;
                         }
                     }
                     match filters.color_channels.value {
                         ColorChannelsOptions::Combined => {}
-                        _ => match filters.color_channels.value {
-                            ColorChannelsOptions::SplitHorizontal => {
-                                pixel_offset[0] += by_vertical_lpp * (color_idx as f32 - 1.0) * (1.0 / 3.0) * scaling.pixel_width
-                                    / (filters.cur_pixel_vertical_gap.value + 1.0);
-                                pixel_scale[0] *= output.color_splits as f32;
-                            }
-                            ColorChannelsOptions::Overlapping => {
-                                pixel_offset[0] += by_vertical_lpp * (color_idx as f32 - 1.0) * (1.0 / 3.0) * scaling.pixel_width
-                                    / (filters.cur_pixel_vertical_gap.value + 1.0);
-                                pixel_scale[0] *= 1.5;
-                            }
-                            ColorChannelsOptions::SplitVertical => {
-                                pixel_offset[1] += by_horizontal_lpp * (color_idx as f32 - 1.0) * (1.0 / 3.0) / (filters.cur_pixel_horizontal_gap.value + 1.0);
-                                pixel_scale[1] *= output.color_splits as f32;
-                            }
-                            _ => unreachable!(),
-                        },
+                        ColorChannelsOptions::SplitHorizontal => {
+                            pixel_offset[0] +=
+                                by_vertical_lpp * (color_idx as f32 - 1.0) * (1.0 / 3.0) * scaling.pixel_width / (filters.cur_pixel_vertical_gap.value + 1.0);
+                            pixel_scale[0] *= output.color_splits as f32;
+                        }
+                        ColorChannelsOptions::Overlapping => {
+                            pixel_offset[0] +=
+                                by_vertical_lpp * (color_idx as f32 - 1.0) * (1.0 / 3.0) * scaling.pixel_width / (filters.cur_pixel_vertical_gap.value + 1.0);
+                            pixel_scale[0] *= 1.5;
+                        }
+                        ColorChannelsOptions::SplitVertical => {
+                            pixel_offset[1] += by_horizontal_lpp * (color_idx as f32 - 1.0) * (1.0 / 3.0) / (filters.cur_pixel_horizontal_gap.value + 1.0);
+                            pixel_scale[1] *= output.color_splits as f32;
+                        }
+                        _ => {
+                            let subpixel = &subpixels[color_idx];
+                            pixel_offset[0] += by_vertical_lpp * subpixel.center[0] * scaling.pixel_width / (filters.cur_pixel_vertical_gap.value + 1.0);
+                            pixel_offset[1] += by_horizontal_lpp * subpixel.center[1] / (filters.cur_pixel_horizontal_gap.value + 1.0);
+                            pixel_scale[0] /= subpixel.size[0];
+                            pixel_scale[1] /= subpixel.size[1];
+                        }
+                    }
+                    if subpixels.is_empty() {
+                        // Green is the reference beam; red and blue land off it.
+                        let convergence = match color_idx {
+                            0 => [filters.convergence_red_x.value, filters.convergence_red_y.value],
+                            2 => [filters.convergence_blue_x.value, filters.convergence_blue_y.value],
+                            _ => [0.0, 0.0],
+                        };
+                        pixel_offset[0] += convergence[0] * output.pixel_spread[0];
+                        pixel_offset[1] += convergence[1] * output.pixel_spread[1];
                     }
                 }
             }
@@ -1112,10 +1557,16 @@ fn calculate_far_away_position(bg_size: Size2D<f32>, internal_resolution: &Inter
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::artwork_layout::ArtworkLayout;
     use crate::camera::CameraChange;
+    use crate::colorimetry::Colorimetry;
     use crate::simulation_command::{ControllerValue, SimulationCommandPlayer};
     use crate::simulation_context::make_fake_simulation_context;
     use crate::simulation_core_state::{AnimationStep, VideoInputResources};
+    use crate::ui_controller::monochrome_luma::MonochromeLumaOptions;
+    use crate::ui_controller::signal_type::SignalTypeOptions;
+    use crate::ui_controller::white_point::WhitePointOptions;
+    use crate::vector_display::{VectorFrame, VectorScene};
 
     fn runnable_resources() -> Resources {
         let mut resources = Resources::default();
@@ -1276,6 +1727,58 @@ mod tests {
         assert!(resources.quit);
     }
 
+    #[test]
+    fn power_hotkey_and_command_both_switch_the_tube() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        let mut input = Input::new(0.0);
+        let mut commands = SimulationCommandBus::default();
+        commands.emit(SimulationCommand::Keyboard {
+            pressed: Pressed::Yes,
+            key: "f2".into(),
+        });
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(16.0).unwrap();
+        commands.emit(SimulationCommand::Keyboard {
+            pressed: Pressed::No,
+            key: "f2".into(),
+        });
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(32.0).unwrap();
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(48.0).unwrap();
+        assert!(!resources.tube_lifecycle.is_powered());
+        assert!(resources.main.render.lifecycle.scale[1] < 1.0);
+
+        commands.emit(SimulationCommand::PowerToggle);
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(64.0).unwrap();
+        assert!(resources.tube_lifecycle.is_powered());
+        assert!(resources.main.render.lifecycle.wobble > 0.0);
+    }
+
+    #[test]
+    fn vector_scene_gets_a_beam_that_glows_through_the_background() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        let mut input = Input::new(0.0);
+        let mut commands = SimulationCommandBus::default();
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(16.0).unwrap();
+        assert_eq!(resources.main.render.vector_beam, None);
+
+        resources.video.vector = Some(VectorScene {
+            width: 256,
+            height: 240,
+            frames: vec![VectorFrame {
+                delay: 60,
+                segments: Vec::new(),
+            }],
+        });
+        resources.controllers.backlight_percent.value = 0.0;
+        resources.controllers.vector_bloom = 0.5.into();
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(32.0).unwrap();
+        let beam = resources.main.render.vector_beam.expect("vector beam");
+        assert_eq!(beam.bloom, 0.5);
+        assert!(beam.persistence > 0.0 && beam.persistence < 1.0);
+        assert!(resources.main.render.showing_background);
+    }
+
     #[test]
     fn first_tick_camera_commands_survive_scaling_initialization() {
         let ctx = make_fake_simulation_context();
@@ -1308,6 +1811,39 @@ mod tests {
         assert_eq!(resources.camera.get_position().x, 123.0);
     }
 
+    #[test]
+    fn camera_reset_frames_the_whole_artwork() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        let mut input = Input::new(0.0);
+        let mut commands = SimulationCommandBus::default();
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(16.0).unwrap();
+        let bare_z = resources.camera.get_position().z;
+        assert!(resources.main.render.artwork.is_empty());
+
+        let mut resources = runnable_resources();
+        resources.video.artwork = Some(
+            ArtworkLayout::parse(
+                r#"<mamelayout version="2">
+                    <element name="bezel"><image file="bezel.png"/></element>
+                    <view name="Bezel">
+                        <screen index="0"><bounds x="1" y="1" width="2" height="2"/></screen>
+                        <bezel element="bezel"><bounds x="0" y="1" width="4" height="3"/></bezel>
+                    </view>
+                </mamelayout>"#,
+            )
+            .unwrap(),
+        );
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(16.0).unwrap();
+
+        let position = resources.camera.get_position();
+        assert!((position.z - bare_z * 2.0).abs() < 1e-3);
+        assert!(position.y < 0.0);
+        assert_eq!(position.x, 0.0);
+        assert_eq!(resources.main.render.artwork.len(), 1);
+        assert!(resources.main.render.artwork[0].over_screen);
+    }
+
     #[test]
     fn duplicate_viewport_notifications_do_not_reset_custom_camera_movement() {
         let ctx = make_fake_simulation_context();
@@ -1401,7 +1937,7 @@ mod tests {
             .collect();
         set_tags.sort();
 
-        assert_eq!(set_tags.len(), 24, "controller inventory changed; audit every UI adapter");
+        assert_eq!(set_tags.len(), 81, "controller inventory changed; audit every UI adapter");
         for tag in set_tags {
             let value = if tag == "front2back:filter-presets-selected" {
                 ControllerValue::Text(FilterPresetOptions::Custom.to_string())
@@ -1456,6 +1992,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn colorimetry_is_written_into_the_rgb_calibration_but_presets_keep_theirs() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        let mut input = Input::new(0.0);
+        let mut commands = SimulationCommandBus::default();
+        resources.controllers.white_point = WhitePointOptions::D93.into();
+
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(16.0).unwrap();
+
+        let [red, _, blue] = resources.controllers.colorimetry().matrix();
+        assert_eq!(resources.controllers.rgb_red_r.value, red[0]);
+        assert_eq!(resources.controllers.rgb_blue_b.value, blue[2]);
+        assert_ne!(resources.controllers.colorimetry(), Colorimetry::default());
+
+        commands.emit(SimulationCommand::controller_set(
+            FILTER_PRESET_EVENT_TAG,
+            ControllerValue::Text(FilterPresetOptions::LcdGba.to_string()),
+        ));
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(32.0).unwrap();
+
+        assert_eq!(resources.controllers.colorimetry(), Colorimetry::default());
+        assert_eq!(resources.controllers.rgb_red_r.value, 0.80);
+    }
+
+    #[test]
+    fn tv_knobs_reach_the_frame_and_presets_store_their_own() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        let mut input = Input::new(0.0);
+        let mut commands = SimulationCommandBus::default();
+        commands.emit(SimulationCommand::controller_set("front2back:tv-color", ControllerValue::Number(0.5)));
+
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(16.0).unwrap();
+
+        assert_eq!(resources.main.render.tv_knobs.color, 0.5);
+        assert_eq!(resources.controllers.preset_kind.value, FilterPresetOptions::Custom);
+
+        commands.emit(SimulationCommand::controller_set(
+            FILTER_PRESET_EVENT_TAG,
+            ControllerValue::Text(FilterPresetOptions::CrtShadowMask1.to_string()),
+        ));
+        SimulationCoreTicker::new(&ctx, &mut resources, &mut input, &mut commands).tick(32.0).unwrap();
+
+        assert!(resources.main.render.tv_knobs.is_passthrough());
+    }
+
     #[test]
     fn preset_and_value_commands_apply_in_fifo_order() {
         fn apply(commands: [SimulationCommand; 2]) -> Resources {
@@ -1749,7 +2332,7 @@ mod tests {
         resources.controllers.pixels_geometry_kind.value = PixelGeometryKindOptions::Squares;
         resources.controllers.pixel_shadow_shape_kind.value.value = 17;
         resources.controllers.color_channels.value = ColorChannelsOptions::SplitVertical;
-        resources.controllers.screen_curvature_kind.value = ScreenCurvatureKindOptions::Curved2;
+        resources.controllers.screen_curvature_kind.value = ScreenCurvatureKindOptions::Spherical;
         resources.controllers.backlight_percent.value = 0.85;
         let expected = flight_visual_state(&resources);
 
@@ -1792,6 +2375,73 @@ mod tests {
         assert_eq!(resources.main.render.pixel_scale_foreground[0][0], [1.0, 1.0, 1.0]);
     }
 
+    #[test]
+    fn misconvergence_splits_combined_channels_and_offsets_red_and_blue() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = Resources::default();
+        resources.scaling.scaling_initialized = true;
+        resources.scaling.pixel_width = 2.0;
+        resources.controllers.vertical_lpp.value = 1;
+        resources.controllers.horizontal_lpp.value = 1;
+        resources.controllers.color_channels.value = ColorChannelsOptions::Combined;
+
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(0.0)).update_outputs();
+        assert_eq!(resources.main.render.color_splits, 1);
+        assert!(!resources.main.render.color_splits_overlap);
+
+        resources.controllers.convergence_red_x.value = 0.5;
+        resources.controllers.convergence_blue_y.value = -1.0;
+        resources.controllers.convergence_radial.value = 0.25;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(16.0)).update_outputs();
+        let output = &resources.main.render;
+        assert_eq!(output.color_splits, 3);
+        assert!(output.color_splits_overlap);
+        assert_eq!(output.light_color[0], [1.0, 0.0, 0.0]);
+        assert_eq!(
+            output.pixel_offset_foreground[0],
+            [[1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 0.0]]
+        );
+        assert_eq!(output.convergence_radial, [0.25, 0.0, -0.25, 0.0]);
+
+        resources.controllers.color_channels.value = ColorChannelsOptions::SplitVertical;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(32.0)).update_outputs();
+        let blue = resources.main.render.pixel_offset_foreground[0][2];
+        assert_eq!(blue, [0.0, 1.0 / 3.0 - 1.0, 0.0]);
+    }
+
+    #[test]
+    fn panel_layouts_place_their_subpixels_and_ignore_convergence() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = Resources::default();
+        resources.scaling.scaling_initialized = true;
+        resources.scaling.pixel_width = 2.0;
+        resources.controllers.vertical_lpp.value = 1;
+        resources.controllers.horizontal_lpp.value = 1;
+        resources.controllers.cur_pixel_vertical_gap.value = 0.0;
+        resources.controllers.cur_pixel_horizontal_gap.value = 0.0;
+        resources.controllers.convergence_red_x.value = 0.5;
+        resources.controllers.color_channels.value = ColorChannelsOptions::Rgbw;
+
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(0.0)).update_outputs();
+        let output = &resources.main.render;
+        assert_eq!(output.color_splits, 4);
+        assert!(!output.color_splits_overlap);
+        assert_eq!(output.white_subpixel, Some(3));
+        assert_eq!(output.light_color[2], [0.0, 0.0, 1.0]);
+        assert_eq!(output.light_color[3], [1.0, 1.0, 1.0]);
+        assert_eq!(output.pixel_offset_foreground[0][0], [-0.5, 0.25, 0.0]);
+        assert_eq!(output.pixel_offset_foreground[0][2], [0.5, -0.25, 0.0]);
+        assert_eq!(output.pixel_scale_foreground[0][2][..2], [1.0, 2.0]);
+        assert_eq!(output.convergence_radial, [0.0; MAX_COLOR_SPLITS]);
+
+        resources.controllers.color_channels.value = ColorChannelsOptions::Bgr;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(16.0)).update_outputs();
+        let output = &resources.main.render;
+        assert_eq!(output.color_splits, 3);
+        assert_eq!(output.white_subpixel, None);
+        assert_eq!(output.light_color[0], [0.0, 0.0, 1.0]);
+    }
+
     #[test]
     fn cube_depth_scale_uses_both_planar_gaps() {
         let ctx = make_fake_simulation_context();
@@ -1822,17 +2472,209 @@ mod tests {
         SimulationUpdater::new(&ctx, &mut resources, &input).update_outputs();
         assert!(!resources.main.render.pixel_have_depth);
 
-        resources.controllers.screen_curvature_kind.value = ScreenCurvatureKindOptions::Curved1;
+        resources.controllers.screen_curvature_kind.value = ScreenCurvatureKindOptions::Spherical;
         SimulationUpdater::new(&ctx, &mut resources, &input).update_outputs();
         assert!(resources.main.render.pixel_have_depth);
 
+        resources.controllers.curvature_horizontal.value = 0.0;
+        resources.controllers.curvature_vertical.value = 0.0;
+        SimulationUpdater::new(&ctx, &mut resources, &input).update_outputs();
+        assert!(!resources.main.render.pixel_have_depth);
+
         resources.controllers.screen_curvature_kind.value = ScreenCurvatureKindOptions::Pulse;
         SimulationUpdater::new(&ctx, &mut resources, &input).update_outputs();
         assert!(resources.main.render.pixel_have_depth);
 
-        resources.controllers.pixels_geometry_kind.value = PixelGeometryKindOptions::Cubes;
         resources.controllers.screen_curvature_kind.value = ScreenCurvatureKindOptions::Flat;
-        SimulationUpdater::new(&ctx, &mut resources, &input).update_outputs();
-        assert!(resources.main.render.pixel_have_depth);
+        for kind in [
+            PixelGeometryKindOptions::Cubes,
+            PixelGeometryKindOptions::Spheres,
+            PixelGeometryKindOptions::Hexagons,
+            PixelGeometryKindOptions::LedDiodes,
+        ] {
+            resources.controllers.pixels_geometry_kind.value = kind;
+            SimulationUpdater::new(&ctx, &mut resources, &input).update_outputs();
+            assert!(resources.main.render.pixel_have_depth, "{} needs depth", kind);
+            assert_eq!(resources.main.render.ambient_strength, 0.5);
+        }
+    }
+
+    #[test]
+    fn line_doubling_draws_two_scanlines_per_source_row() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = Resources::default();
+        let input = Input::new(0.0);
+        resources.scaling.pixel_width = 1.0;
+        resources.controllers.vertical_lpp.value = 3;
+        resources.controllers.horizontal_lpp.value = 1;
+        resources.controllers.scan_mode.value = ScanModeOptions::LineDoubled;
+        resources.main.render.color_splits = 1;
+
+        SimulationUpdater::new(&ctx, &mut resources, &input).update_output_pixel_scale_gap_offset();
+
+        let output = &resources.main.render;
+        assert_eq!((output.vertical_lpp, output.horizontal_lpp), (3, 2));
+        assert_eq!(output.pixel_offset_foreground.len(), 6);
+        assert_eq!(output.pixel_offset_foreground[0][0][1], -0.25);
+        assert_eq!(output.pixel_offset_foreground[1][0][1], 0.25);
+        assert_eq!(output.pixel_scale_foreground[1][0][1], 2.0);
+    }
+
+    #[test]
+    fn interlaced_fields_alternate_with_the_animation_clock() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = Resources::default();
+        resources.scaling.scaling_initialized = true;
+        resources.controllers.scan_mode.value = ScanModeOptions::Interlaced;
+        resources.controllers.interlace_flicker.value = 0.25;
+
+        let fields = [0.0, 17.0, 34.0, 51.0]
+            .iter()
+            .map(|now| {
+                SimulationUpdater::new(&ctx, &mut resources, &Input::new(*now)).update_outputs();
+                resources.main.render.interlace_field
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(fields, [0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(resources.main.render.interlace_flicker, 0.25);
+
+        resources.controllers.scan_mode.value = ScanModeOptions::Progressive;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(68.0)).update_outputs();
+        assert_eq!(resources.main.render.interlace_flicker, 0.0);
+    }
+
+    #[test]
+    fn tape_playback_replays_the_same_fields_after_reinitializing() {
+        let ctx = make_fake_simulation_context();
+        let play = |resources: &mut Resources| {
+            resources.scaling.scaling_initialized = true;
+            resources.controllers.tape_speed.value = TapeSpeedOptions::Lp;
+            resources.main.dt = 1.0 / 60.0;
+            (0..3)
+                .map(|i| {
+                    resources.video.needs_buffer_data_load = false;
+                    SimulationUpdater::new(&ctx, resources, &Input::new(i as f64 * 17.0)).update_outputs();
+                    assert!(resources.video.needs_buffer_data_load);
+                    resources.main.render.tape_playback.clone().expect("tape field")
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut resources = runnable_resources();
+        let first = play(&mut resources);
+        resources.initialize(runnable_resources().video, 0.0);
+        assert_eq!(play(&mut resources), first);
+
+        resources.controllers.tape_speed.value = TapeSpeedOptions::Off;
+        resources.video.needs_buffer_data_load = false;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(68.0)).update_outputs();
+        assert!(resources.main.render.tape_playback.is_none());
+        assert!(resources.video.needs_buffer_data_load);
+    }
+
+    #[test]
+    fn phosphor_afterglow_settles_until_it_fades_out() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        resources.scaling.scaling_initialized = true;
+        resources.controllers.monochrome_luma.value = MonochromeLumaOptions::Rec601;
+        resources.main.dt = 1.0 / 60.0;
+        resources.video.needs_buffer_data_load = false;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(0.0)).update_outputs();
+        assert!(resources.video.needs_buffer_data_load);
+
+        let settles = (1..20)
+            .map(|i| {
+                resources.video.needs_buffer_data_load = false;
+                resources.video.needs_panel_settle = false;
+                SimulationUpdater::new(&ctx, &mut resources, &Input::new(i as f64 * 17.0)).update_outputs();
+                assert!(!resources.video.needs_buffer_data_load);
+                resources.video.needs_panel_settle
+            })
+            .collect::<Vec<_>>();
+        assert!(settles[0]);
+        assert!(!settles[settles.len() - 1]);
+    }
+
+    #[test]
+    fn epaper_settles_over_the_loaded_source() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        resources.scaling.scaling_initialized = true;
+        resources.controllers.reflective_panel.value = ReflectivePanelOptions::EPaper;
+        resources.main.dt = 1.0 / 60.0;
+        resources.video.needs_buffer_data_load = false;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(0.0)).update_outputs();
+        assert!(resources.video.needs_buffer_data_load);
+
+        for i in 1..3 {
+            resources.video.needs_buffer_data_load = false;
+            resources.video.needs_panel_settle = false;
+            SimulationUpdater::new(&ctx, &mut resources, &Input::new(i as f64 * 17.0)).update_outputs();
+            assert!(!resources.video.needs_buffer_data_load);
+            assert!(resources.video.needs_panel_settle);
+        }
+    }
+
+    #[test]
+    fn composite_signal_reloads_the_source_on_every_frame_it_sends() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        resources.scaling.scaling_initialized = true;
+        resources.controllers.signal_type.value = SignalTypeOptions::Composite;
+        resources.main.dt = 1.0 / 50.0;
+        let frames = (0..3)
+            .map(|i| {
+                resources.video.needs_buffer_data_load = false;
+                SimulationUpdater::new(&ctx, &mut resources, &Input::new(i as f64 * 17.0)).update_outputs();
+                assert!(resources.video.needs_buffer_data_load);
+                resources.main.render.signal_path.frame
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(frames, [1, 2, 3]);
+
+        resources.controllers.signal_type.value = SignalTypeOptions::SVideo;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(51.0)).update_outputs();
+        resources.video.needs_buffer_data_load = false;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(68.0)).update_outputs();
+        assert!(!resources.video.needs_buffer_data_load);
+    }
+
+    #[test]
+    fn slow_lcd_settles_over_the_loaded_source() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        resources.scaling.scaling_initialized = true;
+        resources.controllers.lcd_response_rise.value = 50.0;
+        resources.main.dt = 1.0 / 60.0;
+        resources.video.needs_buffer_data_load = false;
+        SimulationUpdater::new(&ctx, &mut resources, &Input::new(0.0)).update_outputs();
+        assert!(resources.video.needs_buffer_data_load);
+
+        for i in 1..3 {
+            resources.video.needs_buffer_data_load = false;
+            resources.video.needs_panel_settle = false;
+            SimulationUpdater::new(&ctx, &mut resources, &Input::new(i as f64 * 17.0)).update_outputs();
+            assert!(!resources.video.needs_buffer_data_load);
+            assert!(resources.video.needs_panel_settle);
+        }
+    }
+
+    #[test]
+    fn burning_in_reloads_the_source_only_once_a_second() {
+        let ctx = make_fake_simulation_context();
+        let mut resources = runnable_resources();
+        resources.scaling.scaling_initialized = true;
+        resources.controllers.burn_in_speed.value = 100.0;
+        resources.main.dt = 0.25;
+        let loads = (0..8)
+            .map(|i| {
+                resources.video.needs_buffer_data_load = false;
+                SimulationUpdater::new(&ctx, &mut resources, &Input::new(i as f64 * 250.0)).update_outputs();
+                resources.video.needs_buffer_data_load
+            })
+            .collect::<Vec<_>>();
+        // The first tick loads the source anyway.
+        assert_eq!(loads[1..], [false, false, true, false, false, false, true]);
+        assert_eq!(resources.main.render.burn_in_hours, 100.0);
     }
 }
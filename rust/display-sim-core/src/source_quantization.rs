@@ -0,0 +1,201 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::source_depth::SourceDepthOptions;
+use crate::ui_controller::source_dither::SourceDitherOptions;
+
+#[rustfmt::skip]
+const NES_PALETTE: [[u8; 3]; 64] = [
+    [0x7C, 0x7C, 0x7C], [0x00, 0x00, 0xFC], [0x00, 0x00, 0xBC], [0x44, 0x28, 0xBC], [0x94, 0x00, 0x84], [0xA8, 0x00, 0x20], [0xA8, 0x10, 0x00], [0x88, 0x14, 0x00],
+    [0x50, 0x30, 0x00], [0x00, 0x78, 0x00], [0x00, 0x68, 0x00], [0x00, 0x58, 0x00], [0x00, 0x40, 0x58], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00],
+    [0xBC, 0xBC, 0xBC], [0x00, 0x78, 0xF8], [0x00, 0x58, 0xF8], [0x68, 0x44, 0xFC], [0xD8, 0x00, 0xCC], [0xE4, 0x00, 0x58], [0xF8, 0x38, 0x00], [0xE4, 0x5C, 0x10],
+    [0xAC, 0x7C, 0x00], [0x00, 0xB8, 0x00], [0x00, 0xA8, 0x00], [0x00, 0xA8, 0x44], [0x00, 0x88, 0x88], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00],
+    [0xF8, 0xF8, 0xF8], [0x3C, 0xBC, 0xFC], [0x68, 0x88, 0xFC], [0x98, 0x78, 0xF8], [0xF8, 0x78, 0xF8], [0xF8, 0x58, 0x98], [0xF8, 0x78, 0x58], [0xFC, 0xA0, 0x44],
+    [0xF8, 0xB8, 0x00], [0xB8, 0xF8, 0x18], [0x58, 0xD8, 0x54], [0x58, 0xF8, 0x98], [0x00, 0xE8, 0xD8], [0x78, 0x78, 0x78], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00],
+    [0xFC, 0xFC, 0xFC], [0xA4, 0xE4, 0xFC], [0xB8, 0xB8, 0xF8], [0xD8, 0xB8, 0xF8], [0xF8, 0xB8, 0xF8], [0xF8, 0xA4, 0xC0], [0xF0, 0xD0, 0xB0], [0xFC, 0xE0, 0xA8],
+    [0xF8, 0xD8, 0x78], [0xD8, 0xF8, 0x78], [0xB8, 0xF8, 0xB8], [0xB8, 0xF8, 0xD8], [0x00, 0xFC, 0xFC], [0xF8, 0xD8, 0xF8], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00],
+];
+
+#[rustfmt::skip]
+const CGA_PALETTE: [[u8; 3]; 16] = [
+    [0x00, 0x00, 0x00], [0x00, 0x00, 0xAA], [0x00, 0xAA, 0x00], [0x00, 0xAA, 0xAA],
+    [0xAA, 0x00, 0x00], [0xAA, 0x00, 0xAA], [0xAA, 0x55, 0x00], [0xAA, 0xAA, 0xAA],
+    [0x55, 0x55, 0x55], [0x55, 0x55, 0xFF], [0x55, 0xFF, 0x55], [0x55, 0xFF, 0xFF],
+    [0xFF, 0x55, 0x55], [0xFF, 0x55, 0xFF], [0xFF, 0xFF, 0x55], [0xFF, 0xFF, 0xFF],
+];
+
+#[rustfmt::skip]
+const BAYER_4X4: [[f32; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+/// Ordered dithering of a palette spreads colors by about the distance
+/// between its neighbouring shades.
+const PALETTE_DITHER_SPREAD: f32 = 64.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuantizationTarget {
+    /// Bits per channel.
+    Depth(u8),
+    Palette(&'static [[u8; 3]]),
+}
+
+/// Reduces the source to the bits or palette the original hardware could output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SourceQuantization {
+    pub target: QuantizationTarget,
+    pub dither: SourceDitherOptions,
+}
+
+impl SourceQuantization {
+    pub fn new(depth: SourceDepthOptions, dither: SourceDitherOptions) -> Option<SourceQuantization> {
+        let target = match depth {
+            SourceDepthOptions::Off => return None,
+            SourceDepthOptions::Rgb555 => QuantizationTarget::Depth(5),
+            SourceDepthOptions::Rgb444 => QuantizationTarget::Depth(4),
+            SourceDepthOptions::Rgb333 => QuantizationTarget::Depth(3),
+            SourceDepthOptions::Nes => QuantizationTarget::Palette(&NES_PALETTE),
+            SourceDepthOptions::Cga => QuantizationTarget::Palette(&CGA_PALETTE),
+        };
+        Some(SourceQuantization { target, dither })
+    }
+
+    pub fn process(&self, frame: &[u8], width: u32, height: u32) -> Vec<u8> {
+        let mut result = frame.to_vec();
+        match self.dither {
+            SourceDitherOptions::Off => {
+                for pixel in result.chunks_exact_mut(4) {
+                    let color = self.nearest([f32::from(pixel[0]), f32::from(pixel[1]), f32::from(pixel[2])]);
+                    pixel[..3].copy_from_slice(&color);
+                }
+            }
+            SourceDitherOptions::Ordered => {
+                let spread = self.dither_spread();
+                for (i, pixel) in result.chunks_exact_mut(4).enumerate() {
+                    let x = i % width as usize;
+                    let y = i / width as usize;
+                    let offset = ((BAYER_4X4[y % 4][x % 4] + 0.5) / 16.0 - 0.5) * spread;
+                    let color = self.nearest([f32::from(pixel[0]) + offset, f32::from(pixel[1]) + offset, f32::from(pixel[2]) + offset]);
+                    pixel[..3].copy_from_slice(&color);
+                }
+            }
+            SourceDitherOptions::ErrorDiffusion => self.diffuse_errors(&mut result, width as usize, height as usize),
+        }
+        result
+    }
+
+    /// Floyd-Steinberg, in scanline order.
+    fn diffuse_errors(&self, frame: &mut [u8], width: usize, height: usize) {
+        let mut wanted: Vec<[f32; 3]> = frame
+            .chunks_exact(4)
+            .map(|pixel| [f32::from(pixel[0]), f32::from(pixel[1]), f32::from(pixel[2])])
+            .collect();
+        for y in 0..height {
+            for x in 0..width {
+                let index = y * width + x;
+                let color = self.nearest(wanted[index]);
+                frame[index * 4..index * 4 + 3].copy_from_slice(&color);
+                let error = [
+                    wanted[index][0] - f32::from(color[0]),
+                    wanted[index][1] - f32::from(color[1]),
+                    wanted[index][2] - f32::from(color[2]),
+                ];
+                let mut spread_error = |dx: isize, dy: usize, weight: f32| {
+                    let nx = x as isize + dx;
+                    if nx < 0 || nx >= width as isize || y + dy >= height {
+                        return;
+                    }
+                    let target = &mut wanted[(y + dy) * width + nx as usize];
+                    for c in 0..3 {
+                        target[c] += error[c] * weight;
+                    }
+                };
+                spread_error(1, 0, 7.0 / 16.0);
+                spread_error(-1, 1, 3.0 / 16.0);
+                spread_error(0, 1, 5.0 / 16.0);
+                spread_error(1, 1, 1.0 / 16.0);
+            }
+        }
+    }
+
+    fn dither_spread(&self) -> f32 {
+        match self.target {
+            QuantizationTarget::Depth(bits) => 255.0 / ((1 << bits) - 1) as f32,
+            QuantizationTarget::Palette(_) => PALETTE_DITHER_SPREAD,
+        }
+    }
+
+    fn nearest(&self, color: [f32; 3]) -> [u8; 3] {
+        match self.target {
+            QuantizationTarget::Depth(bits) => {
+                let levels = ((1 << bits) - 1) as f32;
+                let mut result = [0; 3];
+                for c in 0..3 {
+                    let level = (color[c].clamp(0.0, 255.0) * levels / 255.0).round();
+                    result[c] = (level * 255.0 / levels).round() as u8;
+                }
+                result
+            }
+            QuantizationTarget::Palette(palette) => {
+                let distance = |entry: &[u8; 3]| -> f32 { (0..3).map(|c| (color[c] - f32::from(entry[c])).powi(2)).sum() };
+                *palette
+                    .iter()
+                    .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+                    .expect("Palettes are not empty.")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth_rounds_each_channel_to_its_levels() {
+        let quantization = SourceQuantization::new(SourceDepthOptions::Rgb333, SourceDitherOptions::Off).unwrap();
+
+        let frame = [0, 100, 255, 77];
+
+        assert_eq!(quantization.process(&frame, 1, 1), vec![0, 109, 255, 77]);
+    }
+
+    #[test]
+    fn palettes_only_output_their_own_colors() {
+        let quantization = SourceQuantization::new(SourceDepthOptions::Cga, SourceDitherOptions::ErrorDiffusion).unwrap();
+
+        let frame: Vec<u8> = (0..64u32).flat_map(|i| [(i * 4) as u8, 200, (255 - i * 4) as u8, 255]).collect();
+
+        for pixel in quantization.process(&frame, 8, 8).chunks_exact(4) {
+            assert!(CGA_PALETTE.contains(&[pixel[0], pixel[1], pixel[2]]));
+        }
+    }
+
+    #[test]
+    fn ordered_dither_mixes_the_two_closest_levels() {
+        let quantization = SourceQuantization::new(SourceDepthOptions::Rgb333, SourceDitherOptions::Ordered).unwrap();
+
+        let frame = [128u8, 128, 128, 255].repeat(16);
+
+        let result = quantization.process(&frame, 4, 4);
+        let lighter = result.chunks_exact(4).filter(|pixel| pixel[0] == 146).count();
+        let darker = result.chunks_exact(4).filter(|pixel| pixel[0] == 109).count();
+        assert_eq!(lighter + darker, 16);
+        assert!(lighter > 0 && darker > 0);
+    }
+}
@@ -0,0 +1,336 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::signal_path::{bandwidth_sigma, decode, encode, lowpass, pixel_clock_mhz};
+use crate::simulation_context::RandomGenerator;
+use crate::ui_controller::tape_speed::TapeSpeedOptions;
+use std::f32::consts::TAU;
+
+const FIELDS_PER_SECOND: f32 = 60.0;
+// Colour-under chroma is recovered a little late relative to luma.
+const CHROMA_DELAY_US: f32 = 0.25;
+const HEAD_SWITCHING_LINES: f32 = 0.03;
+const TRACKING_BAND_HEIGHT: f32 = 0.08;
+
+/// Controller values the tape deck reads every field.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TapeSettings {
+    pub speed: TapeSpeedOptions,
+    pub noise: f32,
+    pub tracking: f32,
+    pub dropouts: f32,
+}
+
+/// A short horizontal streak where the tape lost its magnetic coating. All
+/// coordinates are relative to the frame size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dropout {
+    pub x: f32,
+    pub y: f32,
+    pub length: f32,
+}
+
+/// Everything needed to reproduce one played back field from the source frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TapePlayback {
+    pub settings: TapeSettings,
+    pub seed: u32,
+    pub wobble_phase: f32,
+    pub tracking_position: f32,
+    pub tracking_strength: f32,
+    pub dropouts: Vec<Dropout>,
+}
+
+struct TapeFormat {
+    luma_mhz: f32,
+    chroma_mhz: f32,
+    noise_gain: f32,
+    wobble_px: f32,
+    wear: f32,
+}
+
+fn tape_format(speed: TapeSpeedOptions) -> TapeFormat {
+    // Slower tape speeds pack the same signal into less tape, losing
+    // bandwidth and becoming more sensitive to noise and mechanical jitter.
+    match speed {
+        TapeSpeedOptions::Off | TapeSpeedOptions::Sp => TapeFormat {
+            luma_mhz: 3.0,
+            chroma_mhz: 0.5,
+            noise_gain: 1.0,
+            wobble_px: 0.4,
+            wear: 1.0,
+        },
+        TapeSpeedOptions::Lp => TapeFormat {
+            luma_mhz: 2.6,
+            chroma_mhz: 0.45,
+            noise_gain: 1.4,
+            wobble_px: 0.7,
+            wear: 1.5,
+        },
+        TapeSpeedOptions::Ep => TapeFormat {
+            luma_mhz: 2.2,
+            chroma_mhz: 0.4,
+            noise_gain: 2.0,
+            wobble_px: 1.0,
+            wear: 2.0,
+        },
+    }
+}
+
+/// Mechanical state of the deck that carries over from field to field.
+#[derive(Default)]
+pub struct TapeDeck {
+    elapsed: f32,
+    wobble_phase: f32,
+    tracking_drift: f32,
+    tracking_position: f32,
+}
+
+impl TapeDeck {
+    /// Advances the transport by `dt` seconds. Returns the next field once one
+    /// is due; all randomness is drawn from `rng`, so a seeded generator
+    /// replays the same tape.
+    pub fn advance(&mut self, dt: f32, settings: TapeSettings, rng: &dyn RandomGenerator) -> Option<TapePlayback> {
+        self.elapsed += dt;
+        if self.elapsed < 1.0 / FIELDS_PER_SECOND {
+            return None;
+        }
+        let fields = (self.elapsed * FIELDS_PER_SECOND).floor();
+        self.elapsed -= fields / FIELDS_PER_SECOND;
+        let format = tape_format(settings.speed);
+        let mut dropouts = vec![];
+        for _ in 0..(fields as usize).min(4) {
+            self.wobble_phase = (self.wobble_phase + TAU * (0.6 + 0.4 * rng.next()) / FIELDS_PER_SECOND) % TAU;
+            self.tracking_drift = (self.tracking_drift + (rng.next() - 0.5) * 0.2).clamp(-1.0, 1.0);
+            self.tracking_position = (self.tracking_position + 0.01 + 0.02 * self.tracking_drift.abs()).fract();
+            dropouts.clear();
+            for _ in 0..4 {
+                if rng.next() < settings.dropouts * format.wear * 0.15 {
+                    dropouts.push(Dropout {
+                        x: rng.next(),
+                        y: rng.next(),
+                        length: 0.02 + 0.1 * rng.next(),
+                    });
+                }
+            }
+        }
+        Some(TapePlayback {
+            settings,
+            seed: (rng.next() * u32::MAX as f32) as u32,
+            wobble_phase: self.wobble_phase,
+            tracking_position: self.tracking_position,
+            tracking_strength: settings.tracking * (0.5 + 0.5 * self.tracking_drift.abs()),
+            dropouts,
+        })
+    }
+}
+
+impl TapePlayback {
+    /// Plays an RGBA8 frame back from tape. Alpha is preserved.
+    pub fn process(&self, rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+        let mut output = rgba.to_vec();
+        if width == 0 || height == 0 {
+            return output;
+        }
+        let width = width as usize;
+        let height = height as usize;
+        let format = tape_format(self.settings.speed);
+        let scale = width as f32 / 256.0;
+        let chroma_delay = pixel_clock_mhz(width) * CHROMA_DELAY_US;
+        let head_switching = (height as f32 * HEAD_SWITCHING_LINES).ceil().max(2.0) as usize;
+        let noise = self.settings.noise * format.noise_gain * 0.12;
+
+        let mut luma = vec![0.0; width];
+        let mut chroma = [vec![0.0; width], vec![0.0; width]];
+        let mut grain = vec![0.0; width];
+        for (y, row) in output.chunks_exact_mut(width * 4).enumerate() {
+            let source = &rgba[y * width * 4..(y + 1) * width * 4];
+            let line = y as f32 / height as f32;
+            let line_hash = hash(self.seed, 0, y as u32) - 0.5;
+
+            // Time-base errors: a slow flagging wave plus per-line jitter.
+            let mut shift = format.wobble_px * scale * ((self.wobble_phase + line * TAU * 2.0).sin() + line_hash * 0.5);
+            let mut line_noise = noise;
+            let distance = (line - self.tracking_position).abs();
+            let distance = distance.min(1.0 - distance);
+            if distance < TRACKING_BAND_HEIGHT * 0.5 {
+                let band = self.tracking_strength * (1.0 - distance / (TRACKING_BAND_HEIGHT * 0.5));
+                shift += band * line_hash * 12.0 * scale;
+                line_noise += band * 0.5;
+            }
+            if y + head_switching >= height {
+                // The drum switches heads just before vertical sync, skewing
+                // the last lines of the field to the right.
+                let skew = (y + head_switching + 1 - height) as f32 / head_switching as f32;
+                shift += skew * (6.0 + 2.0 * line_hash) * scale;
+                line_noise += skew * 0.3;
+            }
+
+            for x in 0..width {
+                let [l, _, _] = sample(source, x as f32 - shift);
+                let [_, c1, c2] = sample(source, x as f32 - shift - chroma_delay);
+                luma[x] = l;
+                chroma[0][x] = c1;
+                chroma[1][x] = c2;
+                grain[x] = hash(self.seed, x as u32 + 1, y as u32) - 0.5;
+            }
+            lowpass(&mut luma, bandwidth_sigma(width, format.luma_mhz));
+            lowpass(&mut chroma[0], bandwidth_sigma(width, format.chroma_mhz));
+            lowpass(&mut chroma[1], bandwidth_sigma(width, format.chroma_mhz));
+            // Tape noise is streaky: it is smeared along the line like the signal.
+            lowpass(&mut grain, 0.75 * scale);
+
+            for x in 0..width {
+                luma[x] += grain[x] * line_noise * 2.0;
+                chroma[0][x] += grain[(x + width / 2) % width] * line_noise * 0.5;
+            }
+            for dropout in &self.dropouts {
+                if (dropout.y * height as f32) as usize != y {
+                    continue;
+                }
+                let start = (dropout.x * width as f32) as usize;
+                let end = (start + (dropout.length * width as f32).ceil() as usize).min(width);
+                for x in start..end {
+                    let fade = 1.0 - (x - start) as f32 / (end - start) as f32 * 0.5;
+                    luma[x] = luma[x] * (1.0 - fade) + fade;
+                    chroma[0][x] *= 1.0 - fade;
+                    chroma[1][x] *= 1.0 - fade;
+                }
+            }
+
+            for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+                let rgb = decode(false, [luma[x], chroma[0][x], chroma[1][x]]);
+                pixel[..3].copy_from_slice(&rgb);
+            }
+        }
+        output
+    }
+}
+
+/// Samples a line of the source as YIQ with linear interpolation. Positions
+/// outside the line read as black, like the blanking around active video.
+fn sample(source: &[u8], x: f32) -> [f32; 3] {
+    let pixel = |i: f32| -> [f32; 3] {
+        if i < 0.0 || i as usize * 4 >= source.len() {
+            return [0.0; 3];
+        }
+        let i = i as usize * 4;
+        encode(false, [source[i], source[i + 1], source[i + 2]])
+    };
+    let base = x.floor();
+    let t = x - base;
+    let (a, b) = (pixel(base), pixel(base + 1.0));
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, a[2] + (b[2] - a[2]) * t]
+}
+
+fn hash(seed: u32, x: u32, y: u32) -> f32 {
+    let mut h = seed ^ x.wrapping_mul(0x27d4_eb2d) ^ y.wrapping_mul(0x1656_67b1);
+    h = (h ^ (h >> 15)).wrapping_mul(0x2c1b_3c6d);
+    h = (h ^ (h >> 12)).wrapping_mul(0x297a_2d39);
+    h ^= h >> 15;
+    (h >> 8) as f32 / (1u32 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation_context::SeededRandomGenerator;
+
+    fn settings(speed: TapeSpeedOptions) -> TapeSettings {
+        TapeSettings {
+            speed,
+            noise: 0.0,
+            tracking: 0.0,
+            dropouts: 0.0,
+        }
+    }
+
+    fn playback(settings: TapeSettings) -> TapePlayback {
+        TapePlayback {
+            settings,
+            seed: 1,
+            wobble_phase: 0.0,
+            tracking_position: 0.5,
+            tracking_strength: settings.tracking,
+            dropouts: vec![],
+        }
+    }
+
+    fn bar_frame(width: usize, height: usize) -> Vec<u8> {
+        (0..width * height)
+            .flat_map(|i| {
+                if (100..140).contains(&(i % width)) {
+                    [255, 255, 255, 255]
+                } else {
+                    [0, 0, 0, 255]
+                }
+            })
+            .collect()
+    }
+
+    fn bar_center(row: &[u8]) -> f32 {
+        let (sum, weight) = row.chunks_exact(4).enumerate().fold((0.0, 0.0), |(sum, weight), (x, pixel)| {
+            (sum + x as f32 * pixel[0] as f32, weight + pixel[0] as f32)
+        });
+        sum / weight
+    }
+
+    #[test]
+    fn the_same_seed_replays_the_same_tape() {
+        let mut settings = settings(TapeSpeedOptions::Ep);
+        settings.tracking = 0.5;
+        settings.dropouts = 1.0;
+        let play = || {
+            let rng = SeededRandomGenerator::new(7);
+            let mut deck = TapeDeck::default();
+            (0..10).filter_map(|_| deck.advance(1.0 / 60.0, settings, &rng)).collect::<Vec<_>>()
+        };
+        let fields = play();
+        assert_eq!(fields.len(), 10);
+        assert_eq!(fields, play());
+        assert!(fields.windows(2).all(|pair| pair[0].seed != pair[1].seed));
+    }
+
+    #[test]
+    fn fields_are_only_produced_at_the_field_rate() {
+        let rng = SeededRandomGenerator::new(7);
+        let mut deck = TapeDeck::default();
+        assert!(deck.advance(0.01, settings(TapeSpeedOptions::Sp), &rng).is_none());
+        assert!(deck.advance(0.01, settings(TapeSpeedOptions::Sp), &rng).is_some());
+        assert!(deck.advance(0.01, settings(TapeSpeedOptions::Sp), &rng).is_none());
+    }
+
+    #[test]
+    fn head_switching_skews_only_the_bottom_of_the_frame() {
+        let frame = bar_frame(256, 240);
+        let output = playback(settings(TapeSpeedOptions::Sp)).process(&frame, 256, 240);
+        let row = |y: usize| &output[y * 256 * 4..(y + 1) * 256 * 4];
+        assert!((bar_center(row(120)) - 119.5).abs() < 1.0, "{}", bar_center(row(120)));
+        assert!(bar_center(row(239)) - 119.5 > 4.0, "{}", bar_center(row(239)));
+    }
+
+    #[test]
+    fn dropouts_leave_white_streaks_on_their_line() {
+        let frame = [0, 0, 0, 255].repeat(256 * 240);
+        let mut playback = playback(settings(TapeSpeedOptions::Sp));
+        playback.dropouts.push(Dropout { x: 0.25, y: 0.5, length: 0.1 });
+        let output = playback.process(&frame, 256, 240);
+        let pixel = |x: usize, y: usize| output[(y * 256 + x) * 4];
+        assert!(pixel(70, 120) > 200, "{}", pixel(70, 120));
+        assert!(pixel(70, 119) < 10);
+        assert!(pixel(30, 120) < 10);
+        assert_eq!(output[(120 * 256 + 70) * 4 + 3], 255);
+    }
+}
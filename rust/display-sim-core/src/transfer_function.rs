@@ -0,0 +1,150 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::ui_controller::display_transfer::DisplayTransferOptions;
+use crate::ui_controller::source_transfer::SourceTransferOptions;
+
+pub const SRGB_GAMMA: f32 = 2.4;
+pub const BT1886_GAMMA: f32 = 2.4;
+/// Black of the BT.1886 reference display, relative to its white.
+pub const BT1886_BLACK: f32 = 0.001;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferCurve {
+    Srgb,
+    Bt1886,
+    Power(f32),
+}
+
+impl TransferCurve {
+    pub fn to_linear(self, value: f32) -> f32 {
+        let value = value.max(0.0);
+        match self {
+            TransferCurve::Srgb => {
+                if value <= 0.04045 {
+                    value / 12.92
+                } else {
+                    ((value + 0.055) / 1.055).powf(SRGB_GAMMA)
+                }
+            }
+            TransferCurve::Bt1886 => {
+                let (gain, lift) = bt1886_gain_lift();
+                gain * (value + lift).powf(BT1886_GAMMA)
+            }
+            TransferCurve::Power(power) => value.powf(power),
+        }
+    }
+
+    pub fn from_linear(self, light: f32) -> f32 {
+        let light = light.max(0.0);
+        match self {
+            TransferCurve::Srgb => {
+                if light <= 0.003_130_8 {
+                    light * 12.92
+                } else {
+                    1.055 * light.powf(1.0 / SRGB_GAMMA) - 0.055
+                }
+            }
+            TransferCurve::Bt1886 => {
+                let (gain, lift) = bt1886_gain_lift();
+                ((light / gain).powf(1.0 / BT1886_GAMMA) - lift).max(0.0)
+            }
+            TransferCurve::Power(power) => light.powf(1.0 / power),
+        }
+    }
+
+    /// Curve selector in the shaders, where 0 means that no transfer happens.
+    pub fn shader_kind(self) -> f32 {
+        match self {
+            TransferCurve::Srgb => 1.0,
+            TransferCurve::Bt1886 => 2.0,
+            TransferCurve::Power(_) => 3.0,
+        }
+    }
+
+    pub fn shader_power(self) -> f32 {
+        match self {
+            TransferCurve::Power(power) => power,
+            _ => 1.0,
+        }
+    }
+}
+
+/// BT.1886 with a white of 1: `L = gain * (V + lift)^2.4`, so that `V = 0`
+/// gives the reference black.
+fn bt1886_gain_lift() -> (f32, f32) {
+    let black_root = BT1886_BLACK.powf(1.0 / BT1886_GAMMA);
+    ((1.0 - black_root).powf(BT1886_GAMMA), black_root / (1.0 - black_root))
+}
+
+/// The source is decoded to light with `source`, and the composed picture is
+/// encoded back for the screen with `display`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearLight {
+    pub source: TransferCurve,
+    pub display: TransferCurve,
+}
+
+impl LinearLight {
+    pub fn new(source: SourceTransferOptions, display: DisplayTransferOptions, power: f32) -> Option<LinearLight> {
+        let source = match source {
+            SourceTransferOptions::Off => return None,
+            SourceTransferOptions::Srgb => TransferCurve::Srgb,
+            SourceTransferOptions::Bt1886 => TransferCurve::Bt1886,
+            SourceTransferOptions::Power => TransferCurve::Power(power),
+        };
+        let display = match display {
+            DisplayTransferOptions::Srgb => TransferCurve::Srgb,
+            DisplayTransferOptions::Bt1886 => TransferCurve::Bt1886,
+            DisplayTransferOptions::Power => TransferCurve::Power(power),
+        };
+        Some(LinearLight { source, display })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_curve_round_trips() {
+        for curve in [TransferCurve::Srgb, TransferCurve::Bt1886, TransferCurve::Power(2.2)] {
+            for step in 0..=20 {
+                let value = step as f32 / 20.0;
+                let back = curve.from_linear(curve.to_linear(value));
+                assert!((back - value).abs() < 1e-4, "{:?} turned {} into {}", curve, value, back);
+            }
+        }
+    }
+
+    #[test]
+    fn bt1886_keeps_the_reference_black_and_white() {
+        assert!((TransferCurve::Bt1886.to_linear(0.0) - BT1886_BLACK).abs() < 1e-6);
+        assert!((TransferCurve::Bt1886.to_linear(1.0) - 1.0).abs() < 1e-6);
+        assert!((TransferCurve::Srgb.to_linear(0.5) - 0.214).abs() < 1e-3);
+    }
+
+    #[test]
+    fn off_keeps_the_gamma_space_pipeline() {
+        assert_eq!(LinearLight::new(SourceTransferOptions::Off, DisplayTransferOptions::Srgb, 2.2), None);
+        assert_eq!(
+            LinearLight::new(SourceTransferOptions::Power, DisplayTransferOptions::Bt1886, 2.5),
+            Some(LinearLight {
+                source: TransferCurve::Power(2.5),
+                display: TransferCurve::Bt1886,
+            })
+        );
+    }
+}
@@ -0,0 +1,203 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use serde::{Deserialize, Serialize};
+
+/// Efficiency a phosphor loses for every hour lit at full strength.
+const BURN_IN_PER_HOUR: f32 = 1.0 / 20000.0;
+/// Blue phosphors wear the fastest.
+const CHANNEL_WEAR: [f32; 3] = [0.8, 1.0, 1.4];
+const MAX_PHOSPHOR_WEAR: [f32; 3] = [0.15, 0.2, 0.45];
+const MAX_COLOR_DRIFT: [f32; 3] = [0.04, 0.02, -0.06];
+/// Seconds between two updates of the burn-in map. Wear takes hours to
+/// show, so nobody can tell it apart from wearing on every frame.
+const BURN_IN_INTERVAL: f32 = 1.0;
+
+/// A tube that has been on for years. The pixel shader applies it over the
+/// burn-in map.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TubeAging {
+    /// How much brightness falls off towards the edges and in blotches.
+    pub uniformity: f32,
+    /// Overall loss of efficiency of the three phosphors.
+    pub phosphor_wear: f32,
+    pub color_drift: f32,
+}
+
+impl TubeAging {
+    pub fn is_passthrough(&self) -> bool {
+        self.uniformity == 0.0 && self.phosphor_wear == 0.0 && self.color_drift == 0.0
+    }
+
+    pub fn channel_gains(&self) -> [f32; 3] {
+        let mut gains = [1.0; 3];
+        for c in 0..3 {
+            gains[c] = (1.0 - self.phosphor_wear * MAX_PHOSPHOR_WEAR[c]) * (1.0 + self.color_drift * MAX_COLOR_DRIFT[c]);
+        }
+        gains
+    }
+
+    /// Brightness at a position of the face, from -1 to 1 on both axes with
+    /// v going down. The pixel shader follows the same formula.
+    pub fn face_gain(&self, u: f32, v: f32) -> f32 {
+        if self.uniformity == 0.0 {
+            return 1.0;
+        }
+        let edges = (u * u + v * v) * 0.5;
+        let blotches = 0.5 + 0.5 * (3.1 * u + 1.3).sin() * (2.3 * v + 0.7).cos();
+        1.0 - self.uniformity * (0.35 * edges + 0.15 * blotches)
+    }
+}
+
+/// Gathers the hours the tube has been wearing and hands them over at a
+/// slower pace, so that the burn-in map is not rebuilt on every frame.
+#[derive(Default)]
+pub struct BurnInClock {
+    elapsed: f32,
+    hours: f32,
+}
+
+impl BurnInClock {
+    /// Advances by `dt` seconds, in which the tube wore for `hours`. Returns
+    /// the hours gathered once the interval is over.
+    pub fn advance(&mut self, dt: f32, hours: f32) -> Option<f32> {
+        self.elapsed += dt;
+        self.hours += hours;
+        if self.elapsed < BURN_IN_INTERVAL {
+            return None;
+        }
+        let hours = self.hours;
+        *self = BurnInClock::default();
+        Some(hours)
+    }
+}
+
+/// Lost efficiency of every phosphor triad, from 0 to 1 per channel. It can
+/// be saved and loaded again, either serialized or as an image.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BurnInMap {
+    pub width: u32,
+    pub height: u32,
+    wear: Vec<[f32; 3]>,
+}
+
+impl BurnInMap {
+    pub fn new(width: u32, height: u32) -> BurnInMap {
+        BurnInMap {
+            width,
+            height,
+            wear: vec![[0.0; 3]; (width * height) as usize],
+        }
+    }
+
+    /// Brighter texels are more worn.
+    pub fn from_rgba8(width: u32, height: u32, pixels: &[u8]) -> BurnInMap {
+        BurnInMap {
+            width,
+            height,
+            wear: pixels
+                .chunks_exact(4)
+                .map(|pixel| [f32::from(pixel[0]) / 255.0, f32::from(pixel[1]) / 255.0, f32::from(pixel[2]) / 255.0])
+                .collect(),
+        }
+    }
+
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.wear
+            .iter()
+            .flat_map(|wear| [to_u8(wear[0]), to_u8(wear[1]), to_u8(wear[2]), 255])
+            .collect()
+    }
+
+    /// The same map stretched to another size, so that it fits a new source.
+    pub fn resized(&self, width: u32, height: u32) -> BurnInMap {
+        let mut wear = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                wear.push(self.wear_at(x, y, width, height));
+            }
+        }
+        BurnInMap { width, height, wear }
+    }
+
+    fn wear_at(&self, x: u32, y: u32, width: u32, height: u32) -> [f32; 3] {
+        let x = (x * self.width / width).min(self.width - 1);
+        let y = (y * self.height / height).min(self.height - 1);
+        self.wear[(y * self.width + x) as usize]
+    }
+
+    /// Wears the phosphors by what `frame` lights during `hours`. The frame
+    /// has to be the size of the map.
+    pub fn accumulate(&mut self, frame: &[u8], hours: f32) {
+        for (wear, pixel) in self.wear.iter_mut().zip(frame.chunks_exact(4)) {
+            for c in 0..3 {
+                let exposure = f32::from(pixel[c]) / 255.0 * hours * BURN_IN_PER_HOUR * CHANNEL_WEAR[c];
+                wear[c] += (1.0 - wear[c]) * exposure.min(1.0);
+            }
+        }
+    }
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burn_in_darkens_where_the_picture_stayed() {
+        let mut burn_in = BurnInMap::new(2, 1);
+        burn_in.accumulate(&[255, 255, 255, 255, 0, 0, 0, 255], 20000.0);
+
+        let wear = burn_in.to_rgba8();
+
+        assert!(wear[0] > 0 && wear[2] > wear[0]);
+        assert_eq!(wear[4..], [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn phosphor_wear_weakens_blue_the_most() {
+        let aging = TubeAging {
+            phosphor_wear: 1.0,
+            ..TubeAging::default()
+        };
+
+        let gains = aging.channel_gains();
+
+        assert!(gains[2] < gains[1] && gains[1] < gains[0] && gains[0] < 1.0);
+    }
+
+    #[test]
+    fn burn_in_clock_hands_over_the_hours_once_a_second() {
+        let mut clock = BurnInClock::default();
+        assert_eq!(clock.advance(0.5, 2.0), None);
+        assert_eq!(clock.advance(0.5, 3.0), Some(5.0));
+        assert_eq!(clock.advance(0.5, 1.0), None);
+    }
+
+    #[test]
+    fn worn_tubes_survive_a_round_trip() {
+        let mut burn_in = BurnInMap::new(2, 2);
+        burn_in.accumulate(&[255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 9, 9, 9, 255], 5000.0);
+
+        let json = serde_json::to_string(&burn_in).unwrap();
+        assert_eq!(serde_json::from_str::<BurnInMap>(&json).unwrap(), burn_in);
+
+        let image = BurnInMap::from_rgba8(2, 2, &burn_in.to_rgba8());
+        assert_eq!(image.to_rgba8(), burn_in.to_rgba8());
+    }
+}
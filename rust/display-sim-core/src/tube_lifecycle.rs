@@ -0,0 +1,214 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use std::f32::consts::TAU;
+
+/// Seconds the vertical deflection takes to stop.
+const COLLAPSE_VERTICAL: f32 = 0.12;
+/// Seconds the horizontal deflection takes to stop after the vertical one.
+const COLLAPSE_HORIZONTAL: f32 = 0.18;
+/// Seconds the dot left in the middle takes to fade away.
+const DOT_FADE: f32 = 1.2;
+/// Seconds the cathodes take to reach their working temperature.
+const WARM_UP: f32 = 2.5;
+/// Seconds the degaussing coil takes to decay.
+const DEGAUSS: f32 = 2.0;
+/// Wobbles per second of the decaying field, as seen on the screen.
+const DEGAUSS_FREQUENCY: f32 = 9.0;
+/// Smallest size of the picture, so that the dot is still visible.
+const DOT_SIZE: f32 = 0.004;
+
+/// Per-frame transform and color modulation consumed by the pixel shaders.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LifecycleEffect {
+    /// Size of the picture relative to the full deflection.
+    pub scale: [f32; 2],
+    pub brightness: f32,
+    /// Displacement of the picture, relative to its size.
+    pub wobble: f32,
+    pub wobble_phase: f32,
+    /// Hue rotation in radians caused by the beams landing on the wrong phosphors.
+    pub swirl: f32,
+}
+
+impl Default for LifecycleEffect {
+    fn default() -> Self {
+        LifecycleEffect {
+            scale: [1.0, 1.0],
+            brightness: 1.0,
+            wobble: 0.0,
+            wobble_phase: 0.0,
+            swirl: 0.0,
+        }
+    }
+}
+
+impl LifecycleEffect {
+    pub fn is_passthrough(&self) -> bool {
+        *self == LifecycleEffect::default()
+    }
+}
+
+/// Power and degaussing state of the tube, carried between ticks: the picture
+/// collapses and fades when switched off, warms up when switched on and
+/// wobbles while degaussing.
+pub struct TubeLifecycle {
+    powered: bool,
+    /// Seconds since the last time the power was switched.
+    since_switch: f32,
+    /// Seconds since the coil was last triggered, while it still decays.
+    since_degauss: Option<f32>,
+}
+
+impl Default for TubeLifecycle {
+    fn default() -> Self {
+        TubeLifecycle {
+            powered: true,
+            since_switch: f32::INFINITY,
+            since_degauss: None,
+        }
+    }
+}
+
+impl TubeLifecycle {
+    pub fn is_powered(&self) -> bool {
+        self.powered
+    }
+
+    pub fn toggle_power(&mut self) {
+        self.powered = !self.powered;
+        self.since_switch = 0.0;
+        // Sets like these degauss themselves when switched on.
+        self.degauss();
+    }
+
+    pub fn degauss(&mut self) {
+        if self.powered {
+            self.since_degauss = Some(0.0);
+        }
+    }
+
+    pub fn advance(&mut self, dt: f32) -> LifecycleEffect {
+        self.since_switch += dt;
+        if let Some(since_degauss) = &mut self.since_degauss {
+            *since_degauss += dt;
+        }
+        if !self.powered || self.since_degauss.is_some_and(|t| t > DEGAUSS) {
+            self.since_degauss = None;
+        }
+
+        let mut effect = if self.powered { self.warm_up() } else { self.power_off() };
+        if let Some(t) = self.since_degauss {
+            // The field decays quickly at first and then lingers.
+            let strength = (1.0 - t / DEGAUSS).powi(3);
+            effect.wobble = 0.03 * strength;
+            effect.wobble_phase = (t * DEGAUSS_FREQUENCY).fract() * TAU;
+            effect.swirl = std::f32::consts::PI * strength;
+        }
+        effect
+    }
+
+    fn power_off(&self) -> LifecycleEffect {
+        let t = self.since_switch;
+        let vertical = 1.0 - smoothstep(0.0, COLLAPSE_VERTICAL, t);
+        let horizontal = 1.0 - smoothstep(COLLAPSE_VERTICAL, COLLAPSE_VERTICAL + COLLAPSE_HORIZONTAL, t);
+        let fade = smoothstep(COLLAPSE_VERTICAL + COLLAPSE_HORIZONTAL, COLLAPSE_VERTICAL + COLLAPSE_HORIZONTAL + DOT_FADE, t);
+        // The energy of the whole picture ends up in the line and then the dot.
+        let concentration = 1.0 + 0.5 * (1.0 - vertical) + 0.5 * (1.0 - horizontal);
+        LifecycleEffect {
+            scale: [horizontal.max(DOT_SIZE), vertical.max(DOT_SIZE)],
+            brightness: concentration * (1.0 - fade),
+            ..LifecycleEffect::default()
+        }
+    }
+
+    fn warm_up(&self) -> LifecycleEffect {
+        let t = (self.since_switch / WARM_UP).min(1.0);
+        // The picture grows with the high voltage and breathes until it settles.
+        let breathing = 0.03 * (1.0 - t) * (t * 3.0 * TAU).sin();
+        let size = 1.0 - 0.08 * (1.0 - smoothstep(0.0, 0.6, t)) + breathing;
+        LifecycleEffect {
+            scale: [size, size],
+            brightness: smoothstep(0.0, 1.0, t),
+            ..LifecycleEffect::default()
+        }
+    }
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(lifecycle: &mut TubeLifecycle, seconds: f32) -> LifecycleEffect {
+        let steps = (seconds / 0.01).round() as usize;
+        let mut effect = LifecycleEffect::default();
+        for _ in 0..steps {
+            effect = lifecycle.advance(0.01);
+        }
+        effect
+    }
+
+    #[test]
+    fn a_tube_left_alone_does_nothing() {
+        let mut lifecycle = TubeLifecycle::default();
+        assert!(run(&mut lifecycle, 1.0).is_passthrough());
+    }
+
+    #[test]
+    fn powering_off_collapses_to_a_line_and_then_a_fading_dot() {
+        let mut lifecycle = TubeLifecycle::default();
+        lifecycle.toggle_power();
+        let line = run(&mut lifecycle, COLLAPSE_VERTICAL);
+        assert_eq!(line.scale[1], DOT_SIZE);
+        assert!(line.scale[0] > 0.9);
+        assert!(line.brightness > 1.0);
+        let dot = run(&mut lifecycle, COLLAPSE_HORIZONTAL);
+        assert_eq!(dot.scale, [DOT_SIZE, DOT_SIZE]);
+        assert!(dot.brightness > line.brightness);
+        assert_eq!(run(&mut lifecycle, DOT_FADE).brightness, 0.0);
+    }
+
+    #[test]
+    fn powering_on_ramps_up_and_settles() {
+        let mut lifecycle = TubeLifecycle::default();
+        lifecycle.toggle_power();
+        run(&mut lifecycle, 3.0);
+        lifecycle.toggle_power();
+        let early = run(&mut lifecycle, 0.5);
+        assert!(early.brightness > 0.0 && early.brightness < 0.5);
+        assert!(early.scale[0] < 1.0);
+        assert!(run(&mut lifecycle, WARM_UP).is_passthrough());
+    }
+
+    #[test]
+    fn degaussing_wobbles_only_while_the_field_decays() {
+        let mut lifecycle = TubeLifecycle::default();
+        lifecycle.degauss();
+        let start = run(&mut lifecycle, 0.1);
+        let later = run(&mut lifecycle, 1.0);
+        assert!(start.wobble > later.wobble && later.wobble > 0.0);
+        assert!(start.swirl > later.swirl);
+        assert!(run(&mut lifecycle, DEGAUSS).is_passthrough());
+
+        lifecycle.toggle_power();
+        lifecycle.degauss();
+        assert_eq!(run(&mut lifecycle, 0.1).wobble, 0.0);
+    }
+}
@@ -0,0 +1,144 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::signal_path::{bandwidth_sigma, decode, encode, lowpass};
+
+/// Luma detail above this frequency is what the peaking circuit boosts.
+const PEAKING_MHZ: f32 = 1.0;
+const PEAKING_GAIN: f32 = 1.5;
+
+/// Picture controls of a consumer set, acting on the decoded signal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TvKnobs {
+    /// Offset in signal units, the "brightness" of most sets.
+    pub black_level: f32,
+    /// Gain of the whole signal, usually labeled "picture" or "contrast".
+    pub picture: f32,
+    /// Saturation gain.
+    pub color: f32,
+    /// Hue rotation in degrees.
+    pub tint: f32,
+    pub sharpness: f32,
+}
+
+impl Default for TvKnobs {
+    fn default() -> Self {
+        TvKnobs {
+            black_level: 0.0,
+            picture: 1.0,
+            color: 1.0,
+            tint: 0.0,
+            sharpness: 0.0,
+        }
+    }
+}
+
+impl TvKnobs {
+    pub fn is_passthrough(&self) -> bool {
+        *self == TvKnobs::default()
+    }
+
+    /// Runs an RGBA8 frame through the knobs. Alpha is preserved.
+    pub fn process(&self, rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+        let mut output = rgba.to_vec();
+        if self.is_passthrough() || width == 0 || height == 0 {
+            return output;
+        }
+        let width = width as usize;
+        let (sin, cos) = self.tint.to_radians().sin_cos();
+        let sigma = bandwidth_sigma(width, PEAKING_MHZ);
+        let mut luma = vec![0.0; width];
+        let mut chroma = vec![[0.0; 2]; width];
+        for row in output.chunks_exact_mut(width * 4) {
+            for (x, pixel) in row.chunks_exact(4).enumerate() {
+                let [y, u, v] = encode(true, [pixel[0], pixel[1], pixel[2]]);
+                luma[x] = y;
+                chroma[x] = [u, v];
+            }
+            if self.sharpness > 0.0 {
+                let mut blurred = luma.clone();
+                lowpass(&mut blurred, sigma);
+                for (y, low) in luma.iter_mut().zip(blurred) {
+                    *y += self.sharpness * PEAKING_GAIN * (*y - low);
+                }
+            }
+            for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+                let [u, v] = chroma[x];
+                let gain = self.color * self.picture;
+                let rotated = [(u * cos - v * sin) * gain, (u * sin + v * cos) * gain];
+                let y = luma[x] * self.picture + self.black_level;
+                let rgb = decode(true, [y, rotated[0], rotated[1]]);
+                pixel[..3].copy_from_slice(&rgb);
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, rgb: [u8; 3]) -> Vec<u8> {
+        (0..width * height).flat_map(|_| [rgb[0], rgb[1], rgb[2], 255]).collect()
+    }
+
+    #[test]
+    fn neutral_knobs_leave_the_frame_alone() {
+        let frame = (0..16 * 4).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+        assert_eq!(TvKnobs::default().process(&frame, 4, 4), frame);
+    }
+
+    #[test]
+    fn black_level_lifts_black_and_picture_scales_white() {
+        let knobs = TvKnobs {
+            black_level: 0.1,
+            picture: 0.5,
+            ..TvKnobs::default()
+        };
+        let black = knobs.process(&solid(8, 1, [0, 0, 0]), 8, 1);
+        let white = knobs.process(&solid(8, 1, [255, 255, 255]), 8, 1);
+        assert_eq!(&black[..4], &[26, 26, 26, 255]);
+        assert_eq!(&white[..4], &[153, 153, 153, 255]);
+    }
+
+    #[test]
+    fn color_and_tint_leave_gray_alone() {
+        let knobs = TvKnobs {
+            color: 0.0,
+            tint: 30.0,
+            ..TvKnobs::default()
+        };
+        let gray = solid(8, 2, [100, 100, 100]);
+        assert_eq!(knobs.process(&gray, 8, 2), gray);
+        let red = knobs.process(&solid(8, 2, [255, 0, 0]), 8, 2);
+        assert!(red[0] == red[1] && red[1] == red[2], "no color left {:?}", &red[..4]);
+    }
+
+    #[test]
+    fn sharpness_overshoots_an_edge() {
+        let mut frame = solid(256, 1, [64, 64, 64]);
+        for pixel in frame.chunks_exact_mut(4).skip(128) {
+            pixel[..3].copy_from_slice(&[192, 192, 192]);
+        }
+        let knobs = TvKnobs {
+            sharpness: 1.0,
+            ..TvKnobs::default()
+        };
+        let output = knobs.process(&frame, 256, 1);
+        assert!(output[127 * 4] < 64, "dark side rings below {:?}", output[127 * 4]);
+        assert!(output[128 * 4] > 192, "bright side rings above {:?}", output[128 * 4]);
+    }
+}
@@ -33,26 +33,103 @@ pub trait UiController {
     fn post_process_input(&mut self);
 }
 
+pub mod ambient_azimuth;
+pub mod ambient_elevation;
+pub mod ambient_light;
 pub mod backlight_percent;
+pub mod barrel_distortion;
 pub mod blur_passes;
 pub mod brightness_color;
+pub mod burn_in_speed;
 pub mod color_channels;
+pub mod color_drift;
 pub mod color_gamma;
 pub mod color_noise;
+pub mod color_temperature;
+pub mod color_vision;
+pub mod color_vision_severity;
+pub mod convergence_blue_x;
+pub mod convergence_blue_y;
+pub mod convergence_radial;
+pub mod convergence_red_x;
+pub mod convergence_red_y;
 pub mod cur_pixel_horizontal_gap;
 pub mod cur_pixel_spread;
 pub mod cur_pixel_vertical_gap;
+pub mod curvature_horizontal;
+pub mod curvature_vertical;
+pub mod display_primaries;
+pub mod display_refresh;
+pub mod display_transfer;
+pub mod dither_blend;
+pub mod dither_contrast;
+pub mod dither_tolerance;
 mod enum_ui;
+pub mod epaper_ghosting;
+pub mod epaper_gray_levels;
 pub mod extra_bright;
 pub mod extra_contrast;
 pub mod filter_preset;
+pub mod ghost_offset;
+pub mod ghost_strength;
 pub mod horizontal_lpp;
+pub mod hum_bar_frequency;
+pub mod hum_bar_strength;
+pub mod interlace_combing;
+pub mod interlace_flicker;
 pub mod internal_resolution;
+pub mod lcd_palette;
+pub mod lcd_response_fall;
+pub mod lcd_response_rise;
+pub mod lcd_substrate;
 pub mod light_color;
+pub mod lut_interpolation;
+pub mod lut_stage;
+pub mod lut_strength;
+pub mod monochrome_luma;
+pub mod phosphor_bloom;
+pub mod phosphor_type;
+pub mod phosphor_wear;
 pub mod pixel_geometry_kind;
 pub mod pixel_shadow_height;
 pub mod pixel_shadow_shape_kind;
+pub mod reflective_panel;
+pub mod rf_snow;
 pub mod rgb_calibration;
+pub mod scan_mode;
+pub mod scan_output;
+pub mod screen_corner_radius;
 pub mod screen_curvature_kind;
+pub mod screen_edge_softness;
+pub mod screen_keystone;
+pub mod screen_tilt;
+pub mod screen_trapezoid;
+pub mod signal_crosstalk;
+pub mod signal_standard;
+pub mod signal_type;
+pub mod source_depth;
+pub mod source_dither;
+pub mod source_primaries;
+pub mod source_refresh;
+pub mod source_transfer;
+pub mod tape_dropouts;
+pub mod tape_noise;
+pub mod tape_speed;
+pub mod tape_tracking;
 pub mod texture_interpolation;
+pub mod transfer_power;
+pub mod tube_uniformity;
+pub mod tv_black_level;
+pub mod tv_color;
+pub mod tv_picture;
+pub mod tv_sharpness;
+pub mod tv_tint;
+pub mod vector_beam_width;
+pub mod vector_bloom;
+pub mod vector_hot_spot;
+pub mod vector_persistence;
 pub mod vertical_lpp;
+pub mod vertical_roll;
+pub mod vignette_shape;
+pub mod vignette_strength;
+pub mod white_point;
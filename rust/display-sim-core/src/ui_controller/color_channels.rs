@@ -29,6 +29,73 @@ pub enum ColorChannelsOptions {
     Overlapping,
     SplitHorizontal,
     SplitVertical,
+    Bgr,
+    PenTile,
+    Rgbw,
+    OledDelta,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SubpixelChannel {
+    Red,
+    Green,
+    Blue,
+    /// Lit by the common part of the three channels.
+    White,
+}
+
+/// One emitter of a panel cell. Center and size are fractions of the cell,
+/// with the origin in its middle and y going up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Subpixel {
+    pub channel: SubpixelChannel,
+    pub center: [f32; 2],
+    pub size: [f32; 2],
+}
+
+const fn subpixel(channel: SubpixelChannel, center: [f32; 2], size: [f32; 2]) -> Subpixel {
+    Subpixel { channel, center, size }
+}
+
+const BGR_SUBPIXELS: [Subpixel; 3] = [
+    subpixel(SubpixelChannel::Blue, [-1.0 / 3.0, 0.0], [1.0 / 3.0, 1.0]),
+    subpixel(SubpixelChannel::Green, [0.0, 0.0], [1.0 / 3.0, 1.0]),
+    subpixel(SubpixelChannel::Red, [1.0 / 3.0, 0.0], [1.0 / 3.0, 1.0]),
+];
+
+// Red and blue share the diagonal, the two smaller greens fill the other one.
+const PEN_TILE_SUBPIXELS: [Subpixel; 4] = [
+    subpixel(SubpixelChannel::Red, [-0.25, 0.25], [0.5, 0.5]),
+    subpixel(SubpixelChannel::Green, [0.25, 0.25], [0.35, 0.35]),
+    subpixel(SubpixelChannel::Blue, [0.25, -0.25], [0.5, 0.5]),
+    subpixel(SubpixelChannel::Green, [-0.25, -0.25], [0.35, 0.35]),
+];
+
+const RGBW_SUBPIXELS: [Subpixel; 4] = [
+    subpixel(SubpixelChannel::Red, [-0.25, 0.25], [0.5, 0.5]),
+    subpixel(SubpixelChannel::Green, [0.25, 0.25], [0.5, 0.5]),
+    subpixel(SubpixelChannel::Blue, [0.25, -0.25], [0.5, 0.5]),
+    subpixel(SubpixelChannel::White, [-0.25, -0.25], [0.5, 0.5]),
+];
+
+// Blue emitters are the least efficient, so they get the largest area.
+const OLED_DELTA_SUBPIXELS: [Subpixel; 3] = [
+    subpixel(SubpixelChannel::Red, [-0.25, 0.25], [0.45, 0.45]),
+    subpixel(SubpixelChannel::Green, [0.25, 0.25], [0.35, 0.35]),
+    subpixel(SubpixelChannel::Blue, [0.0, -0.25], [0.9, 0.45]),
+];
+
+impl ColorChannelsOptions {
+    /// Fixed subpixel arrangement of a panel, empty for the tube layouts.
+    pub fn subpixels(self) -> &'static [Subpixel] {
+        match self {
+            ColorChannelsOptions::Bgr => &BGR_SUBPIXELS,
+            ColorChannelsOptions::PenTile => &PEN_TILE_SUBPIXELS,
+            ColorChannelsOptions::Rgbw => &RGBW_SUBPIXELS,
+            ColorChannelsOptions::OledDelta => &OLED_DELTA_SUBPIXELS,
+            _ => &[],
+        }
+    }
 }
 
 impl std::fmt::Display for ColorChannelsOptions {
@@ -38,6 +105,10 @@ impl std::fmt::Display for ColorChannelsOptions {
             ColorChannelsOptions::Overlapping => write!(f, "Horizontal overlapping"),
             ColorChannelsOptions::SplitHorizontal => write!(f, "Horizontal split"),
             ColorChannelsOptions::SplitVertical => write!(f, "Vertical split"),
+            ColorChannelsOptions::Bgr => write!(f, "BGR stripes"),
+            ColorChannelsOptions::PenTile => write!(f, "PenTile RGBG"),
+            ColorChannelsOptions::Rgbw => write!(f, "RGBW quad"),
+            ColorChannelsOptions::OledDelta => write!(f, "OLED delta"),
         }
     }
 }
@@ -54,7 +54,7 @@ impl UiController for ColorGamma {
             .set_progression(0.5 * main.dt * main.filter_speed)
             .set_event_value(self.event)
             .set_min(0.0)
-            .set_max(1.0)
+            .set_max(3.0)
             .set_trigger_handler(|x| dispatch(x, ctx.dispatcher()))
             .process_with_sums()
     }
@@ -47,6 +47,11 @@ pub enum FilterPresetOptions {
     CrtApertureGrille1,
     CrtShadowMask1,
     CrtShadowMask2,
+    LcdDmg,
+    LcdGba,
+    LcdGameGear,
+    VectorBlackWhite,
+    VectorColor,
     DemoFlight1,
     Custom,
 }
@@ -58,6 +63,11 @@ impl std::fmt::Display for FilterPresetOptions {
             FilterPresetOptions::CrtApertureGrille1 => write!(f, "crt-aperture-grille-1"),
             FilterPresetOptions::CrtShadowMask1 => write!(f, "crt-shadow-mask-1"),
             FilterPresetOptions::CrtShadowMask2 => write!(f, "crt-shadow-mask-2"),
+            FilterPresetOptions::LcdDmg => write!(f, "lcd-dmg"),
+            FilterPresetOptions::LcdGba => write!(f, "lcd-gba"),
+            FilterPresetOptions::LcdGameGear => write!(f, "lcd-game-gear"),
+            FilterPresetOptions::VectorBlackWhite => write!(f, "vector-bw"),
+            FilterPresetOptions::VectorColor => write!(f, "vector-color"),
             FilterPresetOptions::DemoFlight1 => write!(f, "demo-1"),
             FilterPresetOptions::Custom => write!(f, "custom"),
         }
@@ -72,6 +82,11 @@ impl std::str::FromStr for FilterPresetOptions {
             "crt-aperture-grille-1" => Ok(Self::CrtApertureGrille1),
             "crt-shadow-mask-1" => Ok(Self::CrtShadowMask1),
             "crt-shadow-mask-2" => Ok(Self::CrtShadowMask2),
+            "lcd-dmg" => Ok(Self::LcdDmg),
+            "lcd-gba" => Ok(Self::LcdGba),
+            "lcd-game-gear" => Ok(Self::LcdGameGear),
+            "vector-bw" => Ok(Self::VectorBlackWhite),
+            "vector-color" => Ok(Self::VectorColor),
             "demo-1" => Ok(Self::DemoFlight1),
             "custom" => Ok(Self::Custom),
             _ => Err("Unknown name for a preset".into()),
@@ -80,11 +95,16 @@ impl std::str::FromStr for FilterPresetOptions {
 }
 
 impl FilterPresetOptions {
-    pub const ALL: [Self; 6] = [
+    pub const ALL: [Self; 11] = [
         Self::CrtApertureGrille1,
         Self::CrtShadowMask1,
         Self::CrtShadowMask2,
         Self::Sharp1,
+        Self::LcdDmg,
+        Self::LcdGba,
+        Self::LcdGameGear,
+        Self::VectorBlackWhite,
+        Self::VectorColor,
         Self::DemoFlight1,
         Self::Custom,
     ];
@@ -95,6 +115,11 @@ impl FilterPresetOptions {
             FilterPresetOptions::CrtApertureGrille1 => "CRT Aperture Grille 1",
             FilterPresetOptions::CrtShadowMask1 => "CRT Shadow Mask 1",
             FilterPresetOptions::CrtShadowMask2 => "CRT Shadow Mask 2",
+            FilterPresetOptions::LcdDmg => "LCD Game Boy",
+            FilterPresetOptions::LcdGba => "LCD Game Boy Advance",
+            FilterPresetOptions::LcdGameGear => "LCD Game Gear",
+            FilterPresetOptions::VectorBlackWhite => "Vector Black & White",
+            FilterPresetOptions::VectorColor => "Vector Color",
             FilterPresetOptions::DemoFlight1 => "Flight Demo",
             FilterPresetOptions::Custom => "Custom",
         }
@@ -0,0 +1,74 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::app_events::AppEventDispatcher;
+use crate::simulation_command::ControllerValue;
+use crate::simulation_context::SimulationContext;
+use crate::simulation_core_state::MainState;
+use crate::ui_controller::UiController;
+use app_util::AppResult;
+
+#[derive(Default, Copy, Clone)]
+pub struct LcdSubstrate {
+    event: Option<i32>,
+    pub value: i32,
+}
+
+impl From<i32> for LcdSubstrate {
+    fn from(value: i32) -> Self {
+        LcdSubstrate { event: None, value }
+    }
+}
+
+impl UiController for LcdSubstrate {
+    fn event_tag(&self) -> &'static str {
+        "front2back:lcd-substrate"
+    }
+    fn keys_inc(&self) -> &[&'static str] {
+        &[]
+    }
+    fn keys_dec(&self) -> &[&'static str] {
+        &[]
+    }
+    fn update(&mut self, _: &MainState, _: &dyn SimulationContext) -> bool {
+        if let Some(v) = self.event {
+            self.value = v;
+            self.event = None;
+            true
+        } else {
+            false
+        }
+    }
+    fn reset_inputs(&mut self) {
+        self.event = None;
+    }
+    fn read_event(&mut self, encoded: &ControllerValue) -> AppResult<()> {
+        self.event = Some(encoded.to_i32()?);
+        Ok(())
+    }
+    fn read_key_inc(&mut self, _: bool) {}
+    fn read_key_dec(&mut self, _: bool) {}
+    fn dispatch_event(&self, dispatcher: &dyn AppEventDispatcher) {
+        dispatch(self.value, dispatcher)
+    }
+    fn pre_process_input(&mut self) {}
+    fn post_process_input(&mut self) {
+        self.event = None;
+    }
+}
+
+fn dispatch(value: i32, dispatcher: &dyn AppEventDispatcher) {
+    dispatcher.dispatch_string_event("back2front:change_lcd_substrate", &format!("#{:X}", value));
+}
@@ -24,6 +24,9 @@ use crate::simulation_command::ControllerValue;
 pub enum PixelGeometryKindOptions {
     Squares = 0,
     Cubes = 1,
+    Spheres = 2,
+    Hexagons = 3,
+    LedDiodes = 4,
 }
 
 impl std::fmt::Display for PixelGeometryKindOptions {
@@ -31,6 +34,9 @@ impl std::fmt::Display for PixelGeometryKindOptions {
         match *self {
             PixelGeometryKindOptions::Squares => write!(f, "Squares"),
             PixelGeometryKindOptions::Cubes => write!(f, "Cubes"),
+            PixelGeometryKindOptions::Spheres => write!(f, "Spheres"),
+            PixelGeometryKindOptions::Hexagons => write!(f, "Hexagons"),
+            PixelGeometryKindOptions::LedDiodes => write!(f, "LED Diodes"),
         }
     }
 }
@@ -55,6 +61,9 @@ impl From<ControllerValue> for PixelGeometryKindOptions {
         match value.to_usize() {
             Ok(0) => PixelGeometryKindOptions::Squares,
             Ok(1) => PixelGeometryKindOptions::Cubes,
+            Ok(2) => PixelGeometryKindOptions::Spheres,
+            Ok(3) => PixelGeometryKindOptions::Hexagons,
+            Ok(4) => PixelGeometryKindOptions::LedDiodes,
             Ok(x) => {
                 log_error(&format!("Unexpected PixelGeometryKindOptions value {}", x));
                 PixelGeometryKindOptions::Squares
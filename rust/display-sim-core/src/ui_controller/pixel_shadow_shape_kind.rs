@@ -23,7 +23,10 @@ use crate::simulation_command::ControllerValue;
 
 pub const TEXTURE_SIZE: usize = 510;
 
-const SHADOWS_LEN: usize = 24;
+const SHADOWS_LEN: usize = 25;
+
+/// Flat liquid crystal cell, only slightly darker toward its edges.
+pub const LCD_CELL_SHADOW: usize = 24;
 
 #[derive(Default, Clone, Copy)]
 pub struct ShadowShape {
@@ -90,6 +93,7 @@ pub fn get_shadows() -> [Box<dyn Fn(usize, usize) -> f64>; SHADOWS_LEN] {
         Box::new(|i, j| calc_diamond(i, 0) * calc_diamond(j, 0) * 1.0 * 255.0),
         Box::new(|i, _j| calc_diamond(i, 0) * 1.0 * 255.0),
         Box::new(|i, _j| calc_diamond(i, 1) * 1.5 * 255.0),
+        Box::new(|i, j| calc_lcd_cell(i) * calc_lcd_cell(j) * 255.0),
     ]
 }
 
@@ -104,6 +108,10 @@ fn calc_diamond(number: usize, count: usize) -> f64 {
     let result = 1.0 - ((number - TEXTURE_SIZE / 2) as f64 / (TEXTURE_SIZE as f64 / 2.0));
     pow(result, count)
 }
+fn calc_lcd_cell(number: usize) -> f64 {
+    let distance = (number - TEXTURE_SIZE / 2) as f64 / (TEXTURE_SIZE as f64 / 2.0);
+    1.0 - 0.35 * pow(distance, 3)
+}
 fn pow(mut number: f64, count: usize) -> f64 {
     for _i in 0..count {
         number *= number;
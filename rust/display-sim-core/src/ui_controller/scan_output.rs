@@ -0,0 +1,70 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use std::convert::TryFrom;
+
+use crate::ui_controller::enum_ui::{EnumHolder, EnumUi};
+use app_util::AppError;
+use enum_len_derive::EnumLen;
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::FromPrimitive;
+
+use crate::simulation_command::ControllerValue;
+
+#[derive(FromPrimitive, ToPrimitive, EnumLen, Copy, Clone, Debug, PartialEq)]
+pub enum ScanOutputOptions {
+    Off,
+    RollingScan,
+    BlackFrameInsertion,
+}
+
+impl std::fmt::Display for ScanOutputOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ScanOutputOptions::Off => write!(f, "Off"),
+            ScanOutputOptions::RollingScan => write!(f, "Rolling scan"),
+            ScanOutputOptions::BlackFrameInsertion => write!(f, "Black frame insertion"),
+        }
+    }
+}
+
+impl EnumUi for ScanOutputOptions {
+    fn event_tag(&self) -> &'static str {
+        ""
+    }
+    fn keys_inc(&self) -> &[&'static str] {
+        &["scan-output-inc"]
+    }
+    fn keys_dec(&self) -> &[&'static str] {
+        &["scan-output-dec"]
+    }
+    fn dispatch_tag(&self) -> &'static str {
+        "back2front:scan_output"
+    }
+}
+
+impl TryFrom<ControllerValue> for ScanOutputOptions {
+    type Error = AppError;
+
+    fn try_from(value: ControllerValue) -> Result<Self, Self::Error> {
+        if let Some(option) = ScanOutputOptions::from_usize(value.to_usize()?) {
+            Ok(option)
+        } else {
+            Err(AppError::from("Incorrect value for ScanOutputOptions"))
+        }
+    }
+}
+
+pub type ScanOutput = EnumHolder<'static, ScanOutputOptions>;
@@ -23,9 +23,8 @@ use crate::simulation_command::ControllerValue;
 #[derive(FromPrimitive, ToPrimitive, EnumLen, Copy, Clone)]
 pub enum ScreenCurvatureKindOptions {
     Flat,
-    Curved1,
-    Curved2,
-    Curved3,
+    Spherical,
+    Cylindrical,
     Pulse,
 }
 
@@ -33,9 +32,8 @@ impl std::fmt::Display for ScreenCurvatureKindOptions {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
             ScreenCurvatureKindOptions::Flat => write!(f, "Flat"),
-            ScreenCurvatureKindOptions::Curved1 => write!(f, "Curved 1"),
-            ScreenCurvatureKindOptions::Curved2 => write!(f, "Curved 2"),
-            ScreenCurvatureKindOptions::Curved3 => write!(f, "Curved 3"),
+            ScreenCurvatureKindOptions::Spherical => write!(f, "Spherical"),
+            ScreenCurvatureKindOptions::Cylindrical => write!(f, "Cylindrical"),
             ScreenCurvatureKindOptions::Pulse => write!(f, "Weavy"),
         }
     }
@@ -60,10 +58,9 @@ impl From<ControllerValue> for ScreenCurvatureKindOptions {
     fn from(value: ControllerValue) -> Self {
         match value.to_usize() {
             Ok(0) => ScreenCurvatureKindOptions::Flat,
-            Ok(1) => ScreenCurvatureKindOptions::Curved1,
-            Ok(2) => ScreenCurvatureKindOptions::Curved2,
-            Ok(3) => ScreenCurvatureKindOptions::Curved3,
-            Ok(4) => ScreenCurvatureKindOptions::Pulse,
+            Ok(1) => ScreenCurvatureKindOptions::Spherical,
+            Ok(2) => ScreenCurvatureKindOptions::Cylindrical,
+            Ok(3) => ScreenCurvatureKindOptions::Pulse,
             Ok(x) => {
                 log_error(&format!("Unexpected ScreenCurvatureKindOptions value {}", x));
                 ScreenCurvatureKindOptions::Flat
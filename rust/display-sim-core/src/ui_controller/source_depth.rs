@@ -0,0 +1,76 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use std::convert::TryFrom;
+
+use crate::ui_controller::enum_ui::{EnumHolder, EnumUi};
+use app_util::AppError;
+use enum_len_derive::EnumLen;
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::FromPrimitive;
+
+use crate::simulation_command::ControllerValue;
+
+#[derive(FromPrimitive, ToPrimitive, EnumLen, Copy, Clone, Debug, PartialEq)]
+pub enum SourceDepthOptions {
+    Off,
+    Rgb555,
+    Rgb444,
+    Rgb333,
+    Nes,
+    Cga,
+}
+
+impl std::fmt::Display for SourceDepthOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            SourceDepthOptions::Off => write!(f, "Off"),
+            SourceDepthOptions::Rgb555 => write!(f, "15-bit RGB555"),
+            SourceDepthOptions::Rgb444 => write!(f, "12-bit RGB444"),
+            SourceDepthOptions::Rgb333 => write!(f, "9-bit RGB333 (Genesis)"),
+            SourceDepthOptions::Nes => write!(f, "NES palette"),
+            SourceDepthOptions::Cga => write!(f, "CGA palette"),
+        }
+    }
+}
+
+impl EnumUi for SourceDepthOptions {
+    fn event_tag(&self) -> &'static str {
+        ""
+    }
+    fn keys_inc(&self) -> &[&'static str] {
+        &["source-depth-inc"]
+    }
+    fn keys_dec(&self) -> &[&'static str] {
+        &["source-depth-dec"]
+    }
+    fn dispatch_tag(&self) -> &'static str {
+        "back2front:source_depth"
+    }
+}
+
+impl TryFrom<ControllerValue> for SourceDepthOptions {
+    type Error = AppError;
+
+    fn try_from(value: ControllerValue) -> Result<Self, Self::Error> {
+        if let Some(option) = SourceDepthOptions::from_usize(value.to_usize()?) {
+            Ok(option)
+        } else {
+            Err(AppError::from("Incorrect value for SourceDepthOptions"))
+        }
+    }
+}
+
+pub type SourceDepth = EnumHolder<'static, SourceDepthOptions>;
@@ -0,0 +1,72 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use std::convert::TryFrom;
+
+use crate::ui_controller::enum_ui::{EnumHolder, EnumUi};
+use app_util::AppError;
+use enum_len_derive::EnumLen;
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::FromPrimitive;
+
+use crate::simulation_command::ControllerValue;
+
+#[derive(FromPrimitive, ToPrimitive, EnumLen, Copy, Clone, Debug, PartialEq)]
+pub enum SourceTransferOptions {
+    Off,
+    Srgb,
+    Bt1886,
+    Power,
+}
+
+impl std::fmt::Display for SourceTransferOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            SourceTransferOptions::Off => write!(f, "Off (gamma space)"),
+            SourceTransferOptions::Srgb => write!(f, "sRGB"),
+            SourceTransferOptions::Bt1886 => write!(f, "BT.1886"),
+            SourceTransferOptions::Power => write!(f, "Pure power"),
+        }
+    }
+}
+
+impl EnumUi for SourceTransferOptions {
+    fn event_tag(&self) -> &'static str {
+        ""
+    }
+    fn keys_inc(&self) -> &[&'static str] {
+        &["source-transfer-inc"]
+    }
+    fn keys_dec(&self) -> &[&'static str] {
+        &["source-transfer-dec"]
+    }
+    fn dispatch_tag(&self) -> &'static str {
+        "back2front:source_transfer"
+    }
+}
+
+impl TryFrom<ControllerValue> for SourceTransferOptions {
+    type Error = AppError;
+
+    fn try_from(value: ControllerValue) -> Result<Self, Self::Error> {
+        if let Some(option) = SourceTransferOptions::from_usize(value.to_usize()?) {
+            Ok(option)
+        } else {
+            Err(AppError::from("Incorrect value for SourceTransferOptions"))
+        }
+    }
+}
+
+pub type SourceTransfer = EnumHolder<'static, SourceTransferOptions>;
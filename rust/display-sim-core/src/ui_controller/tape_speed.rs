@@ -0,0 +1,72 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use std::convert::TryFrom;
+
+use crate::ui_controller::enum_ui::{EnumHolder, EnumUi};
+use app_util::AppError;
+use enum_len_derive::EnumLen;
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::FromPrimitive;
+
+use crate::simulation_command::ControllerValue;
+
+#[derive(FromPrimitive, ToPrimitive, EnumLen, Copy, Clone, Debug, PartialEq)]
+pub enum TapeSpeedOptions {
+    Off,
+    Sp,
+    Lp,
+    Ep,
+}
+
+impl std::fmt::Display for TapeSpeedOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            TapeSpeedOptions::Off => write!(f, "Off"),
+            TapeSpeedOptions::Sp => write!(f, "SP"),
+            TapeSpeedOptions::Lp => write!(f, "LP"),
+            TapeSpeedOptions::Ep => write!(f, "EP"),
+        }
+    }
+}
+
+impl EnumUi for TapeSpeedOptions {
+    fn event_tag(&self) -> &'static str {
+        ""
+    }
+    fn keys_inc(&self) -> &[&'static str] {
+        &["tape-speed-inc"]
+    }
+    fn keys_dec(&self) -> &[&'static str] {
+        &["tape-speed-dec"]
+    }
+    fn dispatch_tag(&self) -> &'static str {
+        "back2front:tape_speed"
+    }
+}
+
+impl TryFrom<ControllerValue> for TapeSpeedOptions {
+    type Error = AppError;
+
+    fn try_from(value: ControllerValue) -> Result<Self, Self::Error> {
+        if let Some(option) = TapeSpeedOptions::from_usize(value.to_usize()?) {
+            Ok(option)
+        } else {
+            Err(AppError::from("Incorrect value for TapeSpeedOptions"))
+        }
+    }
+}
+
+pub type TapeSpeed = EnumHolder<'static, TapeSpeedOptions>;
@@ -0,0 +1,287 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::simulation_core_state::AnimationStep;
+use app_util::{AppError, AppResult};
+
+const MAGIC: &[u8; 4] = b"VECT";
+const HEADER_LEN: usize = 8;
+const FRAME_HEADER_LEN: usize = 4;
+const SEGMENT_LEN: usize = 12;
+
+/// Floats per vertex in `VectorFrame::beam_vertices`: position, position
+/// along and across the segment, segment length, and color.
+pub const VECTOR_VERTEX_STRIDE: usize = 8;
+
+/// How far the glow of a line reaches, in beam widths.
+const GLOW_REACH: f32 = 2.0;
+
+/// Vector monitor input, as in Asteroids or Tempest: frames of line segments
+/// that the beam traces directly, instead of a raster image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VectorScene {
+    pub width: u32,
+    pub height: u32,
+    pub frames: Vec<VectorFrame>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VectorFrame {
+    pub delay: u32,
+    pub segments: Vec<VectorSegment>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VectorSegment {
+    pub from: [f32; 2],
+    pub to: [f32; 2],
+    pub intensity: f32,
+    pub color: [f32; 3],
+}
+
+/// How the beam draws the current frame, consumed by the vector render.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VectorBeam {
+    /// Width of the beam spot, in scene units.
+    pub width: f32,
+    /// Extra brightness where the beam stops at the ends of a line.
+    pub hot_spot: f32,
+    /// Part of the phosphor glow that survives since the previous frame.
+    pub persistence: f32,
+    pub bloom: f32,
+    pub tint: [f32; 3],
+}
+
+impl VectorBeam {
+    /// `persistence` is the time in ms for the glow to fall to 1/e.
+    pub fn new(width: f32, hot_spot: f32, persistence: f32, bloom: f32, tint: [f32; 3], dt: f32) -> VectorBeam {
+        VectorBeam {
+            width,
+            hot_spot,
+            persistence: if persistence > 0.0 { (-dt * 1000.0 / persistence).exp() } else { 0.0 },
+            bloom,
+            tint,
+        }
+    }
+
+    /// What the beam adds to the phosphor each frame, so that a still line
+    /// settles at its own brightness at any frame rate.
+    pub fn deposit(&self) -> f32 {
+        1.0 - self.persistence
+    }
+}
+
+impl VectorScene {
+    /// Reads a scene in a small little endian binary format:
+    ///
+    /// ```text
+    /// "VECT"  u16 width  u16 height
+    /// for each frame:    u16 delay in ms  u16 segment count
+    /// for each segment:  i16 x0 y0 x1 y1  u8 intensity  u8 red green blue
+    /// ```
+    ///
+    /// Coordinates start at the bottom left corner of the `width` x `height`
+    /// field, like the Atari vector generators, and may fall outside of it.
+    pub fn parse(bytes: &[u8]) -> AppResult<VectorScene> {
+        if bytes.len() < HEADER_LEN || &bytes[0..4] != MAGIC {
+            return Err(AppError::new("vector scene does not start with 'VECT'".into()));
+        }
+        let width = u32::from(read_u16(bytes, 4));
+        let height = u32::from(read_u16(bytes, 6));
+        if width == 0 || height == 0 {
+            return Err(AppError::new("vector scene has an empty field".into()));
+        }
+
+        let mut frames = Vec::new();
+        let mut offset = HEADER_LEN;
+        while offset < bytes.len() {
+            let frame_number = frames.len() + 1;
+            if offset + FRAME_HEADER_LEN > bytes.len() {
+                return Err(vector_error(frame_number, "header is cut short"));
+            }
+            let delay = u32::from(read_u16(bytes, offset));
+            let count = usize::from(read_u16(bytes, offset + 2));
+            offset += FRAME_HEADER_LEN;
+            if offset + count * SEGMENT_LEN > bytes.len() {
+                return Err(vector_error(frame_number, &format!("expects {} segments, but the file ends before", count)));
+            }
+            let segments = bytes[offset..offset + count * SEGMENT_LEN]
+                .chunks_exact(SEGMENT_LEN)
+                .map(|segment| VectorSegment {
+                    from: [f32::from(read_i16(segment, 0)), f32::from(read_i16(segment, 2))],
+                    to: [f32::from(read_i16(segment, 4)), f32::from(read_i16(segment, 6))],
+                    intensity: f32::from(segment[8]) / 255.0,
+                    color: [f32::from(segment[9]) / 255.0, f32::from(segment[10]) / 255.0, f32::from(segment[11]) / 255.0],
+                })
+                .collect();
+            offset += count * SEGMENT_LEN;
+            frames.push(VectorFrame { delay, segments });
+        }
+        if frames.is_empty() {
+            return Err(AppError::new("vector scene has no frames".into()));
+        }
+        Ok(VectorScene { width, height, frames })
+    }
+
+    /// Frame timings for `VideoInputResources::steps`, which then selects the current frame.
+    pub fn animation_steps(&self) -> Vec<AnimationStep> {
+        self.frames.iter().map(|frame| AnimationStep { delay: frame.delay }).collect()
+    }
+}
+
+impl VectorFrame {
+    /// Two triangles per segment, wide enough to hold the glow around it.
+    pub fn beam_vertices(&self, beam: &VectorBeam) -> Vec<f32> {
+        let reach = beam.width * GLOW_REACH;
+        let mut vertices = Vec::with_capacity(self.segments.len() * 6 * VECTOR_VERTEX_STRIDE);
+        for segment in &self.segments {
+            let delta = [segment.to[0] - segment.from[0], segment.to[1] - segment.from[1]];
+            let length = (delta[0] * delta[0] + delta[1] * delta[1]).sqrt();
+            // A dot has no direction of its own, any will do.
+            let along = if length > 0.0 { [delta[0] / length, delta[1] / length] } else { [1.0, 0.0] };
+            let across = [-along[1], along[0]];
+            let color = [
+                segment.color[0] * segment.intensity * beam.tint[0],
+                segment.color[1] * segment.intensity * beam.tint[1],
+                segment.color[2] * segment.intensity * beam.tint[2],
+            ];
+            let corner = |t: f32, s: f32| {
+                [
+                    segment.from[0] + along[0] * t + across[0] * s,
+                    segment.from[1] + along[1] * t + across[1] * s,
+                    t,
+                    s,
+                    length,
+                    color[0],
+                    color[1],
+                    color[2],
+                ]
+            };
+            let quad = [
+                corner(-reach, -reach),
+                corner(length + reach, -reach),
+                corner(length + reach, reach),
+                corner(-reach, reach),
+            ];
+            for index in [0, 1, 2, 0, 2, 3] {
+                vertices.extend_from_slice(&quad[index]);
+            }
+        }
+        vertices
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn read_i16(bytes: &[u8], offset: usize) -> i16 {
+    i16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn vector_error(frame_number: usize, message: &str) -> AppError {
+    AppError::new(format!("vector scene frame {}: {}", frame_number, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene_bytes(width: u16, height: u16, frames: &[(u16, &[[i16; 4]])]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        for (delay, segments) in frames {
+            bytes.extend_from_slice(&delay.to_le_bytes());
+            bytes.extend_from_slice(&(segments.len() as u16).to_le_bytes());
+            for segment in segments.iter() {
+                for coordinate in segment {
+                    bytes.extend_from_slice(&coordinate.to_le_bytes());
+                }
+                bytes.extend_from_slice(&[255, 255, 0, 0]);
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn parses_frames_and_their_timings() -> AppResult<()> {
+        let bytes = scene_bytes(1024, 768, &[(16, &[[0, 0, 100, -50], [10, 10, 10, 10]]), (33, &[])]);
+
+        let scene = VectorScene::parse(&bytes)?;
+
+        assert_eq!((scene.width, scene.height), (1024, 768));
+        assert_eq!(scene.frames.len(), 2);
+        assert_eq!(
+            scene.frames[0].segments[0],
+            VectorSegment {
+                from: [0.0, 0.0],
+                to: [100.0, -50.0],
+                intensity: 1.0,
+                color: [1.0, 0.0, 0.0],
+            }
+        );
+        assert!(scene.frames[1].segments.is_empty());
+        assert_eq!(scene.animation_steps().iter().map(|step| step.delay).collect::<Vec<_>>(), vec![16, 33]);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_truncated_and_foreign_files() {
+        let mut bytes = scene_bytes(1024, 768, &[(16, &[[0, 0, 100, 100]])]);
+        bytes.pop();
+        assert!(VectorScene::parse(&bytes).is_err());
+        assert!(VectorScene::parse(b"\x89PNG\r\n\x1a\n").is_err());
+        assert!(VectorScene::parse(&scene_bytes(1024, 768, &[])).is_err());
+    }
+
+    #[test]
+    fn segment_quads_cover_the_glow_past_both_ends() {
+        let frame = VectorFrame {
+            delay: 16,
+            segments: vec![VectorSegment {
+                from: [10.0, 20.0],
+                to: [10.0, 60.0],
+                intensity: 0.5,
+                color: [1.0, 1.0, 1.0],
+            }],
+        };
+        let beam = VectorBeam::new(2.0, 1.0, 0.0, 0.0, [1.0, 1.0, 1.0], 0.016);
+
+        let vertices = frame.beam_vertices(&beam);
+
+        assert_eq!(vertices.len(), 6 * VECTOR_VERTEX_STRIDE);
+        let ys: Vec<f32> = vertices.chunks_exact(VECTOR_VERTEX_STRIDE).map(|vertex| vertex[1]).collect();
+        let xs: Vec<f32> = vertices.chunks_exact(VECTOR_VERTEX_STRIDE).map(|vertex| vertex[0]).collect();
+        assert_eq!(ys.iter().cloned().fold(f32::MAX, f32::min), 16.0);
+        assert_eq!(ys.iter().cloned().fold(f32::MIN, f32::max), 64.0);
+        assert_eq!(xs.iter().cloned().fold(f32::MAX, f32::min), 6.0);
+        assert_eq!(xs.iter().cloned().fold(f32::MIN, f32::max), 14.0);
+        assert_eq!(&vertices[4..8], &[40.0, 0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn still_lines_settle_at_the_same_brightness_at_any_frame_rate() {
+        for dt in [1.0 / 30.0, 1.0 / 144.0] {
+            let beam = VectorBeam::new(1.0, 0.0, 50.0, 0.0, [1.0, 1.0, 1.0], dt);
+            let mut glow = 0.0;
+            for _ in 0..2000 {
+                glow = glow * beam.persistence + beam.deposit();
+            }
+            assert!((glow - 1.0).abs() < 1e-3, "{} at {}", glow, dt);
+        }
+        assert_eq!(VectorBeam::new(1.0, 0.0, 0.0, 0.0, [1.0, 1.0, 1.0], 0.016).persistence, 0.0);
+    }
+}
@@ -14,7 +14,10 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
 
 use core::app_events::AppEventDispatcher;
+use core::artwork_layout::ArtworkLayout;
+use core::cabinet_model::CabinetModel;
 use core::camera::CameraLockMode;
+use core::color_lut::ColorLut;
 use core::general_types::Size2D;
 use core::input_types::Input;
 use core::simulation_command::{Pressed, SimulationCommand, SimulationCommandBus};
@@ -22,9 +25,12 @@ use core::simulation_context::{ConcreteSimulationContext, RandomGenerator};
 use core::simulation_core_state::ScalingMethod;
 use core::simulation_core_state::{AnimationStep, Resources, VideoInputResources};
 use core::simulation_core_ticker::SimulationCoreTicker;
+use core::tube_aging::BurnInMap;
+use core::ui_controller::filter_preset::FilterPresetOptions;
+use core::vector_display::VectorScene;
 use render::error::AppResult;
 use render::simulation_draw::{present_to_default_framebuffer, SimulationDrawer};
-use render::simulation_render_state::{Materials, VideoInputMaterials};
+use render::simulation_render_state::{ArtworkImage, Materials, VideoInputMaterials};
 use sim_ui::{shared_panel_events, SharedPanelEvents, SimPanel, SimPanelSection};
 
 use std::cell::Cell;
@@ -46,6 +52,8 @@ use glow::GlowSafeAdapter;
 use crate::simulation_input::{browser_wheel_delta, SimulationKeyboardInput, SimulationPointerInput};
 use crate::winit_egui::WinitEguiInput;
 
+const DEFAULT_FRAMERATE: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
 pub fn main() {
     if let Err(e) = program() {
         println!("Error: {:?}", e);
@@ -63,6 +71,58 @@ impl RandomGenerator for NativeRnd {
     }
 }
 
+/// Loads a `.lay` artwork layout, with its images resolved relative to it.
+fn load_artwork(layout_path: &Path) -> AppResult<(Option<ArtworkLayout>, Vec<ArtworkImage>)> {
+    println!("Loading artwork: {}", layout_path.display());
+    let source = std::fs::read_to_string(layout_path).map_err(|e| format!("{}: {}", layout_path.display(), e))?;
+    let layout = ArtworkLayout::parse(&source)?;
+    let directory = layout_path.parent().unwrap_or_else(|| Path::new(""));
+    let mut images = Vec::with_capacity(layout.layers.len());
+    for layer in &layout.layers {
+        let image_path = directory.join(&layer.file);
+        let image = image::open(&image_path).map_err(|e| format!("{}: {}", image_path.display(), e))?.to_rgba8();
+        let (width, height) = image.dimensions();
+        images.push(ArtworkImage {
+            width,
+            height,
+            pixels: image.into_vec().into_boxed_slice(),
+        });
+    }
+    Ok((Some(layout), images))
+}
+
+fn load_cabinet(model_path: &Path) -> AppResult<CabinetModel> {
+    println!("Loading cabinet: {}", model_path.display());
+    let source = std::fs::read_to_string(model_path).map_err(|e| format!("{}: {}", model_path.display(), e))?;
+    CabinetModel::parse_obj(&source)
+}
+
+fn load_color_lut(lut_path: &Path) -> AppResult<ColorLut> {
+    println!("Loading LUT: {}", lut_path.display());
+    let source = std::fs::read_to_string(lut_path).map_err(|e| format!("{}: {}", lut_path.display(), e))?;
+    ColorLut::parse(&source)
+}
+
+fn load_vector_scene(scene_path: &Path) -> AppResult<VectorScene> {
+    println!("Loading vector scene: {}", scene_path.display());
+    let bytes = std::fs::read(scene_path).map_err(|e| format!("{}: {}", scene_path.display(), e))?;
+    VectorScene::parse(&bytes)
+}
+
+/// A burn-in map image, brighter where the phosphors are more worn.
+fn load_burn_in(map_path: &Path) -> AppResult<BurnInMap> {
+    println!("Loading burn-in map: {}", map_path.display());
+    let image = image::open(map_path).map_err(|e| format!("{}: {}", map_path.display(), e))?.to_rgba8();
+    let (width, height) = image.dimensions();
+    Ok(BurnInMap::from_rgba8(width, height, image.as_raw()))
+}
+
+fn save_burn_in(map_path: &Path, burn_in: &BurnInMap) -> AppResult<()> {
+    println!("Saving burn-in map: {}", map_path.display());
+    image::save_buffer(map_path, &burn_in.to_rgba8(), burn_in.width, burn_in.height, image::ColorType::Rgba8)
+        .map_err(|e| format!("{}: {}", map_path.display(), e).into())
+}
+
 fn program() -> AppResult<()> {
     println!("Initializing Window.");
     let winit_loop = EventLoop::new();
@@ -101,15 +161,45 @@ fn program() -> AppResult<()> {
     let gl_ctx = Arc::new(unsafe { glow::Context::from_loader_function(|ptr| windowed_ctx.context().get_proc_address(ptr) as *const _) });
     println!("Pixel format of the window's GL context: {:?}", windowed_ctx.get_pixel_format());
 
-    let img_path = "www/assets/pics/frames/seiken.png";
-    println!("Loading image: {}", img_path);
-    let img = image::open(img_path).map_err(|e| format!("{}", e))?.to_rgba8();
-    let img_size = img.dimensions();
-    let pixels = img.into_vec().into_boxed_slice();
+    // A vector scene takes the place of the picture, and brings its own frame timings.
+    let (img_size, steps, buffers, vector) = match std::env::var_os("DISPLAY_SIM_VECTOR") {
+        Some(scene_path) => {
+            let scene = load_vector_scene(Path::new(&scene_path))?;
+            ((scene.width, scene.height), scene.animation_steps(), Vec::new(), Some(scene))
+        }
+        None => {
+            let img_path = "www/assets/pics/frames/seiken.png";
+            println!("Loading image: {}", img_path);
+            let img = image::open(img_path).map_err(|e| format!("{}", e))?.to_rgba8();
+            let img_size = img.dimensions();
+            (img_size, vec![AnimationStep { delay: 16 }], vec![img.into_vec().into_boxed_slice()], None)
+        }
+    };
+
+    let (artwork, artwork_images) = match std::env::var_os("DISPLAY_SIM_ARTWORK") {
+        Some(layout_path) => load_artwork(Path::new(&layout_path))?,
+        None => (None, Vec::new()),
+    };
+
+    let cabinet = match std::env::var_os("DISPLAY_SIM_CABINET") {
+        Some(model_path) => Some(load_cabinet(Path::new(&model_path))?),
+        None => None,
+    };
+
+    let color_lut = match std::env::var_os("DISPLAY_SIM_LUT") {
+        Some(lut_path) => Some(load_color_lut(Path::new(&lut_path))?),
+        None => None,
+    };
+
+    // The map does not need to exist yet, the tube is saved there when closing.
+    let burn_in = match std::env::var_os("DISPLAY_SIM_BURN_IN") {
+        Some(map_path) if Path::new(&map_path).exists() => Some(load_burn_in(Path::new(&map_path))?),
+        _ => None,
+    };
 
     let physical_size = windowed_ctx.window().inner_size();
     let res_input = VideoInputResources {
-        steps: vec![AnimationStep { delay: 16 }],
+        steps,
         max_texture_size: std::i32::MAX,
         image_size: Size2D {
             width: img_size.0,
@@ -124,12 +214,22 @@ fn program() -> AppResult<()> {
             height: physical_size.height,
         },
         current_frame: 0,
-        preset: None,
+        preset: vector.as_ref().map(|_| FilterPresetOptions::VectorColor),
         last_frame_change: 0.0,
         needs_buffer_data_load: true,
+        needs_panel_settle: false,
         drawing_activation: true,
+        artwork,
+        cabinet,
+        color_lut,
+        color_lut_generation: 0,
+        burn_in,
+        vector,
+    };
+    let materials_input = VideoInputMaterials {
+        buffers,
+        artwork: artwork_images,
     };
-    let materials_input = VideoInputMaterials { buffers: vec![pixels] };
 
     println!("Preparing resources.");
     let mut res = Resources::default();
@@ -149,6 +249,7 @@ fn program() -> AppResult<()> {
             "image-scaling" => Some(SimPanelSection::ImageScaling),
             "performance" => Some(SimPanelSection::Performance),
             "colors" => Some(SimPanelSection::Colors),
+            "video-signal" => Some(SimPanelSection::VideoSignal),
             "geometry-and-textures" => Some(SimPanelSection::GeometryAndTextures),
             "camera" => Some(SimPanelSection::Camera),
             "command-modifiers" => Some(SimPanelSection::CommandModifiers),
@@ -164,7 +265,7 @@ fn program() -> AppResult<()> {
     println!("Preparing simulation context.");
     let sim_ctx = ConcreteSimulationContext::new(NativeEventDispatcher::new(windowed_ctx.clone(), adapter, panel_events.clone()), NativeRnd {});
 
-    let timings = Timings::new(Instant::now(), Duration::from_secs_f64(1.0 / 60.0));
+    let timings = Timings::new(Instant::now(), DEFAULT_FRAMERATE);
 
     let mut state = NativeSimulationState::new(
         sim_ctx,
@@ -203,6 +304,7 @@ struct NativeSimulationState {
     egui_input: WinitEguiInput,
     painter: Option<egui_glow::Painter>,
     ui_capture_path: Option<PathBuf>,
+    burn_in_path: Option<PathBuf>,
     has_simulation_frame: bool,
     simulation_pointer: SimulationPointerInput,
     canvas_focused: bool,
@@ -256,6 +358,7 @@ impl NativeSimulationState {
             egui_input,
             painter: Some(painter),
             ui_capture_path: std::env::var_os("DISPLAY_SIM_CAPTURE_UI").map(PathBuf::from),
+            burn_in_path: std::env::var_os("DISPLAY_SIM_BURN_IN").map(PathBuf::from),
             has_simulation_frame: false,
             simulation_pointer: SimulationPointerInput::default(),
             canvas_focused: false,
@@ -475,6 +578,11 @@ impl NativeSimulationState {
                     Err(e) => println!("Tick error: {:?}", e),
                 };
             }
+            // Scan output draws one sub-frame per refresh of the real display.
+            self.timings.framerate = match self.res.main.render.scan_band {
+                Some(_) => Duration::from_secs_f64(1.0 / self.res.controllers.display_refresh.value as f64),
+                None => DEFAULT_FRAMERATE,
+            };
 
             let screenshot_frame = self.res.screenshot_trigger.is_triggered;
             let reuse_simulation_for_ui_capture = self.ui_capture_path.is_some() && self.has_simulation_frame;
@@ -528,6 +636,9 @@ impl NativeSimulationState {
 
             if self.res.quit {
                 println!("User closed the simulation.");
+                if let (Some(path), Some(burn_in)) = (&self.burn_in_path, self.materials.pixels_render.burn_in()) {
+                    save_burn_in(path, burn_in)?;
+                }
                 *control_flow = ControlFlow::Exit;
             }
 
@@ -0,0 +1,162 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::error::AppResult;
+use crate::shaders::{make_quad_vao, make_shader};
+use crate::simulation_render_state::ArtworkImage;
+use core::artwork_layout::ArtworkQuad;
+use core::lcd_panel::PanelSubstrate;
+
+use glow::GlowSafeAdapter;
+use glow::HasContext;
+use std::rc::Rc;
+
+/// Draws bezel and backdrop images on the plane of the screen. Backdrops are
+/// blended under what is already in the target and bezels over it, so both
+/// can be composited after the screen has been rendered. The same quad also
+/// draws the solid substrate of unlit LCD panels.
+pub struct ArtworkRender<GL: HasContext> {
+    vao: Option<GL::VertexArray>,
+    shader: GL::Program,
+    textures: Vec<Option<GL::Texture>>,
+    gl: Rc<GlowSafeAdapter<GL>>,
+}
+
+impl<GL: HasContext> ArtworkRender<GL> {
+    pub fn new(gl: Rc<GlowSafeAdapter<GL>>, images: &[ArtworkImage]) -> AppResult<ArtworkRender<GL>> {
+        let shader = make_shader(&*gl, ARTWORK_VERTEX_SHADER, ARTWORK_FRAGMENT_SHADER)?;
+        let vao = make_quad_vao(&*gl, &shader)?;
+        let mut textures = Vec::with_capacity(images.len());
+        for image in images {
+            let texture = Some(gl.create_texture()?);
+            gl.bind_texture(glow::TEXTURE_2D, texture);
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                glow::RGBA as i32,
+                image.width as i32,
+                image.height as i32,
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                Some(&image.pixels),
+            );
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::LINEAR as i32);
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::LINEAR as i32);
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE as i32);
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE as i32);
+            textures.push(texture);
+        }
+        gl.bind_texture(glow::TEXTURE_2D, None);
+        Ok(ArtworkRender { vao, shader, textures, gl })
+    }
+
+    pub fn render(&self, quads: &[ArtworkQuad], view: &[f32; 16], projection: &[f32; 16]) {
+        if quads.is_empty() {
+            return;
+        }
+        let gl = &self.gl;
+        gl.disable(glow::DEPTH_TEST);
+        gl.enable(glow::BLEND);
+        gl.bind_vertex_array(self.vao);
+        gl.use_program(Some(self.shader));
+        gl.uniform_matrix_4_f32_slice(gl.get_uniform_location(self.shader, "view"), false, view);
+        gl.uniform_matrix_4_f32_slice(gl.get_uniform_location(self.shader, "projection"), false, projection);
+        gl.uniform_1_i32(gl.get_uniform_location(self.shader, "image"), 0);
+        gl.active_texture(glow::TEXTURE0 + 0);
+
+        // Backdrops from front to back, so each one only fills what is still transparent.
+        gl.blend_func(glow::ONE_MINUS_DST_ALPHA, glow::ONE);
+        for quad in quads.iter().rev().filter(|quad| !quad.over_screen) {
+            self.render_quad(quad);
+        }
+        gl.blend_func(glow::ONE, glow::ONE_MINUS_SRC_ALPHA);
+        for quad in quads.iter().filter(|quad| quad.over_screen) {
+            self.render_quad(quad);
+        }
+
+        gl.bind_texture(glow::TEXTURE_2D, None);
+        gl.disable(glow::BLEND);
+        gl.enable(glow::DEPTH_TEST);
+    }
+
+    /// Fills what is still transparent on the screen plane with the panel substrate.
+    pub fn render_substrate(&self, substrate: &PanelSubstrate, view: &[f32; 16], projection: &[f32; 16]) {
+        let gl = &self.gl;
+        gl.disable(glow::DEPTH_TEST);
+        gl.enable(glow::BLEND);
+        gl.blend_func(glow::ONE_MINUS_DST_ALPHA, glow::ONE);
+        gl.bind_vertex_array(self.vao);
+        gl.use_program(Some(self.shader));
+        gl.uniform_matrix_4_f32_slice(gl.get_uniform_location(self.shader, "view"), false, view);
+        gl.uniform_matrix_4_f32_slice(gl.get_uniform_location(self.shader, "projection"), false, projection);
+        let solid_color = [substrate.color[0], substrate.color[1], substrate.color[2], 1.0];
+        gl.uniform_4_f32_slice(gl.get_uniform_location(self.shader, "solidColor"), &solid_color);
+        gl.uniform_2_f32_slice(gl.get_uniform_location(self.shader, "center"), &[0.0, 0.0]);
+        gl.uniform_2_f32_slice(gl.get_uniform_location(self.shader, "size"), &substrate.size);
+        gl.draw_elements(glow::TRIANGLES, 6, glow::UNSIGNED_INT, 0);
+        gl.uniform_4_f32_slice(gl.get_uniform_location(self.shader, "solidColor"), &[0.0, 0.0, 0.0, 0.0]);
+        gl.disable(glow::BLEND);
+        gl.enable(glow::DEPTH_TEST);
+    }
+
+    fn render_quad(&self, quad: &ArtworkQuad) {
+        let texture = match self.textures.get(quad.layer) {
+            Some(texture) => *texture,
+            None => return,
+        };
+        self.gl.bind_texture(glow::TEXTURE_2D, texture);
+        self.gl.uniform_2_f32_slice(self.gl.get_uniform_location(self.shader, "center"), &quad.center);
+        self.gl.uniform_2_f32_slice(self.gl.get_uniform_location(self.shader, "size"), &quad.size);
+        self.gl.draw_elements(glow::TRIANGLES, 6, glow::UNSIGNED_INT, 0);
+    }
+}
+
+pub const ARTWORK_VERTEX_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+layout (location = 0) in vec3 qPos;
+layout (location = 1) in vec2 qTexCoords;
+
+out vec2 TexCoord;
+
+uniform mat4 view;
+uniform mat4 projection;
+uniform vec2 center;
+uniform vec2 size;
+
+void main()
+{
+    // Artwork images are stored top row first.
+    TexCoord = vec2(qTexCoords.x, 1.0 - qTexCoords.y);
+    gl_Position = projection * view * vec4(center + qPos.xy * size * 0.5, 0.0, 1.0);
+}
+"#;
+
+pub const ARTWORK_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+out vec4 FragColor;
+in vec2 TexCoord;
+
+uniform sampler2D image;
+uniform vec4 solidColor;
+
+void main()
+{
+    vec4 color = solidColor.a > 0.0 ? solidColor : texture(image, TexCoord);
+    FragColor = vec4(color.rgb * color.a, color.a);
+}
+"#;
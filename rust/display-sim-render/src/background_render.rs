@@ -14,7 +14,8 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
 
 use crate::error::AppResult;
-use crate::shaders::{make_quad_vao, make_shader, TEXTURE_VERTEX_SHADER};
+use crate::shaders::{make_quad_vao, make_shader, with_transfer_functions, TEXTURE_VERTEX_SHADER};
+use core::transfer_function::LinearLight;
 
 use glow::GlowSafeAdapter;
 use glow::HasContext;
@@ -28,16 +29,26 @@ pub struct BackgroundRender<GL: HasContext> {
 
 impl<GL: HasContext> BackgroundRender<GL> {
     pub fn new(gl: Rc<GlowSafeAdapter<GL>>) -> AppResult<BackgroundRender<GL>> {
-        let shader = make_shader(&*gl, TEXTURE_VERTEX_SHADER, BACKGROUND_FRAGMENT_SHADER)?;
+        let shader = make_shader(&*gl, TEXTURE_VERTEX_SHADER, &with_transfer_functions(BACKGROUND_FRAGMENT_SHADER))?;
         let vao = make_quad_vao(&*gl, &shader)?;
         Ok(BackgroundRender { vao, shader, gl })
     }
 
-    pub fn render(&self) {
+    /// In linear light the composition is also where the picture gets encoded for the display.
+    pub fn render(&self, linear_light: &Option<LinearLight>) {
         self.gl.bind_vertex_array(self.vao);
         self.gl.use_program(Some(self.shader));
         self.gl.uniform_1_i32(self.gl.get_uniform_location(self.shader, "foregroundImage"), 0);
         self.gl.uniform_1_i32(self.gl.get_uniform_location(self.shader, "backgroundImage"), 1);
+        let display = linear_light.map(|linear_light| linear_light.display);
+        self.gl.uniform_1_f32(
+            self.gl.get_uniform_location(self.shader, "encode_transfer"),
+            display.map_or(0.0, |curve| curve.shader_kind()),
+        );
+        self.gl.uniform_1_f32(
+            self.gl.get_uniform_location(self.shader, "transfer_power"),
+            display.map_or(1.0, |curve| curve.shader_power()),
+        );
         self.gl.draw_elements(glow::TRIANGLES, 6, glow::UNSIGNED_INT, 0);
     }
 }
@@ -50,6 +61,8 @@ in vec2 TexCoord;
 
 uniform sampler2D foregroundImage;
 uniform sampler2D backgroundImage;
+uniform float encode_transfer;
+uniform float transfer_power;
 
 void main()
 {
@@ -64,5 +77,6 @@ void main()
     }
     float factor = weight1 / (weight1 + backgroundWeight * 0.1);
     FragColor = result1 * factor + (1.0 - factor) * backgroundColor;
+    FragColor.rgb = fromLinear(FragColor.rgb, encode_transfer, transfer_power);
 } 
 "#;
@@ -15,7 +15,8 @@
 
 use crate::error::AppResult;
 use crate::render_types::{TextureBuffer, TextureBufferStack};
-use crate::shaders::{make_quad_vao, make_shader, TEXTURE_VERTEX_SHADER};
+use crate::shaders::{make_quad_vao, make_shader, with_transfer_functions, TEXTURE_VERTEX_SHADER};
+use core::transfer_function::TransferCurve;
 
 use glow::GlowSafeAdapter;
 use glow::HasContext;
@@ -29,12 +30,21 @@ pub struct BlurRender<GL: HasContext> {
 
 impl<GL: HasContext> BlurRender<GL> {
     pub fn new(gl: Rc<GlowSafeAdapter<GL>>) -> AppResult<BlurRender<GL>> {
-        let shader = make_shader(&*gl, TEXTURE_VERTEX_SHADER, BLUR_FRAGMENT_SHADER)?;
+        let shader = make_shader(&*gl, TEXTURE_VERTEX_SHADER, &with_transfer_functions(BLUR_FRAGMENT_SHADER))?;
         let vao = make_quad_vao(&*gl, &shader)?;
         Ok(BlurRender { shader, vao, gl })
     }
 
-    pub fn render(&self, stack: &mut TextureBufferStack<GL>, source: &TextureBuffer<GL>, target: &TextureBuffer<GL>, passes: usize) -> AppResult<()> {
+    /// With a `transfer`, the source and the target hold encoded values and
+    /// the blur happens in linear light between them.
+    pub fn render(
+        &self,
+        stack: &mut TextureBufferStack<GL>,
+        source: &TextureBuffer<GL>,
+        target: &TextureBuffer<GL>,
+        passes: usize,
+        transfer: Option<TransferCurve>,
+    ) -> AppResult<()> {
         if passes < 1 {
             panic!("Should not be called when passes < 1!");
         }
@@ -44,6 +54,19 @@ impl<GL: HasContext> BlurRender<GL> {
 
         let texture_buffers = [stack.get_nth(0)?, stack.get_nth(-1)?];
 
+        let transfer_kind = transfer.map_or(0.0, TransferCurve::shader_kind);
+        let set_transfer = |decode: bool, encode: bool| {
+            let shader = self.shader;
+            self.gl.uniform_1_f32(
+                self.gl.get_uniform_location(shader, "decode_transfer"),
+                if decode { transfer_kind } else { 0.0 },
+            );
+            self.gl.uniform_1_f32(
+                self.gl.get_uniform_location(shader, "encode_transfer"),
+                if encode { transfer_kind } else { 0.0 },
+            );
+        };
+
         let blur_iteration = |texture: Option<GL::Texture>, tb: &TextureBuffer<GL>, horizontal: bool| {
             self.gl.bind_framebuffer(glow::FRAMEBUFFER, tb.framebuffer());
             self.gl.viewport(0, 0, tb.width, tb.height);
@@ -56,8 +79,14 @@ impl<GL: HasContext> BlurRender<GL> {
 
         self.gl.use_program(Some(self.shader));
         self.gl.bind_vertex_array(self.vao);
+        self.gl.uniform_1_f32(
+            self.gl.get_uniform_location(self.shader, "transfer_power"),
+            transfer.map_or(1.0, TransferCurve::shader_power),
+        );
 
+        set_transfer(true, false);
         blur_iteration(source.texture(), texture_buffers[0], true);
+        set_transfer(false, false);
         for i in 1..passes {
             let buffer_index = i % 2;
             let texture_index = (i + 1) % 2;
@@ -65,6 +94,7 @@ impl<GL: HasContext> BlurRender<GL> {
         }
         let buffer_index = passes % 2;
         let texture_index = (passes + 1) % 2;
+        set_transfer(false, true);
         blur_iteration(texture_buffers[texture_index].texture(), target, buffer_index == 0);
         self.gl.bind_vertex_array(None);
         self.gl.bind_texture(glow::TEXTURE_2D, None);
@@ -82,28 +112,35 @@ in vec2 TexCoord;
 
 uniform sampler2D image;
 uniform int horizontal;
+uniform float decode_transfer;
+uniform float encode_transfer;
+uniform float transfer_power;
 const float weight[5] = float[] (0.2270270270, 0.1945945946, 0.1216216216, 0.0540540541, 0.0162162162);
 
+vec3 sampleImage(vec2 coord) {
+    return toLinear(texture(image, coord).rgb, decode_transfer, transfer_power);
+}
+
 void main()
 {
     vec2 tex_offset = vec2(1.0, 1.0) / float(textureSize(image, 0)); // gets size of single texel
-    vec3 result = texture(image, TexCoord).rgb * weight[0];
+    vec3 result = sampleImage(TexCoord) * weight[0];
     if(horizontal == 1)
     {
         for(int i = 1; i < 5; ++i)
         {
-            result += texture(image, TexCoord + vec2(tex_offset.x * float(i), 0.0)).rgb * weight[i % 5];
-            result += texture(image, TexCoord - vec2(tex_offset.x * float(i), 0.0)).rgb * weight[i % 5];
+            result += sampleImage(TexCoord + vec2(tex_offset.x * float(i), 0.0)) * weight[i % 5];
+            result += sampleImage(TexCoord - vec2(tex_offset.x * float(i), 0.0)) * weight[i % 5];
         }
     }
     else
     {
         for(int i = 1; i < 5; ++i)
         {
-            result += texture(image, TexCoord + vec2(0.0, tex_offset.y * float(i))).rgb * weight[i % 5];
-            result += texture(image, TexCoord - vec2(0.0, tex_offset.y * float(i))).rgb * weight[i % 5];
+            result += sampleImage(TexCoord + vec2(0.0, tex_offset.y * float(i))) * weight[i % 5];
+            result += sampleImage(TexCoord - vec2(0.0, tex_offset.y * float(i))) * weight[i % 5];
         }
     }
-    FragColor = vec4(result, 1.0);
+    FragColor = vec4(fromLinear(result, encode_transfer, transfer_power), 1.0);
 }
 "#;
@@ -0,0 +1,154 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::error::AppResult;
+use crate::shaders::make_shader;
+use core::cabinet_model::{CabinetModel, CabinetPlacement, CABINET_VERTEX_STRIDE};
+use core::general_types::f32_to_u8;
+
+use glow::GlowSafeAdapter;
+use glow::HasContext;
+use std::mem::size_of;
+use std::rc::Rc;
+
+pub struct CabinetUniform<'a> {
+    pub view: &'a [f32; 16],
+    pub projection: &'a [f32; 16],
+    pub light_pos: &'a [f32; 3],
+    pub placement: &'a CabinetPlacement,
+    /// Average color of the picture, which lights the cabinet around the screen.
+    pub screen_glow: &'a [f32; 3],
+}
+
+pub struct CabinetRender<GL: HasContext> {
+    vao: Option<GL::VertexArray>,
+    vbo: GL::Buffer,
+    shader: GL::Program,
+    vertex_count: Option<i32>,
+    gl: Rc<GlowSafeAdapter<GL>>,
+}
+
+impl<GL: HasContext> CabinetRender<GL> {
+    pub fn new(gl: Rc<GlowSafeAdapter<GL>>) -> AppResult<CabinetRender<GL>> {
+        let shader = make_shader(&*gl, CABINET_VERTEX_SHADER, CABINET_FRAGMENT_SHADER)?;
+
+        let vao = Some(gl.create_vertex_array()?);
+        gl.bind_vertex_array(vao);
+
+        let vbo = gl.create_buffer()?;
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
+
+        let stride = (CABINET_VERTEX_STRIDE * size_of::<f32>()) as i32;
+        for (index, name) in ["aPos", "aNormal", "aColor"].iter().enumerate() {
+            let position = gl.get_attrib_location(shader, name);
+            gl.vertex_attrib_pointer_f32(position, 3, glow::FLOAT, false, stride, (index * 3 * size_of::<f32>()) as i32);
+            gl.enable_vertex_attrib_array(position);
+        }
+
+        Ok(CabinetRender {
+            vao,
+            vbo,
+            shader,
+            vertex_count: None,
+            gl,
+        })
+    }
+
+    pub fn render(&mut self, model: &CabinetModel, uniforms: CabinetUniform) {
+        let gl = &self.gl;
+        gl.bind_vertex_array(self.vao);
+        // The model does not change while the simulation runs, so it is uploaded once.
+        let vertex_count = match self.vertex_count {
+            Some(vertex_count) => vertex_count,
+            None => {
+                gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
+                gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, f32_to_u8(&model.vertices), glow::STATIC_DRAW);
+                let vertex_count = (model.vertices.len() / CABINET_VERTEX_STRIDE) as i32;
+                self.vertex_count = Some(vertex_count);
+                vertex_count
+            }
+        };
+
+        let shader = self.shader;
+        let screen_half_size = [
+            model.screen_size[0] * uniforms.placement.scale * 0.5,
+            model.screen_size[1] * uniforms.placement.scale * 0.5,
+        ];
+        gl.use_program(Some(shader));
+        gl.uniform_matrix_4_f32_slice(gl.get_uniform_location(shader, "view"), false, uniforms.view);
+        gl.uniform_matrix_4_f32_slice(gl.get_uniform_location(shader, "projection"), false, uniforms.projection);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "modelScale"), uniforms.placement.scale);
+        gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "modelTranslation"), &uniforms.placement.translation);
+        gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "lightPos"), uniforms.light_pos);
+        gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "screenGlow"), uniforms.screen_glow);
+        gl.uniform_2_f32_slice(gl.get_uniform_location(shader, "screenHalfSize"), &screen_half_size);
+        gl.draw_arrays(glow::TRIANGLES, 0, vertex_count);
+    }
+}
+
+pub const CABINET_VERTEX_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec3 aPos;
+in vec3 aNormal;
+in vec3 aColor;
+
+out vec3 FragPos;
+out vec3 Normal;
+out vec3 Color;
+
+uniform mat4 view;
+uniform mat4 projection;
+uniform float modelScale;
+uniform vec3 modelTranslation;
+
+void main()
+{
+    FragPos = aPos * modelScale + modelTranslation;
+    Normal = aNormal;
+    Color = aColor;
+    gl_Position = projection * view * vec4(FragPos, 1.0);
+}
+"#;
+
+pub const CABINET_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+out vec4 FragColor;
+
+in vec3 FragPos;
+in vec3 Normal;
+in vec3 Color;
+
+uniform vec3 lightPos;
+uniform vec3 screenGlow;
+uniform vec2 screenHalfSize;
+
+void main()
+{
+    vec3 normal = normalize(Normal);
+    float diffuse = max(dot(normal, normalize(lightPos - FragPos)), 0.0);
+
+    // The screen lights the cabinet from its closest point, fading with the
+    // distance measured in screen heights.
+    vec3 toScreen = vec3(clamp(FragPos.xy, -screenHalfSize, screenHalfSize), 0.0) - FragPos;
+    float distance = length(toScreen);
+    float facing = distance > 0.0 ? max(dot(normal, toScreen / distance), 0.0) : 1.0;
+    float falloff = 1.0 / (1.0 + pow(distance / (2.0 * screenHalfSize.y), 2.0));
+
+    vec3 light = vec3(0.2 + 0.6 * diffuse) + screenGlow * facing * falloff * 1.5;
+    FragColor = vec4(Color * light, 1.0);
+}
+"#;
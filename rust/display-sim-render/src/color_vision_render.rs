@@ -0,0 +1,97 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::error::AppResult;
+use crate::render_types::{TextureBuffer, TextureBufferStack};
+use crate::shaders::{make_quad_vao, make_shader, with_transfer_functions, TEXTURE_VERTEX_SHADER};
+use core::color_vision::ColorVisionFilter;
+use core::transfer_function::TransferCurve;
+
+use glow::GlowSafeAdapter;
+use glow::HasContext;
+use std::rc::Rc;
+
+const IDENTITY: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+pub struct ColorVisionRender<GL: HasContext> {
+    vao: Option<GL::VertexArray>,
+    shader: GL::Program,
+    gl: Rc<GlowSafeAdapter<GL>>,
+}
+
+impl<GL: HasContext> ColorVisionRender<GL> {
+    pub fn new(gl: Rc<GlowSafeAdapter<GL>>) -> AppResult<ColorVisionRender<GL>> {
+        let shader = make_shader(&*gl, TEXTURE_VERTEX_SHADER, &with_transfer_functions(COLOR_VISION_FRAGMENT_SHADER))?;
+        let vao = make_quad_vao(&*gl, &shader)?;
+        Ok(ColorVisionRender { vao, shader, gl })
+    }
+
+    /// Filters the encoded picture in `target` in place. The matrices work
+    /// on light, so `encoding` is undone first and applied again afterwards.
+    pub fn render(&self, stack: &mut TextureBufferStack<GL>, target: &TextureBuffer<GL>, filter: &ColorVisionFilter, encoding: TransferCurve) -> AppResult<()> {
+        stack.push()?;
+        let scratch = stack.get_current()?.clone();
+
+        self.gl.use_program(Some(self.shader));
+        self.gl.bind_vertex_array(self.vao);
+        self.gl
+            .uniform_1_f32(self.gl.get_uniform_location(self.shader, "transfer_power"), encoding.shader_power());
+
+        self.gl.bind_framebuffer(glow::FRAMEBUFFER, scratch.framebuffer());
+        self.gl.viewport(0, 0, scratch.width, scratch.height);
+        self.draw(target.texture(), &filter.matrix(), encoding.shader_kind());
+
+        // Copying back unchanged.
+        self.gl.bind_framebuffer(glow::FRAMEBUFFER, target.framebuffer());
+        self.gl.viewport(0, 0, target.width, target.height);
+        self.draw(scratch.texture(), &IDENTITY, 0.0);
+
+        self.gl.bind_vertex_array(None);
+        self.gl.bind_texture(glow::TEXTURE_2D, None);
+        stack.pop()?;
+        Ok(())
+    }
+
+    fn draw(&self, texture: Option<GL::Texture>, matrix: &[[f32; 3]; 3], transfer: f32) {
+        self.gl.bind_texture(glow::TEXTURE_2D, texture);
+        self.gl.uniform_3_f32_slice(self.gl.get_uniform_location(self.shader, "red"), &matrix[0]);
+        self.gl.uniform_3_f32_slice(self.gl.get_uniform_location(self.shader, "green"), &matrix[1]);
+        self.gl.uniform_3_f32_slice(self.gl.get_uniform_location(self.shader, "blue"), &matrix[2]);
+        self.gl.uniform_1_f32(self.gl.get_uniform_location(self.shader, "transfer"), transfer);
+        self.gl.draw_elements(glow::TRIANGLES, 6, glow::UNSIGNED_INT, 0);
+    }
+}
+
+pub const COLOR_VISION_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+out vec4 FragColor;
+in vec2 TexCoord;
+
+uniform sampler2D image;
+uniform vec3 red;
+uniform vec3 green;
+uniform vec3 blue;
+uniform float transfer;
+uniform float transfer_power;
+
+void main()
+{
+    vec4 color = texture(image, TexCoord);
+    vec3 light = toLinear(color.rgb, transfer, transfer_power);
+    light = clamp(light.r * red + light.g * green + light.b * blue, 0.0, 1.0);
+    FragColor = vec4(fromLinear(light, transfer, transfer_power), color.a);
+}
+"#;
@@ -15,15 +15,20 @@
 
 #![allow(clippy::identity_op)]
 
+pub mod artwork_render;
 pub mod background_render;
 pub mod blur_render;
+pub mod cabinet_render;
+pub mod color_vision_render;
 pub mod internal_resolution_render;
+pub mod pixel_geometry;
 pub mod pixels_render;
 pub mod render_types;
 pub mod rgb_render;
 mod shaders;
 pub mod simulation_draw;
 pub mod simulation_render_state;
+pub mod vector_render;
 
 pub mod error {
     pub use app_util::*;
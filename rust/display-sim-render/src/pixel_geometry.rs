@@ -0,0 +1,284 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use core::ui_controller::pixel_geometry_kind::PixelGeometryKindOptions;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+/// Floats per vertex: position, normal, and whether it emits the pixel color.
+pub const PIXEL_VERTEX_STRIDE: usize = 7;
+
+const SPHERE_SLICES: usize = 12;
+const SPHERE_STACKS: usize = 8;
+/// The circumradius keeps each hexagon inside its cell.
+const HEXAGON_RADIUS: f32 = 0.5;
+const LED_LENS_RADIUS: f32 = 0.35;
+const LED_LENS_HEIGHT: f32 = 0.25;
+/// Depth of the front of the housing, where the lens sits.
+const LED_HOUSING_FRONT: f32 = 0.2;
+
+/// In the order of the `PixelGeometryKindOptions` values.
+const KINDS: [PixelGeometryKindOptions; 5] = [
+    PixelGeometryKindOptions::Squares,
+    PixelGeometryKindOptions::Cubes,
+    PixelGeometryKindOptions::Spheres,
+    PixelGeometryKindOptions::Hexagons,
+    PixelGeometryKindOptions::LedDiodes,
+];
+
+/// Meshes that `PixelsRender` instances once per pixel, each one filling a unit
+/// cell centered at the origin with its front facing +Z. All kinds share a
+/// single vertex buffer, one after the other.
+pub struct PixelMeshes {
+    pub vertices: Vec<f32>,
+    /// First vertex and vertex count of each kind.
+    ranges: Vec<(i32, i32)>,
+}
+
+impl Default for PixelMeshes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PixelMeshes {
+    pub fn new() -> PixelMeshes {
+        let mut mesh = MeshBuilder::default();
+        let mut ranges = Vec::with_capacity(KINDS.len());
+        for kind in KINDS {
+            let first = mesh.vertex_count();
+            match kind {
+                PixelGeometryKindOptions::Squares => mesh.square(),
+                PixelGeometryKindOptions::Cubes => mesh.cube(),
+                PixelGeometryKindOptions::Spheres => mesh.sphere(),
+                PixelGeometryKindOptions::Hexagons => mesh.hexagonal_prism(),
+                PixelGeometryKindOptions::LedDiodes => mesh.led_diode(),
+            }
+            ranges.push((first, mesh.vertex_count() - first));
+        }
+        PixelMeshes {
+            vertices: mesh.vertices,
+            ranges,
+        }
+    }
+
+    pub fn range(&self, kind: PixelGeometryKindOptions) -> (i32, i32) {
+        self.ranges[kind as usize]
+    }
+}
+
+#[derive(Default)]
+struct MeshBuilder {
+    vertices: Vec<f32>,
+}
+
+impl MeshBuilder {
+    fn vertex_count(&self) -> i32 {
+        (self.vertices.len() / PIXEL_VERTEX_STRIDE) as i32
+    }
+
+    fn vertex(&mut self, position: [f32; 3], normal: [f32; 3], emission: f32) {
+        self.vertices.extend_from_slice(&position);
+        self.vertices.extend_from_slice(&normal);
+        self.vertices.push(emission);
+    }
+
+    /// Corners counter-clockwise when seen from the side `normal` points to.
+    fn face(&mut self, corners: &[[f32; 3]], normal: [f32; 3], emission: f32) {
+        for i in 1..corners.len() - 1 {
+            for corner in [corners[0], corners[i], corners[i + 1]] {
+                self.vertex(corner, normal, emission);
+            }
+        }
+    }
+
+    fn square(&mut self) {
+        self.face(&[[-0.5, -0.5, 0.5], [0.5, -0.5, 0.5], [0.5, 0.5, 0.5], [-0.5, 0.5, 0.5]], [0.0, 0.0, 1.0], 1.0);
+    }
+
+    fn cube(&mut self) {
+        self.box_faces([-0.5, -0.5, -0.5], [0.5, 0.5, 0.5], 1.0);
+    }
+
+    fn box_faces(&mut self, min: [f32; 3], max: [f32; 3], emission: f32) {
+        let [x0, y0, z0] = min;
+        let [x1, y1, z1] = max;
+        self.face(&[[x0, y0, z1], [x1, y0, z1], [x1, y1, z1], [x0, y1, z1]], [0.0, 0.0, 1.0], emission);
+        self.face(&[[x1, y0, z0], [x0, y0, z0], [x0, y1, z0], [x1, y1, z0]], [0.0, 0.0, -1.0], emission);
+        self.face(&[[x0, y0, z0], [x0, y0, z1], [x0, y1, z1], [x0, y1, z0]], [-1.0, 0.0, 0.0], emission);
+        self.face(&[[x1, y0, z1], [x1, y0, z0], [x1, y1, z0], [x1, y1, z1]], [1.0, 0.0, 0.0], emission);
+        self.face(&[[x0, y0, z0], [x1, y0, z0], [x1, y0, z1], [x0, y0, z1]], [0.0, -1.0, 0.0], emission);
+        self.face(&[[x0, y1, z1], [x1, y1, z1], [x1, y1, z0], [x0, y1, z0]], [0.0, 1.0, 0.0], emission);
+    }
+
+    /// A bulb with smooth normals, so the light rolls over it.
+    fn sphere(&mut self) {
+        self.dome(SPHERE_STACKS, PI, 0.5, 0.5, 0.0, 1.0);
+    }
+
+    fn hexagonal_prism(&mut self) {
+        // Flat sides left and right, so the cells of a row face each other.
+        let corner = |k: usize, z: f32| {
+            let angle = PI / 6.0 + k as f32 * PI / 3.0;
+            [HEXAGON_RADIUS * angle.cos(), HEXAGON_RADIUS * angle.sin(), z]
+        };
+        let front: Vec<[f32; 3]> = (0..6).map(|k| corner(k, 0.5)).collect();
+        let back: Vec<[f32; 3]> = (0..6).rev().map(|k| corner(k, -0.5)).collect();
+        self.face(&front, [0.0, 0.0, 1.0], 1.0);
+        self.face(&back, [0.0, 0.0, -1.0], 1.0);
+        for k in 0..6 {
+            let middle = PI / 3.0 + k as f32 * PI / 3.0;
+            self.face(
+                &[corner(k, -0.5), corner(k + 1, -0.5), corner(k + 1, 0.5), corner(k, 0.5)],
+                [middle.cos(), middle.sin(), 0.0],
+                1.0,
+            );
+        }
+    }
+
+    /// A round lens on a dark housing, whose sides show at an angle.
+    fn led_diode(&mut self) {
+        self.box_faces([-0.5, -0.5, -0.5], [0.5, 0.5, LED_HOUSING_FRONT], 0.0);
+        self.dome(SPHERE_STACKS / 2, FRAC_PI_2, LED_LENS_RADIUS, LED_LENS_HEIGHT, LED_HOUSING_FRONT, 1.0);
+    }
+
+    /// Part of an ellipsoid around +Z, from its apex down to `end` radians
+    /// away from it. The normals are those of the ellipsoid.
+    fn dome(&mut self, stacks: usize, end: f32, radius: f32, height: f32, base: f32, emission: f32) {
+        let point = |stack: usize, slice: usize| {
+            let polar = end * stack as f32 / stacks as f32;
+            let azimuth = TAU * slice as f32 / SPHERE_SLICES as f32;
+            let (x, y, z) = (polar.sin() * azimuth.cos(), polar.sin() * azimuth.sin(), polar.cos());
+            let normal = [x / radius, y / radius, z / height];
+            let length = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+            (
+                [x * radius, y * radius, z * height + base],
+                [normal[0] / length, normal[1] / length, normal[2] / length],
+            )
+        };
+        for stack in 0..stacks {
+            for slice in 0..SPHERE_SLICES {
+                let a = point(stack, slice);
+                let b = point(stack + 1, slice);
+                let c = point(stack + 1, slice + 1);
+                let d = point(stack, slice + 1);
+                // The triangles that would touch the poles with two corners are empty.
+                let mut triangles = Vec::with_capacity(2);
+                if stack + 1 < stacks || end < PI {
+                    triangles.push([a, b, c]);
+                }
+                if stack > 0 {
+                    triangles.push([a, c, d]);
+                }
+                for triangle in triangles {
+                    for (position, normal) in triangle {
+                        self.vertex(position, normal, emission);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangles(kind: PixelGeometryKindOptions) -> Vec<[[f32; 7]; 3]> {
+        let meshes = PixelMeshes::new();
+        let (first, count) = meshes.range(kind);
+        let vertices = &meshes.vertices[first as usize * PIXEL_VERTEX_STRIDE..(first + count) as usize * PIXEL_VERTEX_STRIDE];
+        vertices
+            .chunks_exact(PIXEL_VERTEX_STRIDE * 3)
+            .map(|triangle| {
+                let vertex = |i: usize| {
+                    let mut vertex = [0.0; 7];
+                    vertex.copy_from_slice(&triangle[i * PIXEL_VERTEX_STRIDE..(i + 1) * PIXEL_VERTEX_STRIDE]);
+                    vertex
+                };
+                [vertex(0), vertex(1), vertex(2)]
+            })
+            .collect()
+    }
+
+    fn subtract(a: &[f32], b: &[f32]) -> [f32; 3] {
+        [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+    }
+
+    fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+        [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+    }
+
+    fn dot(a: [f32; 3], b: &[f32]) -> f32 {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+    }
+
+    #[test]
+    fn every_triangle_winds_counter_clockwise_around_its_normals() {
+        for kind in KINDS {
+            let triangles = triangles(kind);
+            assert!(!triangles.is_empty(), "{} has no triangles", kind);
+            for (index, [p0, p1, p2]) in triangles.iter().enumerate() {
+                let winding = cross(subtract(p1, p0), subtract(p2, p0));
+                for vertex in [p0, p1, p2] {
+                    assert!(dot(winding, &vertex[3..6]) > 0.0, "{} triangle {} winds against its normals", kind, index);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cube_faces_have_flat_normals() {
+        let triangles = triangles(PixelGeometryKindOptions::Cubes);
+        assert_eq!(triangles.len(), 12);
+        for [p0, p1, p2] in triangles {
+            assert_eq!(p0[3..6], p1[3..6]);
+            assert_eq!(p0[3..6], p2[3..6]);
+        }
+    }
+
+    #[test]
+    fn square_geometry_is_a_flat_unit_square() {
+        let positions: Vec<[f32; 7]> = triangles(PixelGeometryKindOptions::Squares).into_iter().flatten().collect();
+        assert_eq!(positions.len(), 6);
+        assert!(positions.iter().all(|point| point[2] == 0.5));
+        let min_x = positions.iter().map(|point| point[0]).fold(f32::INFINITY, f32::min);
+        let max_x = positions.iter().map(|point| point[0]).fold(f32::NEG_INFINITY, f32::max);
+        let min_y = positions.iter().map(|point| point[1]).fold(f32::INFINITY, f32::min);
+        let max_y = positions.iter().map(|point| point[1]).fold(f32::NEG_INFINITY, f32::max);
+
+        assert_eq!(max_x - min_x, 1.0);
+        assert_eq!(max_y - min_y, 1.0);
+    }
+
+    #[test]
+    fn every_kind_stays_inside_its_cell() {
+        for kind in KINDS {
+            for vertex in triangles(kind).into_iter().flatten() {
+                assert!(vertex[..3].iter().all(|coordinate| coordinate.abs() <= 0.5 + 1e-6), "{} leaves its cell", kind);
+            }
+        }
+    }
+
+    #[test]
+    fn only_the_led_housing_is_dark() {
+        for kind in KINDS {
+            let dark = triangles(kind).into_iter().flatten().filter(|vertex| vertex[6] == 0.0).count();
+            match kind {
+                PixelGeometryKindOptions::LedDiodes => assert_eq!(dark, 36),
+                _ => assert_eq!(dark, 0, "{} has dark vertices", kind),
+            }
+        }
+    }
+}
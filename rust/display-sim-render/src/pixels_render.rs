@@ -14,10 +14,22 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
 
 use crate::error::AppResult;
-use crate::shaders::make_shader;
+use crate::pixel_geometry::{PixelMeshes, PIXEL_VERTEX_STRIDE};
+use crate::shaders::{make_shader, with_transfer_functions};
 use crate::simulation_render_state::VideoInputMaterials;
+use core::color_lut::{ColorLut, LutGrading};
 use core::general_types::f32_to_u8;
-use core::simulation_core_state::VideoInputResources;
+use core::persistence::Persistence;
+use core::reflective_panel::{AmbientLighting, EPaperPanel, ReflectiveKind};
+use core::rf_interference::RfInterference;
+use core::scan_output::ScanBand;
+use core::screen_geometry::{ScreenGeometry, ScreenMask};
+use core::simulation_core_state::{VideoInputResources, ViewModel};
+use core::transfer_function::LinearLight;
+use core::tube_aging::{BurnInMap, TubeAging};
+use core::tube_lifecycle::LifecycleEffect;
+use core::ui_controller::lut_interpolation::LutInterpolationOptions;
+use core::ui_controller::lut_stage::LutStageOptions;
 use core::ui_controller::pixel_geometry_kind::PixelGeometryKindOptions;
 use core::ui_controller::pixel_shadow_shape_kind::{get_shadows, TEXTURE_SIZE};
 
@@ -31,14 +43,62 @@ pub struct PixelsRender<GL: HasContext> {
     vao: Option<GL::VertexArray>,
     colors_vbo: GL::Buffer,
     offsets_vbo: GL::Buffer,
+    wear_vbo: GL::Buffer,
+    meshes: PixelMeshes,
     width: u32,
     height: u32,
     offset_inverse_max_length: f32,
+    /// Mean RGB of the last loaded frame, only tracked when a cabinet is lit by it.
+    pub average_color: [f32; 3],
+    phosphor_persistence: Persistence,
+    lcd_persistence: Persistence,
+    epaper_panel: EPaperPanel,
+    burn_in: Option<BurnInMap>,
     shadows: Vec<Option<GL::Texture>>,
+    lut: LutSlot<LutTexture<GL>>,
     video_buffers: Vec<Box<[u8]>>,
+    /// The last source as it reaches what keeps settling after it stops
+    /// changing: the phosphor afterglow and the panel.
+    panel_input: Vec<u8>,
     gl: Rc<GlowSafeAdapter<GL>>,
 }
 
+struct LutTexture<GL: HasContext> {
+    texture: GL::Texture,
+    size: f32,
+    tiles: f32,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+}
+
+/// Holds what was made from the LUT of a given generation, until another one comes.
+struct LutSlot<T> {
+    generation: Option<u32>,
+    current: Option<T>,
+}
+
+impl<T> LutSlot<T> {
+    fn update(
+        &mut self,
+        generation: u32,
+        color_lut: Option<&ColorLut>,
+        create: impl FnOnce(&ColorLut) -> AppResult<T>,
+        delete: impl FnOnce(T),
+    ) -> AppResult<()> {
+        if self.generation == Some(generation) {
+            return Ok(());
+        }
+        if let Some(old) = self.current.take() {
+            delete(old);
+        }
+        if let Some(color_lut) = color_lut {
+            self.current = Some(create(color_lut)?);
+        }
+        self.generation = Some(generation);
+        Ok(())
+    }
+}
+
 pub struct PixelsUniform<'a> {
     pub shadow_kind: usize,
     pub geometry_kind: PixelGeometryKindOptions,
@@ -46,13 +106,16 @@ pub struct PixelsUniform<'a> {
     pub projection: &'a [f32; 16],
     pub light_pos: &'a [f32; 3],
     pub light_color: &'a [f32; 3],
+    pub white_subpixel: bool,
     pub extra_light: &'a [f32; 3],
     pub ambient_strength: f32,
     pub contrast_factor: f32,
-    pub screen_curvature: f32,
+    pub screen_geometry: &'a ScreenGeometry,
+    pub screen_mask: &'a ScreenMask,
     pub pixel_spread: &'a [f32; 2],
     pub pixel_scale: &'a [f32; 3],
     pub pixel_offset: &'a [f32; 3],
+    pub convergence_radial: f32,
 
     pub rgb_red: &'a [f32; 3],
     pub rgb_green: &'a [f32; 3],
@@ -63,34 +126,45 @@ pub struct PixelsUniform<'a> {
 
     pub pixel_pulse: f32,
     pub height_modifier_factor: f32,
+
+    pub interlace_field: f32,
+    pub interlace_flicker: f32,
+    pub interlace_combing: f32,
+    pub scan_band: &'a Option<ScanBand>,
+
+    pub rf_interference: &'a RfInterference,
+    pub lifecycle: &'a LifecycleEffect,
+    pub tube_aging: &'a TubeAging,
+    pub ambient_lighting: &'a Option<AmbientLighting>,
+    pub lut_grading: &'a Option<LutGrading>,
+    pub linear_light: &'a Option<LinearLight>,
 }
 
 impl<GL: HasContext> PixelsRender<GL> {
     pub fn new(gl: Rc<GlowSafeAdapter<GL>>, video_materials: VideoInputMaterials) -> AppResult<PixelsRender<GL>> {
-        let shader = make_shader(&*gl, PIXEL_VERTEX_SHADER, PIXEL_FRAGMENT_SHADER)?;
+        let shader = make_shader(&*gl, PIXEL_VERTEX_SHADER, &with_transfer_functions(PIXEL_FRAGMENT_SHADER))?;
 
         let vao = Some(gl.create_vertex_array()?);
         gl.bind_vertex_array(vao);
 
+        let meshes = PixelMeshes::new();
         let pixels_vbo = gl.create_buffer()?;
         gl.bind_buffer(glow::ARRAY_BUFFER, Some(pixels_vbo));
-        gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, f32_to_u8(&CUBE_GEOMETRY), glow::STATIC_DRAW);
+        gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, f32_to_u8(&meshes.vertices), glow::STATIC_DRAW);
 
+        let stride = (PIXEL_VERTEX_STRIDE * size_of::<f32>()) as i32;
         let a_pos_position = gl.get_attrib_location(shader, "aPos");
-        gl.vertex_attrib_pointer_f32(a_pos_position, 3, glow::FLOAT, false, 6 * size_of::<f32>() as i32, 0);
+        gl.vertex_attrib_pointer_f32(a_pos_position, 3, glow::FLOAT, false, stride, 0);
         gl.enable_vertex_attrib_array(a_pos_position);
 
         let a_normal_position = gl.get_attrib_location(shader, "aNormal");
-        gl.vertex_attrib_pointer_f32(
-            a_normal_position,
-            3,
-            glow::FLOAT,
-            false,
-            6 * size_of::<f32>() as i32,
-            3 * size_of::<f32>() as i32,
-        );
+        gl.vertex_attrib_pointer_f32(a_normal_position, 3, glow::FLOAT, false, stride, 3 * size_of::<f32>() as i32);
         gl.enable_vertex_attrib_array(a_normal_position);
 
+        let a_emission_position = gl.get_attrib_location(shader, "aEmission");
+        gl.vertex_attrib_pointer_f32(a_emission_position, 1, glow::FLOAT, false, stride, 6 * size_of::<f32>() as i32);
+        gl.enable_vertex_attrib_array(a_emission_position);
+
         let colors_vbo = gl.create_buffer()?;
         gl.bind_buffer(glow::ARRAY_BUFFER, Some(colors_vbo));
 
@@ -107,6 +181,14 @@ impl<GL: HasContext> PixelsRender<GL> {
         gl.vertex_attrib_pointer_f32(a_offset_position, 2, glow::FLOAT, false, 2 * size_of::<f32>() as i32, 0);
         gl.vertex_attrib_divisor(a_offset_position, 1);
 
+        let wear_vbo = gl.create_buffer()?;
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(wear_vbo));
+
+        let a_wear_position = gl.get_attrib_location(shader, "aWear");
+        gl.enable_vertex_attrib_array(a_wear_position);
+        gl.vertex_attrib_pointer_f32(a_wear_position, 1, glow::FLOAT, false, size_of::<f32>() as i32, 0);
+        gl.vertex_attrib_divisor(a_wear_position, 1);
+
         let shadows = get_shadows()
             .iter()
             .map(|closure| Self::create_shadow_texture(&*gl, &**closure))
@@ -114,14 +196,26 @@ impl<GL: HasContext> PixelsRender<GL> {
 
         Ok(PixelsRender {
             video_buffers: video_materials.buffers,
+            panel_input: Vec::new(),
             vao,
             shader,
             offsets_vbo,
+            wear_vbo,
             colors_vbo,
+            meshes,
             width: 0,
             height: 0,
             offset_inverse_max_length: 0.0,
+            average_color: [0.0, 0.0, 0.0],
+            phosphor_persistence: Persistence::default(),
+            lcd_persistence: Persistence::default(),
+            epaper_panel: EPaperPanel::default(),
+            burn_in: None,
             shadows,
+            lut: LutSlot {
+                generation: None,
+                current: None,
+            },
             gl,
         })
     }
@@ -188,8 +282,46 @@ impl<GL: HasContext> PixelsRender<GL> {
         Ok(pixel_shadow_texture)
     }
 
-    pub fn load_image(&mut self, video_res: &VideoInputResources) {
-        if video_res.image_size.width != self.width || video_res.image_size.height != self.height {
+    fn create_lut_texture(gl: &GlowSafeAdapter<GL>, color_lut: &ColorLut) -> AppResult<LutTexture<GL>> {
+        let side = (color_lut.atlas_tiles() * color_lut.size) as i32;
+        let texture = gl.create_texture()?;
+        gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+        gl.tex_image_2d(
+            glow::TEXTURE_2D,
+            0,
+            glow::RGBA32F as i32,
+            side,
+            side,
+            0,
+            glow::RGBA,
+            glow::FLOAT,
+            Some(f32_to_u8(&color_lut.atlas())),
+        );
+        // Float textures are not filterable everywhere, the shader interpolates by itself.
+        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::NEAREST as i32);
+        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::NEAREST as i32);
+        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE as i32);
+        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE as i32);
+        gl.bind_texture(glow::TEXTURE_2D, None);
+        Ok(LutTexture {
+            texture,
+            size: color_lut.size as f32,
+            tiles: color_lut.atlas_tiles() as f32,
+            domain_min: color_lut.domain_min,
+            domain_max: color_lut.domain_max,
+        })
+    }
+
+    pub fn load_image(&mut self, video_res: &VideoInputResources, output: &ViewModel) -> AppResult<()> {
+        let gl = &self.gl;
+        self.lut.update(
+            video_res.color_lut_generation,
+            video_res.color_lut.as_ref(),
+            |color_lut| Self::create_lut_texture(gl, color_lut),
+            |old| gl.delete_texture(old.texture),
+        )?;
+        let resized = video_res.image_size.width != self.width || video_res.image_size.height != self.height;
+        if resized {
             self.width = video_res.image_size.width;
             self.height = video_res.image_size.height;
             self.offset_inverse_max_length = 1.0 / ((self.width as f32 * 0.5).powi(2) + (self.height as f32 * 0.5).powi(2)).sqrt();
@@ -198,10 +330,136 @@ impl<GL: HasContext> PixelsRender<GL> {
             self.gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, f32_to_u8(&offsets), glow::STATIC_DRAW);
         }
         self.gl.bind_vertex_array(self.vao);
+
+        // A loaded map is where the tube starts wearing from.
+        let mut wear_changed = resized;
+        if self.burn_in.is_none() {
+            self.burn_in = video_res.burn_in.clone();
+            wear_changed |= self.burn_in.is_some();
+        }
+        if self.burn_in.is_none() && output.burn_in_hours > 0.0 {
+            self.burn_in = Some(BurnInMap::new(self.width, self.height));
+        }
+        if let Some(burn_in) = &mut self.burn_in {
+            if burn_in.width != self.width || burn_in.height != self.height {
+                *burn_in = burn_in.resized(self.width, self.height);
+            }
+        }
+
         self.gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.colors_vbo));
+        let frame = &self.video_buffers[video_res.current_frame];
+        let processed;
+        let frame = if output.source_quantization.is_none()
+            && output.dither_blend.is_none()
+            && output.ghost.is_passthrough()
+            && output.signal_path.is_passthrough()
+            && output.tv_knobs.is_passthrough()
+            && output.tape_playback.is_none()
+        {
+            &frame[..]
+        } else {
+            // The console can only output the colors of its own hardware.
+            let mut source = None;
+            if let Some(source_quantization) = &output.source_quantization {
+                source = Some(source_quantization.process(frame, self.width, self.height));
+            }
+            // What the game dithered is what the tube blurs first.
+            if let Some(dither_blend) = &output.dither_blend {
+                source = Some(dither_blend.process(source.as_deref().unwrap_or(frame), self.width, self.height));
+            }
+            let frame = source.as_deref().unwrap_or(frame);
+            // Reception happens before decoding, and the tape records whatever
+            // the receiver decoded.
+            let frame = output.ghost.process(frame, self.width, self.height);
+            let mut frame = output.signal_path.process(&frame, self.width, self.height);
+            if let Some(tape_playback) = &output.tape_playback {
+                frame = tape_playback.process(&frame, self.width, self.height);
+            }
+            // The set adjusts whatever it decoded, played back or not.
+            if !output.tv_knobs.is_passthrough() {
+                frame = output.tv_knobs.process(&frame, self.width, self.height);
+            }
+            processed = frame;
+            &processed[..]
+        };
+        if wear_changed {
+            self.upload_wear();
+        }
+        if output.monochrome.is_none() && output.burn_in_hours == 0.0 && output.lcd.is_passthrough() && output.epaper.is_none() {
+            self.phosphor_persistence.reset();
+            self.panel_input.clear();
+            if video_res.cabinet.is_some() {
+                self.average_color = average_color(frame);
+            }
+            self.gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, frame, glow::STATIC_DRAW);
+        } else {
+            self.panel_input = frame.to_vec();
+            self.settle_panel(video_res, output);
+        }
+        Ok(())
+    }
+
+    /// Runs the phosphor and the panel again over the last loaded source,
+    /// which is all that changes while the afterglow fades or the cells settle.
+    pub fn settle_panel(&mut self, video_res: &VideoInputResources, output: &ViewModel) {
+        if output.monochrome.is_none() {
+            self.phosphor_persistence.reset();
+        }
+        if output.lcd.response.is_none() {
+            self.lcd_persistence.reset();
+        }
+        if output.epaper.is_none() {
+            self.epaper_panel.reset();
+        }
+        let mut glow = None;
+        if let Some(monochrome) = &output.monochrome {
+            glow = Some(
+                self.phosphor_persistence
+                    .process(&monochrome.process(&self.panel_input), &monochrome.response()),
+            );
+        }
+        let tube = glow.as_deref().unwrap_or(&self.panel_input);
+        // The tube wears by what drives it.
+        if let Some(burn_in) = &mut self.burn_in {
+            if output.burn_in_hours > 0.0 {
+                burn_in.accumulate(tube, output.burn_in_hours);
+                self.upload_wear();
+            }
+        }
+        // The panel is last: it quantizes and then slowly follows what it receives.
+        let mut panel = None;
+        if output.lcd.palette.is_some() {
+            panel = Some(output.lcd.map_palette(tube));
+        }
+        if let Some(response) = &output.lcd.response {
+            panel = Some(self.lcd_persistence.process(panel.as_deref().unwrap_or(tube), response));
+        }
+        if let Some(epaper) = &output.epaper {
+            panel = Some(self.epaper_panel.process(panel.as_deref().unwrap_or(tube), epaper));
+        }
+        let frame = panel.as_deref().unwrap_or(tube);
+        if video_res.cabinet.is_some() {
+            self.average_color = average_color(frame);
+        }
+        self.gl.bind_vertex_array(self.vao);
+        self.gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.colors_vbo));
+        self.gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, frame, glow::STATIC_DRAW);
+    }
+
+    fn upload_wear(&self) {
+        let wear = match &self.burn_in {
+            Some(burn_in) => burn_in.to_rgba8(),
+            None => vec![0; (self.width * self.height * 4) as usize],
+        };
+        self.gl.bind_vertex_array(self.vao);
+        self.gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.wear_vbo));
+        self.gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, &wear, glow::STATIC_DRAW);
+        self.gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.colors_vbo));
+    }
 
-        self.gl
-            .buffer_data_u8_slice(glow::ARRAY_BUFFER, &self.video_buffers[video_res.current_frame], glow::STATIC_DRAW);
+    /// The wear of the tube so far, so that it can be saved.
+    pub fn burn_in(&self) -> Option<&BurnInMap> {
+        self.burn_in.as_ref()
     }
 
     pub fn render(&self, uniforms: PixelsUniform) {
@@ -217,16 +475,73 @@ impl<GL: HasContext> PixelsRender<GL> {
         gl.uniform_matrix_4_f32_slice(gl.get_uniform_location(shader, "projection"), false, uniforms.projection);
         gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "lightPos"), uniforms.light_pos);
         gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "lightColor"), uniforms.light_color);
+        gl.uniform_1_f32(
+            gl.get_uniform_location(shader, "white_subpixel"),
+            if uniforms.white_subpixel { 1.0 } else { 0.0 },
+        );
         gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "extraLight"), uniforms.extra_light);
         gl.uniform_1_f32(gl.get_uniform_location(shader, "ambientStrength"), uniforms.ambient_strength);
+        match uniforms.ambient_lighting {
+            Some(ambient) => {
+                let kind = match ambient.kind {
+                    ReflectiveKind::EPaper => 1.0,
+                    ReflectiveKind::ReflectiveLcd => 2.0,
+                    ReflectiveKind::TransflectiveLcd => 3.0,
+                };
+                gl.uniform_1_f32(gl.get_uniform_location(shader, "reflective_kind"), kind);
+                gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "ambientDir"), &ambient.direction);
+                gl.uniform_1_f32(gl.get_uniform_location(shader, "ambientIntensity"), ambient.intensity);
+            }
+            None => gl.uniform_1_f32(gl.get_uniform_location(shader, "reflective_kind"), 0.0),
+        }
         gl.uniform_1_f32(gl.get_uniform_location(shader, "contrastFactor"), uniforms.contrast_factor);
         gl.uniform_1_f32(gl.get_uniform_location(shader, "offset_inverse_max_length"), self.offset_inverse_max_length);
-        gl.uniform_1_f32(gl.get_uniform_location(shader, "screen_curvature"), uniforms.screen_curvature);
+        gl.uniform_2_f32_slice(gl.get_uniform_location(shader, "screen_curvature"), &uniforms.screen_geometry.curvature);
+        gl.uniform_1_f32(
+            gl.get_uniform_location(shader, "screen_cylindrical"),
+            if uniforms.screen_geometry.cylindrical { 1.0 } else { 0.0 },
+        );
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "screen_barrel"), uniforms.screen_geometry.barrel);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "screen_keystone"), uniforms.screen_geometry.keystone);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "screen_trapezoid"), uniforms.screen_geometry.trapezoid);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "screen_tilt"), uniforms.screen_geometry.tilt);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "screen_corner_radius"), uniforms.screen_mask.corner_radius);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "screen_edge_softness"), uniforms.screen_mask.edge_softness);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "vignette_strength"), uniforms.screen_mask.vignette_strength);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "vignette_exponent"), uniforms.screen_mask.vignette_exponent);
         gl.uniform_2_f32_slice(gl.get_uniform_location(shader, "pixel_spread"), uniforms.pixel_spread);
         gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "pixel_scale"), uniforms.pixel_scale);
         gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "pixel_offset"), uniforms.pixel_offset);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "convergence_radial"), uniforms.convergence_radial);
         gl.uniform_1_f32(gl.get_uniform_location(shader, "pixel_pulse"), uniforms.pixel_pulse);
         gl.uniform_1_f32(gl.get_uniform_location(shader, "heightModifierFactor"), uniforms.height_modifier_factor);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "image_width"), self.width as f32);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "image_height"), self.height as f32);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "interlace_field"), uniforms.interlace_field);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "interlace_flicker"), uniforms.interlace_flicker);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "interlace_combing"), uniforms.interlace_combing);
+        if let Some(band) = uniforms.scan_band {
+            gl.uniform_1_f32(gl.get_uniform_location(shader, "scan_output"), 1.0);
+            gl.uniform_4_f32_slice(
+                gl.get_uniform_location(shader, "scan_band"),
+                &[band.position, band.width, band.decay, band.gain],
+            );
+        } else {
+            gl.uniform_1_f32(gl.get_uniform_location(shader, "scan_output"), 0.0);
+        }
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "rf_snow"), uniforms.rf_interference.snow);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "rf_snow_seed"), uniforms.rf_interference.snow_seed);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "hum_bar_strength"), uniforms.rf_interference.hum_bar_strength);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "hum_bar_phase"), uniforms.rf_interference.hum_bar_phase);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "roll_offset"), uniforms.rf_interference.roll_offset);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "roll_blanking"), uniforms.rf_interference.roll_blanking);
+        gl.uniform_2_f32_slice(gl.get_uniform_location(shader, "lifecycle_scale"), &uniforms.lifecycle.scale);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "lifecycle_brightness"), uniforms.lifecycle.brightness);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "degauss_wobble"), uniforms.lifecycle.wobble);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "degauss_phase"), uniforms.lifecycle.wobble_phase);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "degauss_swirl"), uniforms.lifecycle.swirl);
+        gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "tube_gains"), &uniforms.tube_aging.channel_gains());
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "tube_uniformity"), uniforms.tube_aging.uniformity);
 
         gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "red"), uniforms.rgb_red);
         gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "green"), uniforms.rgb_green);
@@ -234,27 +549,68 @@ impl<GL: HasContext> PixelsRender<GL> {
         gl.uniform_1_f32(gl.get_uniform_location(shader, "gamma"), uniforms.color_gamma);
         gl.uniform_1_f32(gl.get_uniform_location(shader, "time"), uniforms.time);
         gl.uniform_1_f32(gl.get_uniform_location(shader, "color_noise"), uniforms.color_noise);
+        let (source, display) = match uniforms.linear_light {
+            Some(linear_light) => (Some(linear_light.source), Some(linear_light.display)),
+            None => (None, None),
+        };
+        gl.uniform_1_f32(
+            gl.get_uniform_location(shader, "source_transfer"),
+            source.map_or(0.0, |curve| curve.shader_kind()),
+        );
+        gl.uniform_1_f32(
+            gl.get_uniform_location(shader, "source_power"),
+            source.map_or(1.0, |curve| curve.shader_power()),
+        );
+        gl.uniform_1_f32(
+            gl.get_uniform_location(shader, "display_transfer"),
+            display.map_or(0.0, |curve| curve.shader_kind()),
+        );
+        gl.uniform_1_f32(
+            gl.get_uniform_location(shader, "display_power"),
+            display.map_or(1.0, |curve| curve.shader_power()),
+        );
+        gl.uniform_1_i32(gl.get_uniform_location(shader, "lut"), 1);
+        match (&self.lut.current, uniforms.lut_grading) {
+            (Some(lut), Some(grading)) => {
+                gl.active_texture(glow::TEXTURE0 + 1);
+                gl.bind_texture(glow::TEXTURE_2D, Some(lut.texture));
+                gl.active_texture(glow::TEXTURE0 + 0);
+                let stage = match grading.stage {
+                    LutStageOptions::Off => 0.0,
+                    LutStageOptions::BeforeMatrix => 1.0,
+                    LutStageOptions::AfterGamma => 2.0,
+                };
+                gl.uniform_1_f32(gl.get_uniform_location(shader, "lut_stage"), stage);
+                gl.uniform_1_f32(
+                    gl.get_uniform_location(shader, "lut_tetrahedral"),
+                    if grading.interpolation == LutInterpolationOptions::Tetrahedral {
+                        1.0
+                    } else {
+                        0.0
+                    },
+                );
+                gl.uniform_1_f32(gl.get_uniform_location(shader, "lut_strength"), grading.strength);
+                gl.uniform_1_f32(gl.get_uniform_location(shader, "lut_size"), lut.size);
+                gl.uniform_1_f32(gl.get_uniform_location(shader, "lut_tiles"), lut.tiles);
+                gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "lut_domain_min"), &lut.domain_min);
+                gl.uniform_3_f32_slice(gl.get_uniform_location(shader, "lut_domain_max"), &lut.domain_max);
+            }
+            _ => gl.uniform_1_f32(gl.get_uniform_location(shader, "lut_stage"), 0.0),
+        }
 
         gl.bind_vertex_array(self.vao);
         // Adjacent cubes share coplanar faces. With both windings rendered,
         // those faces compete for the same depth value and expose individual
-        // triangles. Cull only closed cube geometry; flat squares remain
+        // triangles. Cull every closed geometry; flat squares remain
         // visible from either side.
-        let cull_back_faces = matches!(uniforms.geometry_kind, PixelGeometryKindOptions::Cubes);
+        let cull_back_faces = !matches!(uniforms.geometry_kind, PixelGeometryKindOptions::Squares);
         if cull_back_faces {
             gl.enable(glow::CULL_FACE);
         } else {
             gl.disable(glow::CULL_FACE);
         }
-        gl.draw_arrays_instanced(
-            glow::TRIANGLES,
-            0,
-            match uniforms.geometry_kind {
-                PixelGeometryKindOptions::Squares => 6,
-                PixelGeometryKindOptions::Cubes => 36,
-            },
-            (self.width * self.height) as i32,
-        );
+        let (first, count) = self.meshes.range(uniforms.geometry_kind);
+        gl.draw_arrays_instanced(glow::TRIANGLES, first, count, (self.width * self.height) as i32);
         // Full-screen composition quads use the historical opposite winding.
         // Do not leak cube culling into those later passes (or into egui).
         if cull_back_faces {
@@ -263,6 +619,17 @@ impl<GL: HasContext> PixelsRender<GL> {
     }
 }
 
+fn average_color(frame: &[u8]) -> [f32; 3] {
+    let mut sum = [0u64; 3];
+    for pixel in frame.chunks_exact(4) {
+        for channel in 0..3 {
+            sum[channel] += u64::from(pixel[channel]);
+        }
+    }
+    let count = (frame.len() / 4).max(1) as f32 * 255.0;
+    [sum[0] as f32 / count, sum[1] as f32 / count, sum[2] as f32 / count]
+}
+
 fn calculate_offsets(width: u32, height: u32) -> Vec<f32> {
     let pixels_total = width * height;
     let mut offsets: Vec<f32> = vec![0.0; pixels_total as usize * 2];
@@ -284,62 +651,19 @@ fn calculate_offsets(width: u32, height: u32) -> Vec<f32> {
     offsets
 }
 
-#[rustfmt::skip]
-const CUBE_GEOMETRY : [f32; 216] = [
-    // cube coordinates       cube normals
-    -0.5, -0.5,  0.5,      0.0,  0.0,  1.0,
-     0.5, -0.5,  0.5,      0.0,  0.0,  1.0,
-     0.5,  0.5,  0.5,      0.0,  0.0,  1.0,
-     0.5,  0.5,  0.5,      0.0,  0.0,  1.0,
-    -0.5,  0.5,  0.5,      0.0,  0.0,  1.0,
-    -0.5, -0.5,  0.5,      0.0,  0.0,  1.0,
-
-    -0.5, -0.5, -0.5,      0.0,  0.0, -1.0,
-    -0.5,  0.5, -0.5,      0.0,  0.0, -1.0,
-     0.5,  0.5, -0.5,      0.0,  0.0, -1.0,
-     0.5,  0.5, -0.5,      0.0,  0.0, -1.0,
-     0.5, -0.5, -0.5,      0.0,  0.0, -1.0,
-    -0.5, -0.5, -0.5,      0.0,  0.0, -1.0,
-
-    -0.5,  0.5,  0.5,      -1.0,  0.0,  0.0,
-    -0.5,  0.5, -0.5,      -1.0,  0.0,  0.0,
-    -0.5, -0.5, -0.5,      -1.0,  0.0,  0.0,
-    -0.5, -0.5, -0.5,      -1.0,  0.0,  0.0,
-    -0.5, -0.5,  0.5,      -1.0,  0.0,  0.0,
-    -0.5,  0.5,  0.5,      -1.0,  0.0,  0.0,
-
-     0.5, -0.5, -0.5,      1.0,  0.0,  0.0,
-     0.5,  0.5, -0.5,      1.0,  0.0,  0.0,
-     0.5,  0.5,  0.5,      1.0,  0.0,  0.0,
-     0.5,  0.5,  0.5,      1.0,  0.0,  0.0,
-     0.5, -0.5,  0.5,      1.0,  0.0,  0.0,
-     0.5, -0.5, -0.5,      1.0,  0.0,  0.0,
-
-    -0.5, -0.5, -0.5,      0.0, -1.0,  0.0,
-     0.5, -0.5, -0.5,      0.0, -1.0,  0.0,
-     0.5, -0.5,  0.5,      0.0, -1.0,  0.0,
-     0.5, -0.5,  0.5,      0.0, -1.0,  0.0,
-    -0.5, -0.5,  0.5,      0.0, -1.0,  0.0,
-    -0.5, -0.5, -0.5,      0.0, -1.0,  0.0,
-
-     0.5,  0.5,  0.5,      0.0,  1.0,  0.0,
-     0.5,  0.5, -0.5,      0.0,  1.0,  0.0,
-    -0.5,  0.5, -0.5,      0.0,  1.0,  0.0,
-    -0.5,  0.5, -0.5,      0.0,  1.0,  0.0,
-    -0.5,  0.5,  0.5,      0.0,  1.0,  0.0,
-     0.5,  0.5,  0.5,      0.0,  1.0,  0.0,
-];
-
 pub const PIXEL_VERTEX_SHADER: &str = r#"#version 300 es
 precision highp float;
 
 in vec3 aPos;
 in vec3 aNormal;
+in float aEmission;
 in float aColor;
 in vec2 aOffset;
+in float aWear;
 
 out vec3 FragPos;
 out vec3 Normal;
+out float Emission;
 out vec4 ObjectColor;
 out vec2 ImagePos;
 
@@ -347,15 +671,60 @@ uniform mat4 view;
 uniform mat4 projection;
 
 uniform float offset_inverse_max_length;
-uniform float screen_curvature;
+uniform vec2 screen_curvature;
+uniform float screen_cylindrical;
+uniform float screen_barrel;
+uniform float screen_keystone;
+uniform float screen_trapezoid;
+uniform float screen_tilt;
+uniform float screen_corner_radius;
+uniform float screen_edge_softness;
+uniform float vignette_strength;
+uniform float vignette_exponent;
 uniform vec2 pixel_spread;
 uniform vec3 pixel_scale;
 uniform float pixel_pulse;
 uniform vec3 pixel_offset;
+uniform float convergence_radial;
 uniform float heightModifierFactor;
+uniform float image_width;
+uniform float image_height;
+uniform float interlace_field;
+uniform float interlace_flicker;
+uniform float interlace_combing;
+uniform float scan_output;
+// Beam position, lit band height, decay and gain, in fractions of the frame.
+uniform vec4 scan_band;
+uniform float rf_snow;
+uniform float rf_snow_seed;
+uniform float hum_bar_strength;
+uniform float hum_bar_phase;
+uniform float roll_offset;
+uniform float roll_blanking;
+uniform vec2 lifecycle_scale;
+uniform float lifecycle_brightness;
+uniform float degauss_wobble;
+uniform float degauss_phase;
+uniform float degauss_swirl;
+uniform vec3 tube_gains;
+uniform float tube_uniformity;
 
 const float COLOR_FACTOR = 1.0/255.0;
 const uint hex_FF = uint(0xFF);
+const float TAU = 6.2831853;
+
+uint hash( uint x ) {
+    x += ( x << 10u );
+    x ^= ( x >>  6u );
+    x += ( x <<  3u );
+    x ^= ( x >> 11u );
+    x += ( x << 15u );
+    return x;
+}
+
+uint hash( uvec3 v ) { return hash( v.x ^ hash(v.y) ^ hash(v.z)             ); }
+
+float random( vec3 v ) { return uintBitsToFloat((hash(floatBitsToUint(v)) & 0x007FFFFFu) | 0x3F800000u) - 1.0; }
 
 void main()
 {
@@ -374,22 +743,136 @@ void main()
     vec3 modPos = (1.0 - heightModifierFactor) * aPos + heightModifierFactor * vec3(aPos.x, aPos.y * height_mod, aPos.z);
 
     vec3 pos = modPos / pixel_scale + vec3(aOffset * pixel_spread, 0);
+    // Where the pixel center lands on the tube face.
+    vec2 tube = aOffset * pixel_spread;
 
     if (pixel_pulse > 0.0) {
         float radius = length(aOffset);
         pos += vec3(0, 0, sin(pixel_pulse + sin(pixel_pulse * 0.1) * radius * 0.25) * 2.0);
     }
-    if (screen_curvature > 0.0) {
-        float radius = length(aOffset);
-        float normalized = radius * offset_inverse_max_length;
-        pos.z -= sin(normalized) * screen_curvature * 100.0;
-    }
     if (pixel_offset.x != 0.0 || pixel_offset.y != 0.0 || pixel_offset.z != 0.0) {
         pos += pixel_offset;
     }
+    if (convergence_radial != 0.0) {
+        // Beams drift apart toward the corners of the tube.
+        pos.xy += aOffset * offset_inverse_max_length * convergence_radial * pixel_spread;
+    }
+    if (interlace_flicker > 0.0 || interlace_combing > 0.0) {
+        // Rows are counted from the top of the image, like the source fields.
+        float row = image_height - 1.0 - floor(aOffset.y + image_height * 0.5);
+        if (mod(row, 2.0) != interlace_field) {
+            ObjectColor.rgb *= 1.0 - interlace_flicker;
+            pos.x += (interlace_field * 2.0 - 1.0) * interlace_combing * pixel_spread.x;
+        }
+    }
+    if (scan_output > 0.0) {
+        // Time since the beam went over this row, in frames.
+        float row = (image_height - 1.0 - floor(aOffset.y + image_height * 0.5)) / image_height;
+        float age = fract(scan_band.x - row);
+        float light = age < scan_band.y ? 1.0 : (scan_band.z > 0.0 ? exp((scan_band.y - age) / scan_band.z) : 0.0);
+        ObjectColor.rgb *= light * scan_band.w;
+    }
+    if (rf_snow > 0.0) {
+        // Snow is a per-pixel grain that drowns dark areas the most.
+        float luma = dot(ObjectColor.rgb, vec3(0.299, 0.587, 0.114));
+        float grain = random(vec3(aOffset, rf_snow_seed)) * 2.0 - 1.0;
+        ObjectColor.rgb = mix(ObjectColor.rgb, vec3(luma), rf_snow * 0.5) + grain * rf_snow * (1.0 - 0.75 * luma);
+    }
+    if (hum_bar_strength > 0.0) {
+        float row = (aOffset.y + image_height * 0.5) / image_height;
+        ObjectColor.rgb *= 1.0 - hum_bar_strength * 0.35 * (1.0 + sin(TAU * (row + hum_bar_phase)));
+    }
+    if (roll_offset > 0.0) {
+        // Without vertical sync the visible window slides over a period that
+        // includes the blanking interval, which shows up as a black bar.
+        float row = floor(aOffset.y + image_height * 0.5);
+        float rolled = mod(row - roll_offset, image_height + roll_blanking);
+        if (rolled >= image_height) {
+            ObjectColor.a = 0.0;
+        }
+        pos.y += (rolled - row) * pixel_spread.y;
+        tube.y += (rolled - row) * pixel_spread.y;
+    }
+    if (screen_corner_radius > 0.0 || screen_edge_softness > 0.0 || vignette_strength > 0.0) {
+        vec2 half_size = vec2(image_width, image_height) * pixel_spread * 0.5;
+        vec2 p = abs(tube);
+        float short_side = min(half_size.x, half_size.y);
+        // Signed distance to the rounded rectangle of the tube, negative inside.
+        float radius = screen_corner_radius * short_side;
+        vec2 q = p - (half_size - radius);
+        float edge = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - radius;
+        if (edge > 0.0) {
+            ObjectColor.a = 0.0;
+        }
+        if (screen_edge_softness > 0.0) {
+            ObjectColor.rgb *= smoothstep(0.0, screen_edge_softness * short_side, -edge);
+        }
+        if (vignette_strength > 0.0) {
+            vec2 uv = p / half_size;
+            float r = pow(pow(uv.x, vignette_exponent) + pow(uv.y, vignette_exponent), 1.0 / vignette_exponent);
+            ObjectColor.rgb *= max(1.0 - vignette_strength * r * r, 0.0);
+        }
+    }
+
+    if (lifecycle_brightness != 1.0) {
+        ObjectColor.rgb *= lifecycle_brightness;
+    }
+    // Same formula as TubeAging::face_gain, with v going down.
+    vec2 face_uv = vec2(aOffset.x, -aOffset.y) * 2.0 / vec2(image_width, image_height);
+    float face_gain = tube_uniformity > 0.0
+        ? 1.0 - tube_uniformity * (0.175 * dot(face_uv, face_uv) + 0.15 * (0.5 + 0.5 * sin(3.1 * face_uv.x + 1.3) * cos(2.3 * face_uv.y + 0.7)))
+        : 1.0;
+    uint wear = floatBitsToUint(aWear);
+    vec3 vecWear = vec3(float((wear >> 0) & hex_FF), float((wear >> 8) & hex_FF), float((wear >> 16) & hex_FF)) * COLOR_FACTOR;
+    ObjectColor.rgb *= tube_gains * face_gain * (1.0 - vecWear);
+    if (degauss_swirl != 0.0) {
+        // The beams land on the wrong phosphors, differently around the face.
+        vec2 face = tube / (vec2(image_width, image_height) * pixel_spread);
+        float angle = degauss_swirl * sin(degauss_phase + 6.0 * atan(face.y, face.x) + 8.0 * length(face));
+        // Rotation around the gray axis keeps the luminance roughly in place.
+        vec3 k = vec3(0.57735);
+        ObjectColor.rgb = ObjectColor.rgb * cos(angle) + cross(k, ObjectColor.rgb) * sin(angle) + k * dot(k, ObjectColor.rgb) * (1.0 - cos(angle));
+    }
+    if (degauss_wobble != 0.0) {
+        vec2 size = vec2(image_width, image_height) * pixel_spread;
+        vec2 face = tube / size;
+        pos.xy += degauss_wobble * size * vec2(sin(degauss_phase + 5.0 * face.y), cos(degauss_phase * 1.3 + 5.0 * face.x));
+    }
+    if (lifecycle_scale.x != 1.0 || lifecycle_scale.y != 1.0) {
+        // The deflection shrinks the whole raster, pixels included.
+        pos.xy *= lifecycle_scale;
+    }
+
+    // Image position normalized so that the corners sit at a distance of 1.
+    vec2 screen = pos.xy / pixel_spread * offset_inverse_max_length;
+    if (screen_barrel != 0.0) {
+        pos.xy *= 1.0 - screen_barrel * 0.25 * dot(screen, screen);
+    }
+    if (screen_keystone != 0.0) {
+        pos.x *= 1.0 + screen_keystone * 0.5 * screen.y;
+    }
+    if (screen_trapezoid != 0.0) {
+        pos.y *= 1.0 + screen_trapezoid * 0.5 * screen.x;
+    }
+    if (screen_tilt != 0.0) {
+        pos.xy = mat2(cos(screen_tilt), sin(screen_tilt), -sin(screen_tilt), cos(screen_tilt)) * pos.xy;
+    }
+    if (screen_curvature.x > 0.0 || screen_curvature.y > 0.0) {
+        float depth;
+        if (screen_cylindrical > 0.0) {
+            depth = sin(abs(screen.x)) * screen_curvature.x;
+        } else {
+            // Equal radii give the historical radial bulge; different radii
+            // weight it by direction.
+            float radius2 = dot(screen, screen);
+            depth = radius2 > 0.0 ? sin(sqrt(radius2)) * dot(screen_curvature, screen * screen) / radius2 : 0.0;
+        }
+        pos.z -= depth * 100.0;
+    }
 
     FragPos = pos;
     Normal = aNormal;
+    Emission = aEmission;
     
     gl_Position = projection * view * vec4(FragPos, 1.0);
 
@@ -403,6 +886,7 @@ precision highp float;
 out vec4 FragColor;
 
 in vec3 Normal;  
+in float Emission;
 in vec3 FragPos;
 in vec4 ObjectColor;
 in vec2 ImagePos;
@@ -414,15 +898,36 @@ uniform vec3 blue;
 uniform float gamma;
 
 uniform vec3 lightColor;
+uniform float white_subpixel;
 uniform vec3 extraLight;
 uniform vec3 lightPos;
 uniform float ambientStrength;
+// 0 emits light, 1 is e-paper, 2 a reflective LCD and 3 a transflective one.
+uniform float reflective_kind;
+uniform vec3 ambientDir;
+uniform float ambientIntensity;
 uniform float contrastFactor;
 
 uniform sampler2D image;
 uniform float time;
 uniform float color_noise;
 
+uniform sampler2D lut;
+uniform float lut_stage;
+uniform float lut_tetrahedral;
+uniform float lut_strength;
+uniform float lut_size;
+uniform float lut_tiles;
+uniform vec3 lut_domain_min;
+uniform vec3 lut_domain_max;
+
+// Without a source transfer everything happens on the encoded values, as it
+// always did. Otherwise the output stays in linear light for the composition.
+uniform float source_transfer;
+uniform float source_power;
+uniform float display_transfer;
+uniform float display_power;
+
 uint hash( uint x ) {
     x += ( x << 10u );
     x ^= ( x >>  6u );
@@ -447,15 +952,87 @@ float floatConstruct( uint m ) {
 
 float random( vec3  v ) { return floatConstruct(hash(floatBitsToUint(v))); }
 
+// The blue slices of the table are tiled in rows of lut_tiles.
+vec3 lutEntry(ivec3 p) {
+    int size = int(lut_size);
+    int tiles = int(lut_tiles);
+    return texelFetch(lut, ivec2(p.r + (p.b % tiles) * size, p.g + (p.b / tiles) * size), 0).rgb;
+}
+
+vec3 applyLut(vec3 color) {
+    vec3 t = clamp((color - lut_domain_min) / (lut_domain_max - lut_domain_min), 0.0, 1.0) * (lut_size - 1.0);
+    vec3 base = min(floor(t), vec3(lut_size - 2.0));
+    vec3 f = t - base;
+    ivec3 p = ivec3(base);
+    vec3 c000 = lutEntry(p);
+    vec3 c111 = lutEntry(p + ivec3(1, 1, 1));
+    vec3 graded;
+    if (lut_tetrahedral > 0.0) {
+        // The unit cube is split in six tetrahedra along its diagonal, only
+        // four corners are read.
+        if (f.r > f.g) {
+            if (f.g > f.b) {
+                graded = (1.0 - f.r) * c000 + (f.r - f.g) * lutEntry(p + ivec3(1, 0, 0)) + (f.g - f.b) * lutEntry(p + ivec3(1, 1, 0)) + f.b * c111;
+            } else if (f.r > f.b) {
+                graded = (1.0 - f.r) * c000 + (f.r - f.b) * lutEntry(p + ivec3(1, 0, 0)) + (f.b - f.g) * lutEntry(p + ivec3(1, 0, 1)) + f.g * c111;
+            } else {
+                graded = (1.0 - f.b) * c000 + (f.b - f.r) * lutEntry(p + ivec3(0, 0, 1)) + (f.r - f.g) * lutEntry(p + ivec3(1, 0, 1)) + f.g * c111;
+            }
+        } else {
+            if (f.b > f.g) {
+                graded = (1.0 - f.b) * c000 + (f.b - f.g) * lutEntry(p + ivec3(0, 0, 1)) + (f.g - f.r) * lutEntry(p + ivec3(0, 1, 1)) + f.r * c111;
+            } else if (f.b > f.r) {
+                graded = (1.0 - f.g) * c000 + (f.g - f.b) * lutEntry(p + ivec3(0, 1, 0)) + (f.b - f.r) * lutEntry(p + ivec3(0, 1, 1)) + f.r * c111;
+            } else {
+                graded = (1.0 - f.g) * c000 + (f.g - f.r) * lutEntry(p + ivec3(0, 1, 0)) + (f.r - f.b) * lutEntry(p + ivec3(1, 1, 0)) + f.b * c111;
+            }
+        }
+    } else {
+        vec3 c00 = mix(c000, lutEntry(p + ivec3(1, 0, 0)), f.r);
+        vec3 c10 = mix(lutEntry(p + ivec3(0, 1, 0)), lutEntry(p + ivec3(1, 1, 0)), f.r);
+        vec3 c01 = mix(lutEntry(p + ivec3(0, 0, 1)), lutEntry(p + ivec3(1, 0, 1)), f.r);
+        vec3 c11 = mix(lutEntry(p + ivec3(0, 1, 1)), c111, f.r);
+        graded = mix(mix(c00, c10, f.g), mix(c01, c11, f.g), f.b);
+    }
+    return mix(color, graded, lut_strength);
+}
+
 void main()
 {
     if (ObjectColor.a == 0.0) {
         discard;
     }
 
+    vec4 objectColor = vec4(toLinear(ObjectColor.rgb, source_transfer, source_power), ObjectColor.a);
+    if (white_subpixel == 1.0) {
+        objectColor.rgb = vec3(min(objectColor.r, min(objectColor.g, objectColor.b)));
+    }
     vec4 result;
-    if (ambientStrength == 1.0) {
-        result = ObjectColor * vec4(lightColor, 1.0) * texture(image, ImagePos);
+    if (reflective_kind > 0.0) {
+        // Nothing is emitted, the panel shows what it reflects of the room
+        // toward the camera.
+        vec3 norm = normalize(Normal);
+        vec3 viewDir = normalize(lightPos - FragPos);
+        float diffuse = max(dot(norm, ambientDir), 0.0);
+        float reflected;
+        if (reflective_kind == 1.0) {
+            // Ink never absorbs all the light and paper never returns all of it,
+            // but it scatters some of the room light even when lit from aside.
+            objectColor.rgb = mix(vec3(0.08), vec3(0.7), objectColor.rgb);
+            reflected = 0.2 + 0.8 * diffuse;
+        } else {
+            // The reflector favors the mirror direction, and going twice
+            // through the polarizers loses almost half of the light.
+            float mirror = pow(max(dot(reflect(-ambientDir, norm), viewDir), 0.0), 8.0);
+            reflected = 0.55 * (0.4 * diffuse + 0.6 * mirror);
+        }
+        vec3 light = lightColor * reflected * ambientIntensity;
+        if (reflective_kind == 3.0) {
+            light += lightColor * 0.35;
+        }
+        result = objectColor * vec4(light, 1.0) * texture(image, ImagePos);
+    } else if (ambientStrength == 1.0) {
+        result = objectColor * vec4(lightColor, 1.0) * texture(image, ImagePos);
     } else {
         vec3 norm = normalize(Normal);
         vec3 lightDir = normalize(lightPos - FragPos);
@@ -465,14 +1042,27 @@ void main()
         float diff = max(dot(norm, lightDir), 0.0);
         vec3 diffuse = diff * lightColor;
         
-        result = ObjectColor * vec4(ambient + diffuse * (1.0 - ambientStrength), 1.0) * texture(image, ImagePos);
+        result = objectColor * vec4(ambient + diffuse * (1.0 - ambientStrength), 1.0) * texture(image, ImagePos);
+    }
+    if (Emission < 1.0) {
+        // The housing of a LED doesn't glow, it only shows the light that reaches it.
+        float diff = max(dot(normalize(Normal), normalize(lightPos - FragPos)), 0.0);
+        result.rgb = mix(vec3(0.03) * lightColor * (0.3 + 0.7 * diff), result.rgb, Emission);
+        result.a = 1.0;
     }
     float contrastUmbral = 0.5;
     result.r = (result.r - contrastUmbral) * contrastFactor + contrastFactor * contrastUmbral - color_noise/2.0 + color_noise * random(vec3(ImagePos, time * 0.5));
     result.g = (result.g - contrastUmbral) * contrastFactor + contrastFactor * contrastUmbral - color_noise/2.0 + color_noise * random(vec3(ImagePos, time));
     result.b = (result.b - contrastUmbral) * contrastFactor + contrastFactor * contrastUmbral - color_noise/2.0 + color_noise * random(vec3(ImagePos, time * 2.0));
+    // LUTs are authored for encoded values.
+    if (lut_stage == 1.0) {
+        result.rgb = toLinear(applyLut(fromLinear(result.rgb, source_transfer, source_power)), source_transfer, source_power);
+    }
     result = result.r * vec4(red, result.a) + result.g * vec4(green, result.a) + result.b * vec4(blue, result.a) + vec4(extraLight, 0.0);
     FragColor = vec4(pow(result.r, gamma), pow(result.g, gamma), pow(result.b, gamma), result.a);
+    if (lut_stage == 2.0) {
+        FragColor.rgb = toLinear(applyLut(fromLinear(FragColor.rgb, display_transfer, display_power)), display_transfer, display_power);
+    }
 } 
 "#;
 
@@ -480,62 +1070,38 @@ void main()
 mod tests {
     use super::*;
 
-    fn position(vertex: usize) -> [f32; 3] {
-        let offset = vertex * 6;
-        [CUBE_GEOMETRY[offset], CUBE_GEOMETRY[offset + 1], CUBE_GEOMETRY[offset + 2]]
-    }
-
-    fn normal(vertex: usize) -> [f32; 3] {
-        let offset = vertex * 6 + 3;
-        [CUBE_GEOMETRY[offset], CUBE_GEOMETRY[offset + 1], CUBE_GEOMETRY[offset + 2]]
-    }
-
-    fn subtract(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
-        [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
-    }
-
-    fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
-        [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
-    }
-
-    fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
-        a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
-    }
-
     #[test]
-    fn every_cube_triangle_has_counter_clockwise_outward_winding() {
-        for triangle in 0..12 {
-            let first = triangle * 3;
-            let p0 = position(first);
-            let p1 = position(first + 1);
-            let p2 = position(first + 2);
-            let n0 = normal(first);
-
-            assert_eq!(normal(first + 1), n0, "triangle {triangle} has split normals");
-            assert_eq!(normal(first + 2), n0, "triangle {triangle} has split normals");
-            assert!(
-                dot(cross(subtract(p1, p0), subtract(p2, p0)), n0) > 0.0,
-                "triangle {} winds against its outward normal",
-                triangle
-            );
-        }
+    fn pixel_offsets_form_a_centered_unit_grid() {
+        assert_eq!(calculate_offsets(2, 2), vec![-0.5, 0.5, 0.5, 0.5, -0.5, -0.5, 0.5, -0.5]);
     }
 
     #[test]
-    fn square_geometry_is_a_flat_unit_square() {
-        let positions = (0..6).map(position).collect::<Vec<_>>();
-        assert!(positions.iter().all(|point| point[2] == 0.5));
-        let min_x = positions.iter().map(|point| point[0]).fold(f32::INFINITY, f32::min);
-        let max_x = positions.iter().map(|point| point[0]).fold(f32::NEG_INFINITY, f32::max);
-        let min_y = positions.iter().map(|point| point[1]).fold(f32::INFINITY, f32::min);
-        let max_y = positions.iter().map(|point| point[1]).fold(f32::NEG_INFINITY, f32::max);
+    fn average_color_ignores_alpha() {
+        let frame = [255, 0, 0, 0, 255, 255, 0, 255];
 
-        assert_eq!(max_x - min_x, 1.0);
-        assert_eq!(max_y - min_y, 1.0);
+        assert_eq!(average_color(&frame), [1.0, 0.5, 0.0]);
     }
 
     #[test]
-    fn pixel_offsets_form_a_centered_unit_grid() {
-        assert_eq!(calculate_offsets(2, 2), vec![-0.5, 0.5, 0.5, 0.5, -0.5, -0.5, 0.5, -0.5]);
+    fn lut_is_made_again_when_another_one_is_set() {
+        let first = ColorLut::parse("LUT_3D_SIZE 2\n0 0 0\n1 0 0\n0 1 0\n1 1 0\n0 0 1\n1 0 1\n0 1 1\n1 1 1\n").unwrap();
+        let second = ColorLut::parse("LUT_3D_SIZE 2\n1 1 1\n0 1 1\n1 0 1\n0 0 1\n1 1 0\n0 1 0\n1 0 0\n0 0 0\n").unwrap();
+        let mut slot = LutSlot {
+            generation: None,
+            current: None,
+        };
+        let mut deleted = Vec::new();
+
+        slot.update(1, Some(&first), |lut| Ok(lut.table.clone()), |old| deleted.push(old)).unwrap();
+        slot.update(1, Some(&first), |_| panic!("made twice"), |old| deleted.push(old)).unwrap();
+        assert_eq!(slot.current, Some(first.table.clone()));
+
+        slot.update(2, Some(&second), |lut| Ok(lut.table.clone()), |old| deleted.push(old)).unwrap();
+        assert_eq!(slot.current, Some(second.table.clone()));
+        assert_eq!(deleted, vec![first.table.clone()]);
+
+        slot.update(3, None, |_| panic!("nothing to make"), |old| deleted.push(old)).unwrap();
+        assert_eq!(slot.current, None);
+        assert_eq!(deleted, vec![first.table, second.table]);
     }
 }
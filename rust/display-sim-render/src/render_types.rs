@@ -40,14 +40,18 @@ impl<GL: HasContext> std::clone::Clone for TextureBuffer<GL> {
 }
 
 impl<GL: HasContext> TextureBuffer<GL> {
-    fn new(gl: &GlowSafeAdapter<GL>, width: i32, height: i32, interpolation: u32) -> AppResult<TextureBuffer<GL>> {
+    fn new(gl: &GlowSafeAdapter<GL>, width: i32, height: i32, interpolation: u32, high_precision: bool) -> AppResult<TextureBuffer<GL>> {
         let framebuffer = Some(gl.create_framebuffer()?);
         gl.bind_framebuffer(glow::FRAMEBUFFER, framebuffer);
 
         let texture = Some(gl.create_texture()?);
         gl.bind_texture(glow::TEXTURE_2D, texture);
 
-        gl.tex_image_2d(glow::TEXTURE_2D, 0, glow::RGBA as i32, width, height, 0, glow::RGBA, glow::UNSIGNED_BYTE, None);
+        if high_precision {
+            gl.tex_image_2d(glow::TEXTURE_2D, 0, glow::RGBA16F as i32, width, height, 0, glow::RGBA, glow::HALF_FLOAT, None);
+        } else {
+            gl.tex_image_2d(glow::TEXTURE_2D, 0, glow::RGBA as i32, width, height, 0, glow::RGBA, glow::UNSIGNED_BYTE, None);
+        }
         gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, interpolation as i32);
         gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, interpolation as i32);
         gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE as i32);
@@ -63,9 +67,9 @@ impl<GL: HasContext> TextureBuffer<GL> {
         })
     }
 
-    fn new_with_depthbuffer(gl: &GlowSafeAdapter<GL>, width: i32, height: i32, interpolation: u32) -> AppResult<TextureBuffer<GL>> {
+    fn new_with_depthbuffer(gl: &GlowSafeAdapter<GL>, width: i32, height: i32, interpolation: u32, high_precision: bool) -> AppResult<TextureBuffer<GL>> {
         let depthbuffer = Some(gl.create_renderbuffer()?);
-        let mut texture_buffer = Self::new(gl, width, height, interpolation)?;
+        let mut texture_buffer = Self::new(gl, width, height, interpolation, high_precision)?;
         gl.bind_renderbuffer(glow::RENDERBUFFER, depthbuffer);
         // The flight camera can be thousands of world units from one-unit
         // pixel cubes. Sixteen depth bits cannot distinguish their faces.
@@ -89,6 +93,7 @@ pub struct TextureBufferStack<GL: HasContext> {
     width: i32,
     height: i32,
     interpolation: u32,
+    high_precision_from: Option<usize>,
     cursor: usize,
     max_cursor: usize,
     cycle_depth_requirements: Vec<bool>,
@@ -102,6 +107,7 @@ impl<GL: HasContext> TextureBufferStack<GL> {
             width: 800,
             height: 600,
             interpolation: glow::LINEAR,
+            high_precision_from: None,
             cursor: 0,
             max_cursor: 0,
             cycle_depth_requirements: vec![],
@@ -129,6 +135,16 @@ impl<GL: HasContext> TextureBufferStack<GL> {
         Ok(())
     }
 
+    /// Buffers from that position up get half float channels, for values in
+    /// linear light that 8 bits would band.
+    pub fn set_high_precision_from(&mut self, index: Option<usize>) -> AppResult<()> {
+        if self.high_precision_from != index {
+            self.high_precision_from = index;
+            self.reset_stack()?;
+        }
+        Ok(())
+    }
+
     fn reset_stack(&mut self) -> AppResult<()> {
         self.cursor = 0;
         self.max_cursor = 0;
@@ -159,7 +175,7 @@ impl<GL: HasContext> TextureBufferStack<GL> {
 
         let buffer_has_depth = self.stack.get(index).map(|buffer| buffer.depthbuffer.is_some());
         if buffer_has_depth != Some(depth_required) {
-            let replacement = self.create_texture_buffer(depth_required)?;
+            let replacement = self.create_texture_buffer(index, depth_required)?;
             if index == self.stack.len() {
                 self.stack.push(replacement);
             } else if index < self.stack.len() {
@@ -227,11 +243,12 @@ impl<GL: HasContext> TextureBufferStack<GL> {
         Ok(())
     }
 
-    fn create_texture_buffer(&self, with_depth: bool) -> AppResult<TextureBuffer<GL>> {
+    fn create_texture_buffer(&self, index: usize, with_depth: bool) -> AppResult<TextureBuffer<GL>> {
+        let high_precision = self.high_precision_from.is_some_and(|from| index >= from);
         if with_depth {
-            TextureBuffer::new_with_depthbuffer(&*self.gl, self.width, self.height, self.interpolation)
+            TextureBuffer::new_with_depthbuffer(&*self.gl, self.width, self.height, self.interpolation, high_precision)
         } else {
-            TextureBuffer::new(&*self.gl, self.width, self.height, self.interpolation)
+            TextureBuffer::new(&*self.gl, self.width, self.height, self.interpolation, high_precision)
         }
     }
 
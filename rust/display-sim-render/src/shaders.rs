@@ -25,6 +25,12 @@ pub fn make_shader<GL: HasContext>(gl: &GlowSafeAdapter<GL>, vertex_shader: &str
     link_shader(gl, [vert_shader, frag_shader].iter())
 }
 
+/// Adds the transfer curves of `core::transfer_function` to a shader, right
+/// after its precision statement.
+pub fn with_transfer_functions(source: &str) -> String {
+    source.replacen("precision highp float;\n", &format!("precision highp float;\n{}", TRANSFER_FUNCTIONS), 1)
+}
+
 fn compile_shader<GL: HasContext>(gl: &GlowSafeAdapter<GL>, shader_type: u32, source: &str) -> AppResult<GL::Shader> {
     let shader = gl.create_shader(shader_type)?;
     gl_shader_source(gl, shader, source);
@@ -120,6 +126,39 @@ void main()
 }
 "#;
 
+// Curve kinds: 0 none, 1 sRGB, 2 BT.1886, 3 pure power.
+pub const TRANSFER_FUNCTIONS: &str = r#"
+const float BT1886_BLACK = 0.001;
+
+vec3 toLinear(vec3 v, float kind, float power) {
+    if (kind == 0.0) {
+        return v;
+    }
+    v = max(v, 0.0);
+    if (kind == 1.0) {
+        return mix(v / 12.92, pow((v + 0.055) / 1.055, vec3(2.4)), step(0.04045, v));
+    } else if (kind == 2.0) {
+        float black_root = pow(BT1886_BLACK, 1.0 / 2.4);
+        return pow(1.0 - black_root, 2.4) * pow(v + black_root / (1.0 - black_root), vec3(2.4));
+    }
+    return pow(v, vec3(power));
+}
+
+vec3 fromLinear(vec3 light, float kind, float power) {
+    if (kind == 0.0) {
+        return light;
+    }
+    light = max(light, 0.0);
+    if (kind == 1.0) {
+        return mix(light * 12.92, 1.055 * pow(light, vec3(1.0 / 2.4)) - 0.055, step(0.0031308, light));
+    } else if (kind == 2.0) {
+        float black_root = pow(BT1886_BLACK, 1.0 / 2.4);
+        return max(pow(light / pow(1.0 - black_root, 2.4), vec3(1.0 / 2.4)) - black_root / (1.0 - black_root), 0.0);
+    }
+    return pow(light, vec3(1.0 / power));
+}
+"#;
+
 pub const TEXTURE_FRAGMENT_SHADER: &str = r#"#version 300 es
 precision highp float;
 
@@ -13,12 +13,15 @@
  * You should have received a copy of the GNU Affero General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
 
+use crate::cabinet_render::CabinetUniform;
 use crate::error::AppResult;
 use crate::pixels_render::PixelsUniform;
 use crate::simulation_render_state::Materials;
+use crate::vector_render::VectorUniform;
 use core::simulation_context::SimulationContext;
 use core::simulation_core_state::Resources;
-use core::ui_controller::{color_channels::ColorChannelsOptions, texture_interpolation::TextureInterpolationOptions};
+use core::transfer_function::TransferCurve;
+use core::ui_controller::texture_interpolation::TextureInterpolationOptions;
 
 use glow::GlowSafeAdapter;
 
@@ -53,8 +56,26 @@ impl<'a> SimulationDrawer<'a> {
         let viewport_width = self.res.video.viewport_size.width;
         let viewport_height = self.res.video.viewport_size.height;
 
-        if self.res.video.needs_buffer_data_load {
-            materials.pixels_render.load_image(&self.res.video);
+        let vector = self.res.video.vector.as_ref().zip(output.vector_beam.as_ref());
+        let mut vector_half_size = [0.0, 0.0];
+        if let Some((scene, beam)) = vector {
+            vector_half_size = [
+                scene.width as f32 * output.pixel_spread[0] * output.lifecycle.scale[0] * 0.5,
+                scene.height as f32 * output.pixel_spread[1] * output.lifecycle.scale[1] * 0.5,
+            ];
+            // The phosphor keeps the aspect of the screen, as tall as the internal resolution.
+            let phosphor_width =
+                (resolution_height as f32 * scene.width as f32 * output.pixel_spread[0] / (scene.height as f32 * output.pixel_spread[1])).round() as i32;
+            materials.vector_render.expose(
+                &scene.frames[self.res.video.current_frame],
+                beam,
+                [scene.width as f32, scene.height as f32],
+                (phosphor_width, resolution_height),
+            )?;
+        } else if self.res.video.needs_buffer_data_load {
+            materials.pixels_render.load_image(&self.res.video, output)?;
+        } else if self.res.video.needs_panel_settle {
+            materials.pixels_render.settle_panel(&self.res.video, output);
         }
 
         materials.main_buffer_stack.set_resolution(resolution_width, resolution_height)?;
@@ -62,12 +83,17 @@ impl<'a> SimulationDrawer<'a> {
             TextureInterpolationOptions::Linear => glow::LINEAR,
             TextureInterpolationOptions::Nearest => glow::NEAREST,
         })?;
+        // The bottom target is presented and read back for screenshots, so it
+        // always holds 8 bit values encoded for the display.
+        materials.main_buffer_stack.set_high_precision_from(output.linear_light.map(|_| 1))?;
+        materials.bg_buffer_stack.set_high_precision_from(output.linear_light.map(|_| 0))?;
 
-        let overlapping_colors = matches!(filters.color_channels.value, ColorChannelsOptions::Overlapping);
+        let overlapping_colors = output.color_splits_overlap && vector.is_none();
 
         // The first target is the final composition. The second target holds
-        // geometry only in combined-color mode; overlapping colors render
-        // their geometry into three separate targets below.
+        // geometry only when color splits never overlap; overlapping or
+        // misconverged colors render their geometry into three separate
+        // targets below.
         materials.main_buffer_stack.push()?;
         materials.main_buffer_stack.push_with_depth(output.pixel_have_depth && !overlapping_colors)?;
         materials.main_buffer_stack.bind_current()?;
@@ -84,50 +110,72 @@ impl<'a> SimulationDrawer<'a> {
             self.res.camera.get_projection(viewport_width as f32, viewport_height as f32)
         };
 
-        for hl_idx in 0..filters.horizontal_lpp.value {
-            for vl_idx in 0..filters.vertical_lpp.value {
-                for color_idx in 0..output.color_splits {
-                    if overlapping_colors {
-                        materials.main_buffer_stack.push_with_depth(output.pixel_have_depth)?;
-                        materials.main_buffer_stack.bind_current()?;
-                        if vl_idx == 0 && hl_idx == 0 {
-                            gl.clear(glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT);
+        if vector.is_some() {
+            materials.vector_render.render(VectorUniform {
+                view: &matrix_to_16_f32(view),
+                projection: &matrix_to_16_f32(projection),
+                half_size: &vector_half_size,
+                brightness: output.lifecycle.brightness,
+            })?;
+        } else {
+            for hl_idx in 0..output.horizontal_lpp {
+                for vl_idx in 0..output.vertical_lpp {
+                    for color_idx in 0..output.color_splits {
+                        if overlapping_colors {
+                            materials.main_buffer_stack.push_with_depth(output.pixel_have_depth)?;
+                            materials.main_buffer_stack.bind_current()?;
+                            if vl_idx == 0 && hl_idx == 0 {
+                                gl.clear(glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT);
+                            }
                         }
+                        materials.pixels_render.render(PixelsUniform {
+                            shadow_kind: filters.pixel_shadow_shape_kind.value.value,
+                            geometry_kind: filters.pixels_geometry_kind.value,
+                            view: &matrix_to_16_f32(view),
+                            projection: &matrix_to_16_f32(projection),
+                            ambient_strength: output.ambient_strength,
+                            contrast_factor: filters.extra_contrast.value,
+                            light_color: &output.light_color[color_idx],
+                            white_subpixel: output.white_subpixel == Some(color_idx),
+                            extra_light: &output.extra_light,
+                            light_pos: &vec_to_3_f32(position),
+                            screen_geometry: &output.screen_geometry,
+                            screen_mask: &output.screen_mask,
+                            pixel_spread: &output.pixel_spread,
+                            pixel_scale: &output
+                                .pixel_scale_foreground
+                                .get(vl_idx * output.horizontal_lpp + hl_idx)
+                                .expect("Bad pixel_scale_foreground")[color_idx],
+                            pixel_pulse: output.pixels_pulse,
+                            pixel_offset: &output
+                                .pixel_offset_foreground
+                                .get(vl_idx * output.horizontal_lpp + hl_idx)
+                                .expect("Bad pixel_offset_foreground")[color_idx],
+                            convergence_radial: output.convergence_radial[color_idx],
+                            rgb_red: &output.rgb_red,
+                            rgb_green: &output.rgb_green,
+                            rgb_blue: &output.rgb_blue,
+                            color_gamma: output.color_gamma,
+                            color_noise: output.color_noise,
+                            time: output.time as f32,
+                            height_modifier_factor: output.height_modifier_factor,
+                            interlace_field: output.interlace_field,
+                            interlace_flicker: output.interlace_flicker,
+                            interlace_combing: output.interlace_combing,
+                            scan_band: &output.scan_band,
+                            rf_interference: &output.rf_interference,
+                            lifecycle: &output.lifecycle,
+                            tube_aging: &output.tube_aging,
+                            ambient_lighting: &output.ambient_lighting,
+                            lut_grading: &output.lut_grading,
+                            linear_light: &output.linear_light,
+                        });
+                    }
+                    if overlapping_colors {
+                        materials.main_buffer_stack.pop()?;
+                        materials.main_buffer_stack.pop()?;
+                        materials.main_buffer_stack.pop()?;
                     }
-                    materials.pixels_render.render(PixelsUniform {
-                        shadow_kind: filters.pixel_shadow_shape_kind.value.value,
-                        geometry_kind: filters.pixels_geometry_kind.value,
-                        view: &matrix_to_16_f32(view),
-                        projection: &matrix_to_16_f32(projection),
-                        ambient_strength: output.ambient_strength,
-                        contrast_factor: filters.extra_contrast.value,
-                        light_color: &output.light_color[color_idx],
-                        extra_light: &output.extra_light,
-                        light_pos: &vec_to_3_f32(position),
-                        screen_curvature: output.screen_curvature_factor,
-                        pixel_spread: &output.pixel_spread,
-                        pixel_scale: &output
-                            .pixel_scale_foreground
-                            .get(vl_idx * filters.horizontal_lpp.value + hl_idx)
-                            .expect("Bad pixel_scale_foreground")[color_idx],
-                        pixel_pulse: output.pixels_pulse,
-                        pixel_offset: &output
-                            .pixel_offset_foreground
-                            .get(vl_idx * filters.horizontal_lpp.value + hl_idx)
-                            .expect("Bad pixel_offset_foreground")[color_idx],
-                        rgb_red: &output.rgb_red,
-                        rgb_green: &output.rgb_green,
-                        rgb_blue: &output.rgb_blue,
-                        color_gamma: output.color_gamma,
-                        color_noise: output.color_noise,
-                        time: output.time as f32,
-                        height_modifier_factor: output.height_modifier_factor,
-                    });
-                }
-                if overlapping_colors {
-                    materials.main_buffer_stack.pop()?;
-                    materials.main_buffer_stack.pop()?;
-                    materials.main_buffer_stack.pop()?;
                 }
             }
         }
@@ -158,36 +206,58 @@ impl<'a> SimulationDrawer<'a> {
             materials.bg_buffer_stack.push_with_depth(output.pixel_have_depth)?;
             materials.bg_buffer_stack.bind_current()?;
             gl.clear(glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT);
-            for hl_idx in 0..filters.horizontal_lpp.value {
-                for vl_idx in 0..filters.vertical_lpp.value {
-                    materials.pixels_render.render(PixelsUniform {
-                        shadow_kind: 0,
-                        geometry_kind: filters.pixels_geometry_kind.value,
-                        view: &matrix_to_16_f32(view),
-                        projection: &matrix_to_16_f32(projection),
-                        ambient_strength: output.ambient_strength,
-                        contrast_factor: filters.extra_contrast.value,
-                        light_color: &output.light_color_background,
-                        extra_light: &[0.0, 0.0, 0.0],
-                        light_pos: &vec_to_3_f32(position),
-                        pixel_spread: &output.pixel_spread,
-                        pixel_scale: &output.pixel_scale_background[vl_idx * filters.horizontal_lpp.value + hl_idx],
-                        screen_curvature: output.screen_curvature_factor,
-                        pixel_pulse: output.pixels_pulse,
-                        pixel_offset: &output.pixel_offset_background[vl_idx * filters.horizontal_lpp.value + hl_idx],
-                        rgb_red: &output.rgb_red,
-                        rgb_green: &output.rgb_green,
-                        rgb_blue: &output.rgb_blue,
-                        color_gamma: output.color_gamma,
-                        color_noise: output.color_noise,
-                        time: output.time as f32,
-                        height_modifier_factor: 0.0,
-                    });
+            if let Some((_, beam)) = vector {
+                materials.vector_render.render(VectorUniform {
+                    view: &matrix_to_16_f32(view),
+                    projection: &matrix_to_16_f32(projection),
+                    half_size: &vector_half_size,
+                    brightness: output.lifecycle.brightness * beam.bloom,
+                })?;
+            } else {
+                for hl_idx in 0..output.horizontal_lpp {
+                    for vl_idx in 0..output.vertical_lpp {
+                        materials.pixels_render.render(PixelsUniform {
+                            shadow_kind: 0,
+                            geometry_kind: filters.pixels_geometry_kind.value,
+                            view: &matrix_to_16_f32(view),
+                            projection: &matrix_to_16_f32(projection),
+                            ambient_strength: output.ambient_strength,
+                            contrast_factor: filters.extra_contrast.value,
+                            light_color: &output.light_color_background,
+                            white_subpixel: false,
+                            extra_light: &[0.0, 0.0, 0.0],
+                            light_pos: &vec_to_3_f32(position),
+                            pixel_spread: &output.pixel_spread,
+                            pixel_scale: &output.pixel_scale_background[vl_idx * output.horizontal_lpp + hl_idx],
+                            screen_geometry: &output.screen_geometry,
+                            screen_mask: &output.screen_mask,
+                            pixel_pulse: output.pixels_pulse,
+                            pixel_offset: &output.pixel_offset_background[vl_idx * output.horizontal_lpp + hl_idx],
+                            convergence_radial: 0.0,
+                            rgb_red: &output.rgb_red,
+                            rgb_green: &output.rgb_green,
+                            rgb_blue: &output.rgb_blue,
+                            color_gamma: output.color_gamma,
+                            color_noise: output.color_noise,
+                            time: output.time as f32,
+                            height_modifier_factor: 0.0,
+                            interlace_field: output.interlace_field,
+                            interlace_flicker: output.interlace_flicker,
+                            interlace_combing: output.interlace_combing,
+                            scan_band: &output.scan_band,
+                            rf_interference: &output.rf_interference,
+                            lifecycle: &output.lifecycle,
+                            tube_aging: &output.tube_aging,
+                            ambient_lighting: &output.ambient_lighting,
+                            lut_grading: &output.lut_grading,
+                            linear_light: &output.linear_light,
+                        });
+                    }
                 }
             }
             let source = (*materials.bg_buffer_stack.get_current()?).clone();
             let target = materials.main_buffer_stack.get_current()?;
-            materials.blur_render.render(&mut materials.bg_buffer_stack, &source, target, 6)?;
+            materials.blur_render.render(&mut materials.bg_buffer_stack, &source, target, 6, None)?;
             materials.bg_buffer_stack.pop()?;
             materials.bg_buffer_stack.assert_no_stack()?;
         } else {
@@ -204,14 +274,71 @@ impl<'a> SimulationDrawer<'a> {
         gl.bind_texture(glow::TEXTURE_2D, materials.main_buffer_stack.get_nth(1)?.texture());
         gl.active_texture(glow::TEXTURE0 + 1);
         gl.bind_texture(glow::TEXTURE_2D, materials.main_buffer_stack.get_nth(2)?.texture());
-        materials.background_render.render();
+        materials.background_render.render(&output.linear_light);
         gl.active_texture(glow::TEXTURE0 + 0);
 
         if filters.blur_passes.value > 0 {
             let target = materials.main_buffer_stack.get_current()?.clone();
+            materials.blur_render.render(
+                &mut materials.main_buffer_stack,
+                &target,
+                &target,
+                filters.blur_passes.value,
+                output.linear_light.map(|linear_light| linear_light.display),
+            )?;
+        }
+
+        if let Some(substrate) = &output.lcd_substrate {
+            materials.main_buffer_stack.bind_current()?;
+            materials
+                .artwork_render
+                .render_substrate(substrate, &matrix_to_16_f32(view), &matrix_to_16_f32(projection));
+        }
+
+        if let (Some(placement), Some(model)) = (&output.cabinet, &self.res.video.cabinet) {
+            // The cabinet needs its own depth buffer, and then goes behind
+            // everything already composed.
+            materials.cabinet_buffer_stack.set_resolution(resolution_width, resolution_height)?;
+            materials.cabinet_buffer_stack.push_with_depth(true)?;
+            materials.cabinet_buffer_stack.bind_current()?;
+            gl.clear(glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT);
+            let screen_glow = materials.pixels_render.average_color;
+            materials.cabinet_render.render(
+                model,
+                CabinetUniform {
+                    view: &matrix_to_16_f32(view),
+                    projection: &matrix_to_16_f32(projection),
+                    light_pos: &vec_to_3_f32(position),
+                    placement,
+                    screen_glow: &screen_glow,
+                },
+            );
+            let cabinet_texture = materials.cabinet_buffer_stack.get_current()?.texture();
+            materials.cabinet_buffer_stack.pop()?;
+            materials.cabinet_buffer_stack.assert_no_stack()?;
+
+            materials.main_buffer_stack.bind_current()?;
+            gl.enable(glow::BLEND);
+            gl.blend_func(glow::ONE_MINUS_DST_ALPHA, glow::ONE);
+            materials.internal_resolution_render.render(cabinet_texture);
+            gl.disable(glow::BLEND);
+        } else {
+            materials.cabinet_buffer_stack.clear()?;
+        }
+
+        materials.main_buffer_stack.bind_current()?;
+        materials
+            .artwork_render
+            .render(&output.artwork, &matrix_to_16_f32(view), &matrix_to_16_f32(projection));
+
+        if let Some(color_vision) = &output.color_vision {
+            // Last, so that it covers everything the viewer would look at.
+            let target = materials.main_buffer_stack.get_current()?.clone();
+            let encoding = output.linear_light.map_or(TransferCurve::Srgb, |linear_light| linear_light.display);
             materials
-                .blur_render
-                .render(&mut materials.main_buffer_stack, &target, &target, filters.blur_passes.value)?;
+                .color_vision_render
+                .render(&mut materials.main_buffer_stack, &target, color_vision, encoding)?;
+            materials.main_buffer_stack.bind_current()?;
         }
 
         materials.screenshot_pixels = None;
@@ -13,13 +13,17 @@
  * You should have received a copy of the GNU Affero General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
 
+use crate::artwork_render::ArtworkRender;
 use crate::background_render::BackgroundRender;
 use crate::blur_render::BlurRender;
+use crate::cabinet_render::CabinetRender;
+use crate::color_vision_render::ColorVisionRender;
 use crate::error::AppResult;
 use crate::internal_resolution_render::InternalResolutionRender;
 use crate::pixels_render::PixelsRender;
 use crate::render_types::TextureBufferStack;
 use crate::rgb_render::RgbRender;
+use crate::vector_render::VectorRender;
 
 use glow::Context;
 use glow::GlowSafeAdapter;
@@ -28,6 +32,14 @@ use std::rc::Rc;
 #[derive(Default)]
 pub struct VideoInputMaterials {
     pub buffers: Vec<Box<[u8]>>,
+    /// RGBA images for the layers of `VideoInputResources::artwork`, in the same order.
+    pub artwork: Vec<ArtworkImage>,
+}
+
+pub struct ArtworkImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Box<[u8]>,
 }
 
 // Rendering Materials
@@ -35,11 +47,16 @@ pub struct Materials {
     pub gl: Rc<GlowSafeAdapter<Context>>,
     pub main_buffer_stack: TextureBufferStack<Context>,
     pub bg_buffer_stack: TextureBufferStack<Context>,
+    pub cabinet_buffer_stack: TextureBufferStack<Context>,
     pub pixels_render: PixelsRender<Context>,
     pub blur_render: BlurRender<Context>,
     pub background_render: BackgroundRender<Context>,
     pub internal_resolution_render: InternalResolutionRender<Context>,
     pub rgb_render: RgbRender<Context>,
+    pub artwork_render: ArtworkRender<Context>,
+    pub cabinet_render: CabinetRender<Context>,
+    pub color_vision_render: ColorVisionRender<Context>,
+    pub vector_render: VectorRender<Context>,
     pub screenshot_pixels: Option<Box<[u8]>>,
 }
 
@@ -48,6 +65,11 @@ impl Materials {
         Ok(Materials {
             main_buffer_stack: TextureBufferStack::new(gl.clone()),
             bg_buffer_stack: TextureBufferStack::new(gl.clone()),
+            cabinet_buffer_stack: TextureBufferStack::new(gl.clone()),
+            artwork_render: ArtworkRender::new(gl.clone(), &video.artwork)?,
+            cabinet_render: CabinetRender::new(gl.clone())?,
+            color_vision_render: ColorVisionRender::new(gl.clone())?,
+            vector_render: VectorRender::new(gl.clone())?,
             pixels_render: PixelsRender::new(gl.clone(), video)?,
             blur_render: BlurRender::new(gl.clone())?,
             internal_resolution_render: InternalResolutionRender::new(gl.clone())?,
@@ -0,0 +1,246 @@
+/* Copyright (c) 2019-2024 José manuel Barroso Galindo <theypsilon@gmail.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>. */
+
+use crate::error::AppResult;
+use crate::render_types::TextureBufferStack;
+use crate::shaders::{make_quad_vao, make_shader, TEXTURE_VERTEX_SHADER};
+use core::general_types::f32_to_u8;
+use core::vector_display::{VectorBeam, VectorFrame, VECTOR_VERTEX_STRIDE};
+
+use glow::GlowSafeAdapter;
+use glow::HasContext;
+use std::mem::size_of;
+use std::rc::Rc;
+
+pub struct VectorUniform<'a> {
+    pub view: &'a [f32; 16],
+    pub projection: &'a [f32; 16],
+    /// World size of the screen, from its center to its edges.
+    pub half_size: &'a [f32; 2],
+    pub brightness: f32,
+}
+
+/// Draws vector scenes. The beam traces the lines of each frame on a
+/// phosphor texture that keeps fading between frames, and that texture is
+/// then placed on the plane of the screen like the pixels would be.
+pub struct VectorRender<GL: HasContext> {
+    beam_shader: GL::Program,
+    beam_vao: Option<GL::VertexArray>,
+    beam_vbo: GL::Buffer,
+    fade_shader: GL::Program,
+    fade_vao: Option<GL::VertexArray>,
+    screen_shader: GL::Program,
+    screen_vao: Option<GL::VertexArray>,
+    phosphor: TextureBufferStack<GL>,
+    phosphor_resolution: Option<(i32, i32)>,
+    gl: Rc<GlowSafeAdapter<GL>>,
+}
+
+impl<GL: HasContext> VectorRender<GL> {
+    pub fn new(gl: Rc<GlowSafeAdapter<GL>>) -> AppResult<VectorRender<GL>> {
+        let beam_shader = make_shader(&*gl, BEAM_VERTEX_SHADER, BEAM_FRAGMENT_SHADER)?;
+        let beam_vao = Some(gl.create_vertex_array()?);
+        gl.bind_vertex_array(beam_vao);
+        let beam_vbo = gl.create_buffer()?;
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(beam_vbo));
+        let stride = (VECTOR_VERTEX_STRIDE * size_of::<f32>()) as i32;
+        let mut offset = 0;
+        for (name, size) in [("aPos", 2), ("aLocal", 2), ("aLength", 1), ("aColor", 3)] {
+            let position = gl.get_attrib_location(beam_shader, name);
+            gl.vertex_attrib_pointer_f32(position, size, glow::FLOAT, false, stride, offset * size_of::<f32>() as i32);
+            gl.enable_vertex_attrib_array(position);
+            offset += size;
+        }
+
+        let fade_shader = make_shader(&*gl, TEXTURE_VERTEX_SHADER, FADE_FRAGMENT_SHADER)?;
+        let fade_vao = make_quad_vao(&*gl, &fade_shader)?;
+        let screen_shader = make_shader(&*gl, SCREEN_VERTEX_SHADER, SCREEN_FRAGMENT_SHADER)?;
+        let screen_vao = make_quad_vao(&*gl, &screen_shader)?;
+
+        Ok(VectorRender {
+            beam_shader,
+            beam_vao,
+            beam_vbo,
+            fade_shader,
+            fade_vao,
+            screen_shader,
+            screen_vao,
+            phosphor: TextureBufferStack::new(gl.clone()),
+            phosphor_resolution: None,
+            gl,
+        })
+    }
+
+    /// Fades what the phosphor still shows, and traces `frame` over it.
+    pub fn expose(&mut self, frame: &VectorFrame, beam: &VectorBeam, scene_size: [f32; 2], resolution: (i32, i32)) -> AppResult<()> {
+        // Half floats, or the slow end of the fade would get stuck on the
+        // lowest 8 bit value.
+        self.phosphor.set_high_precision_from(Some(0))?;
+        self.phosphor.set_resolution(resolution.0, resolution.1)?;
+        self.phosphor.push()?;
+        self.phosphor.bind_current()?;
+
+        let gl = &self.gl;
+        gl.disable(glow::DEPTH_TEST);
+        if self.phosphor_resolution != Some(resolution) {
+            self.phosphor_resolution = Some(resolution);
+            gl.clear_color(0.0, 0.0, 0.0, 0.0);
+            gl.clear(glow::COLOR_BUFFER_BIT);
+        }
+        gl.enable(glow::BLEND);
+
+        gl.blend_func(glow::ZERO, glow::SRC_ALPHA);
+        gl.use_program(Some(self.fade_shader));
+        gl.bind_vertex_array(self.fade_vao);
+        gl.uniform_1_f32(gl.get_uniform_location(self.fade_shader, "persistence"), beam.persistence);
+        gl.draw_elements(glow::TRIANGLES, 6, glow::UNSIGNED_INT, 0);
+
+        let vertices = frame.beam_vertices(beam);
+        if !vertices.is_empty() {
+            gl.blend_func(glow::ONE, glow::ONE);
+            gl.use_program(Some(self.beam_shader));
+            gl.bind_vertex_array(self.beam_vao);
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.beam_vbo));
+            gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, f32_to_u8(&vertices), glow::STREAM_DRAW);
+            gl.uniform_2_f32_slice(gl.get_uniform_location(self.beam_shader, "sceneSize"), &scene_size);
+            gl.uniform_1_f32(gl.get_uniform_location(self.beam_shader, "beamWidth"), beam.width);
+            gl.uniform_1_f32(gl.get_uniform_location(self.beam_shader, "hotSpot"), beam.hot_spot);
+            gl.uniform_1_f32(gl.get_uniform_location(self.beam_shader, "deposit"), beam.deposit());
+            gl.draw_arrays(glow::TRIANGLES, 0, (vertices.len() / VECTOR_VERTEX_STRIDE) as i32);
+        }
+
+        gl.bind_vertex_array(None);
+        gl.disable(glow::BLEND);
+        gl.enable(glow::DEPTH_TEST);
+        self.phosphor.pop()
+    }
+
+    /// Draws the phosphor on the plane of the screen, into the bound target.
+    pub fn render(&self, uniforms: VectorUniform) -> AppResult<()> {
+        let gl = &self.gl;
+        let shader = self.screen_shader;
+        gl.use_program(Some(shader));
+        gl.bind_vertex_array(self.screen_vao);
+        gl.uniform_matrix_4_f32_slice(gl.get_uniform_location(shader, "view"), false, uniforms.view);
+        gl.uniform_matrix_4_f32_slice(gl.get_uniform_location(shader, "projection"), false, uniforms.projection);
+        gl.uniform_2_f32_slice(gl.get_uniform_location(shader, "halfSize"), uniforms.half_size);
+        gl.uniform_1_f32(gl.get_uniform_location(shader, "brightness"), uniforms.brightness);
+        gl.active_texture(glow::TEXTURE0 + 0);
+        gl.bind_texture(glow::TEXTURE_2D, self.phosphor.get_nth(1)?.texture());
+        gl.draw_elements(glow::TRIANGLES, 6, glow::UNSIGNED_INT, 0);
+        gl.bind_texture(glow::TEXTURE_2D, None);
+        gl.bind_vertex_array(None);
+        Ok(())
+    }
+}
+
+pub const BEAM_VERTEX_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 aPos;
+in vec2 aLocal;
+in float aLength;
+in vec3 aColor;
+
+out vec2 Local;
+out float Length;
+out vec3 Color;
+
+uniform vec2 sceneSize;
+
+void main()
+{
+    Local = aLocal;
+    Length = aLength;
+    Color = aColor;
+    gl_Position = vec4(aPos / sceneSize * 2.0 - 1.0, 0.0, 1.0);
+}
+"#;
+
+pub const BEAM_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+out vec4 FragColor;
+
+in vec2 Local;
+in float Length;
+in vec3 Color;
+
+uniform float beamWidth;
+uniform float hotSpot;
+uniform float deposit;
+
+float spot(vec2 offset) {
+    float sigma = beamWidth * 0.5;
+    return exp(-dot(offset, offset) / (2.0 * sigma * sigma));
+}
+
+void main()
+{
+    // Gaussian spot swept along the segment, plus the spots where the beam
+    // settles at both ends and leaves more energy.
+    vec2 toLine = vec2(Local.x - clamp(Local.x, 0.0, Length), Local.y);
+    float ends = spot(Local) + spot(Local - vec2(Length, 0.0));
+    FragColor = vec4(Color * (spot(toLine) + hotSpot * ends) * deposit, 0.0);
+}
+"#;
+
+pub const FADE_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+out vec4 FragColor;
+in vec2 TexCoord;
+
+uniform float persistence;
+
+void main()
+{
+    FragColor = vec4(0.0, 0.0, 0.0, persistence);
+}
+"#;
+
+pub const SCREEN_VERTEX_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+layout (location = 0) in vec3 qPos;
+layout (location = 1) in vec2 qTexCoords;
+
+out vec2 TexCoord;
+
+uniform mat4 view;
+uniform mat4 projection;
+uniform vec2 halfSize;
+
+void main()
+{
+    TexCoord = qTexCoords;
+    gl_Position = projection * view * vec4(qPos.xy * halfSize, 0.0, 1.0);
+}
+"#;
+
+pub const SCREEN_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+out vec4 FragColor;
+in vec2 TexCoord;
+
+uniform sampler2D image;
+uniform float brightness;
+
+void main()
+{
+    FragColor = vec4(texture(image, TexCoord).rgb * brightness, 1.0);
+}
+"#;
@@ -9,7 +9,21 @@
 use core::camera::{CameraChange, CameraLockMode};
 use core::simulation_command::{ControllerValue, Pressed, SimulationCommand, SimulationCommandBus};
 use core::simulation_core_state::{Resources, ScalingMethod};
+use core::ui_controller::color_vision::ColorVisionOptions;
+use core::ui_controller::display_transfer::DisplayTransferOptions;
+use core::ui_controller::dither_blend::DitherBlendOptions;
 use core::ui_controller::filter_preset::FilterPresetOptions;
+use core::ui_controller::lut_stage::LutStageOptions;
+use core::ui_controller::monochrome_luma::MonochromeLumaOptions;
+use core::ui_controller::reflective_panel::ReflectivePanelOptions;
+use core::ui_controller::scan_mode::ScanModeOptions;
+use core::ui_controller::scan_output::ScanOutputOptions;
+use core::ui_controller::screen_curvature_kind::ScreenCurvatureKindOptions;
+use core::ui_controller::signal_type::SignalTypeOptions;
+use core::ui_controller::source_depth::SourceDepthOptions;
+use core::ui_controller::source_transfer::SourceTransferOptions;
+use core::ui_controller::tape_speed::TapeSpeedOptions;
+use core::ui_controller::white_point::WhitePointOptions;
 use egui::{
     Align, Align2, Color32, Context, CornerRadius, CursorIcon, Event, FontId, Id, Popup, Pos2, Rect, Response, ScrollArea, Sense, Stroke, TextEdit, TextStyle,
     Ui, Vec2,
@@ -194,6 +208,7 @@ struct SectionStates {
     image_scaling: bool,
     performance: bool,
     colors: bool,
+    signal: bool,
     geometry: bool,
     camera: bool,
     modifiers: bool,
@@ -207,6 +222,7 @@ pub enum SimPanelSection {
     ImageScaling,
     Performance,
     Colors,
+    VideoSignal,
     GeometryAndTextures,
     Camera,
     CommandModifiers,
@@ -221,6 +237,7 @@ impl Default for SectionStates {
             image_scaling: false,
             performance: true,
             colors: false,
+            signal: false,
             geometry: false,
             camera: false,
             modifiers: false,
@@ -288,6 +305,7 @@ impl SimPanel {
             image_scaling: section == SimPanelSection::ImageScaling,
             performance: section == SimPanelSection::Performance,
             colors: section == SimPanelSection::Colors,
+            signal: section == SimPanelSection::VideoSignal,
             geometry: section == SimPanelSection::GeometryAndTextures,
             camera: section == SimPanelSection::Camera,
             modifiers: section == SimPanelSection::CommandModifiers,
@@ -586,6 +604,51 @@ impl SimPanel {
             if changed {
                 set(commands, "front2back:blur-level", blur as f64);
             }
+            self.selector(
+                ui,
+                "Scan output",
+                None,
+                Accent::Yellow,
+                &res.controllers.scan_output.value.to_string(),
+                "scan-output-dec",
+                "scan-output-inc",
+                commands,
+            );
+            let scanning = !matches!(res.controllers.scan_output.value, ScanOutputOptions::Off);
+            let mut display_refresh = res.controllers.display_refresh.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Display refresh (Hz)",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut display_refresh,
+                24.0..=480.0,
+                1.0,
+                "display-refresh-dec",
+                "display-refresh-inc",
+                scanning,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:display-refresh", display_refresh as f64);
+            }
+            let mut source_refresh = res.controllers.source_refresh.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Source refresh (Hz)",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut source_refresh,
+                24.0..=120.0,
+                1.0,
+                "source-refresh-dec",
+                "source-refresh-inc",
+                scanning,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:source-refresh", source_refresh as f64);
+            }
         }
 
         if section_header(ui, "Colors", &mut self.sections.colors) {
@@ -593,76 +656,909 @@ impl SimPanel {
             let mut gamma = res.controllers.color_gamma.value;
             let (_, changed) = self.number_f32(
                 ui,
-                "Gamma correction",
+                "Gamma correction",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut gamma,
+                0.0..=3.0,
+                0.1,
+                "color-gamma-dec",
+                "color-gamma-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:color-gamma", gamma as f64);
+            }
+            self.selector(
+                ui,
+                "Source primaries",
+                None,
+                Accent::Red,
+                &res.controllers.source_primaries.value.to_string(),
+                "source-primaries-dec",
+                "source-primaries-inc",
+                commands,
+            );
+            self.selector(
+                ui,
+                "Display primaries",
+                None,
+                Accent::Red,
+                &res.controllers.display_primaries.value.to_string(),
+                "display-primaries-dec",
+                "display-primaries-inc",
+                commands,
+            );
+            self.selector(
+                ui,
+                "White point",
+                None,
+                Accent::Red,
+                &res.controllers.white_point.value.to_string(),
+                "white-point-dec",
+                "white-point-inc",
+                commands,
+            );
+            let mut temperature = res.controllers.color_temperature.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Color temperature (K)",
+                ("????", "Shift + ????"),
+                Accent::Red,
+                &mut temperature,
+                4000.0..=12000.0,
+                100.0,
+                "color-temperature-dec",
+                "color-temperature-inc",
+                matches!(res.controllers.white_point.value, WhitePointOptions::Custom),
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:color-temperature", temperature as f64);
+            }
+            // Only meaningful once a .cube file has been loaded.
+            if res.video.color_lut.is_some() {
+                self.selector(
+                    ui,
+                    "LUT stage",
+                    None,
+                    Accent::Red,
+                    &res.controllers.lut_stage.value.to_string(),
+                    "lut-stage-dec",
+                    "lut-stage-inc",
+                    commands,
+                );
+                self.selector(
+                    ui,
+                    "LUT interpolation",
+                    None,
+                    Accent::Red,
+                    &res.controllers.lut_interpolation.value.to_string(),
+                    "lut-interpolation-dec",
+                    "lut-interpolation-inc",
+                    commands,
+                );
+                let mut strength = res.controllers.lut_strength.value;
+                let (_, changed) = self.number_f32(
+                    ui,
+                    "LUT strength",
+                    ("????", "Shift + ????"),
+                    Accent::Red,
+                    &mut strength,
+                    0.0..=1.0,
+                    0.1,
+                    "lut-strength-dec",
+                    "lut-strength-inc",
+                    !matches!(res.controllers.lut_stage.value, LutStageOptions::Off),
+                    commands,
+                );
+                if changed {
+                    set(commands, "front2back:lut-strength", strength as f64);
+                }
+            }
+            self.selector(
+                ui,
+                "Source EOTF",
+                None,
+                Accent::Lilac,
+                &res.controllers.source_transfer.value.to_string(),
+                "source-transfer-dec",
+                "source-transfer-inc",
+                commands,
+            );
+            self.selector(
+                ui,
+                "Display transfer",
+                None,
+                Accent::Lilac,
+                &res.controllers.display_transfer.value.to_string(),
+                "display-transfer-dec",
+                "display-transfer-inc",
+                commands,
+            );
+            let linear_light = !matches!(res.controllers.source_transfer.value, SourceTransferOptions::Off);
+            let mut power = res.controllers.transfer_power.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Transfer power",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut power,
+                1.0..=3.0,
+                0.1,
+                "transfer-power-dec",
+                "transfer-power-inc",
+                linear_light
+                    && (matches!(res.controllers.source_transfer.value, SourceTransferOptions::Power)
+                        || matches!(res.controllers.display_transfer.value, DisplayTransferOptions::Power)),
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:transfer-power", power as f64);
+            }
+            let mut noise = res.controllers.color_noise.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Color noise",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut noise,
+                0.0..=1.0,
+                0.1,
+                "color-noise-dec",
+                "color-noise-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:color-noise", noise as f64);
+            }
+            let packed = res.controllers.light_color.value as u32;
+            let mut color = [((packed >> 16) & 0xff) as u8, ((packed >> 8) & 0xff) as u8, (packed & 0xff) as u8];
+            if color_row(ui, "Source light color", Accent::Blue, &mut color) {
+                let value = ((color[0] as u32) << 16) | ((color[1] as u32) << 8) | color[2] as u32;
+                set(commands, "front2back:light-color", value as f64);
+            }
+            let mut bright = res.controllers.extra_bright.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Brightness",
+                ("X", "Shift + X"),
+                Accent::White,
+                &mut bright,
+                -1.0..=1.0,
+                0.001,
+                "pixel-brightness-dec",
+                "pixel-brightness-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:pixel-brightness", bright as f64);
+            }
+            let mut contrast = res.controllers.extra_contrast.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Contrast",
+                ("Z", "Shift + Z"),
+                Accent::White,
+                &mut contrast,
+                0.0..=20.0,
+                0.001,
+                "pixel-contrast-dec",
+                "pixel-contrast-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:pixel-contrast", contrast as f64);
+            }
+            self.selector(
+                ui,
+                "Monochrome",
+                None,
+                Accent::Green,
+                &res.controllers.monochrome_luma.value.to_string(),
+                "monochrome-luma-dec",
+                "monochrome-luma-inc",
+                commands,
+            );
+            let monochrome = !matches!(res.controllers.monochrome_luma.value, MonochromeLumaOptions::Off);
+            self.selector(
+                ui,
+                "Phosphor",
+                None,
+                Accent::Green,
+                &res.controllers.phosphor_type.value.to_string(),
+                "phosphor-type-dec",
+                "phosphor-type-inc",
+                commands,
+            );
+            let mut bloom = res.controllers.phosphor_bloom.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Phosphor bloom",
+                ("????", "Shift + ????"),
+                Accent::Green,
+                &mut bloom,
+                0.0..=1.0,
+                0.01,
+                "phosphor-bloom-dec",
+                "phosphor-bloom-inc",
+                monochrome,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:phosphor-bloom", bloom as f64);
+            }
+            self.selector(
+                ui,
+                "LCD palette",
+                None,
+                Accent::Lilac,
+                &res.controllers.lcd_palette.value.to_string(),
+                "lcd-palette-dec",
+                "lcd-palette-inc",
+                commands,
+            );
+            let mut rise = res.controllers.lcd_response_rise.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "LCD rise time (ms)",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut rise,
+                0.0..=250.0,
+                1.0,
+                "lcd-response-rise-dec",
+                "lcd-response-rise-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:lcd-response-rise", rise as f64);
+            }
+            let mut fall = res.controllers.lcd_response_fall.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "LCD fall time (ms)",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut fall,
+                0.0..=250.0,
+                1.0,
+                "lcd-response-fall-dec",
+                "lcd-response-fall-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:lcd-response-fall", fall as f64);
+            }
+            let packed = res.controllers.lcd_substrate.value as u32;
+            let mut color = [((packed >> 16) & 0xff) as u8, ((packed >> 8) & 0xff) as u8, (packed & 0xff) as u8];
+            if color_row(ui, "LCD substrate", Accent::Lilac, &mut color) {
+                let value = ((color[0] as u32) << 16) | ((color[1] as u32) << 8) | color[2] as u32;
+                set(commands, "front2back:lcd-substrate", value as f64);
+            }
+            self.selector(
+                ui,
+                "Reflective panel",
+                None,
+                Accent::Lilac,
+                &res.controllers.reflective_panel.value.to_string(),
+                "reflective-panel-dec",
+                "reflective-panel-inc",
+                commands,
+            );
+            let lit = !matches!(res.controllers.reflective_panel.value, ReflectivePanelOptions::Off);
+            let epaper = matches!(res.controllers.reflective_panel.value, ReflectivePanelOptions::EPaper);
+            let mut ambient_light = res.controllers.ambient_light.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Ambient light",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut ambient_light,
+                0.0..=2.0,
+                0.05,
+                "ambient-light-dec",
+                "ambient-light-inc",
+                lit,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:ambient-light", ambient_light as f64);
+            }
+            let mut ambient_elevation = res.controllers.ambient_elevation.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Light elevation (°)",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut ambient_elevation,
+                0.0..=90.0,
+                1.0,
+                "ambient-elevation-dec",
+                "ambient-elevation-inc",
+                lit,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:ambient-elevation", ambient_elevation as f64);
+            }
+            let mut ambient_azimuth = res.controllers.ambient_azimuth.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Light azimuth (°)",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut ambient_azimuth,
+                -180.0..=180.0,
+                1.0,
+                "ambient-azimuth-dec",
+                "ambient-azimuth-inc",
+                lit,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:ambient-azimuth", ambient_azimuth as f64);
+            }
+            let mut epaper_gray_levels = res.controllers.epaper_gray_levels.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "E-paper gray levels",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut epaper_gray_levels,
+                2.0..=16.0,
+                1.0,
+                "epaper-gray-levels-dec",
+                "epaper-gray-levels-inc",
+                epaper,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:epaper-gray-levels", epaper_gray_levels as f64);
+            }
+            let mut epaper_ghosting = res.controllers.epaper_ghosting.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "E-paper ghosting",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut epaper_ghosting,
+                0.0..=0.5,
+                0.01,
+                "epaper-ghosting-dec",
+                "epaper-ghosting-inc",
+                epaper,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:epaper-ghosting", epaper_ghosting as f64);
+            }
+            let mut tube_uniformity = res.controllers.tube_uniformity.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Tube uniformity",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut tube_uniformity,
+                0.0..=1.0,
+                0.05,
+                "tube-uniformity-dec",
+                "tube-uniformity-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:tube-uniformity", tube_uniformity as f64);
+            }
+            let mut phosphor_wear = res.controllers.phosphor_wear.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Phosphor wear",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut phosphor_wear,
+                0.0..=1.0,
+                0.05,
+                "phosphor-wear-dec",
+                "phosphor-wear-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:phosphor-wear", phosphor_wear as f64);
+            }
+            let mut color_drift = res.controllers.color_drift.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Color drift",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut color_drift,
+                0.0..=1.0,
+                0.05,
+                "color-drift-dec",
+                "color-drift-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:color-drift", color_drift as f64);
+            }
+            let mut burn_in_speed = res.controllers.burn_in_speed.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Burn-in speed (hours/s)",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut burn_in_speed,
+                0.0..=10000.0,
+                100.0,
+                "burn-in-speed-dec",
+                "burn-in-speed-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:burn-in-speed", burn_in_speed as f64);
+            }
+            let vector = res.video.vector.is_some();
+            let mut vector_beam_width = res.controllers.vector_beam_width.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Vector beam width",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut vector_beam_width,
+                0.25..=8.0,
+                0.05,
+                "vector-beam-width-dec",
+                "vector-beam-width-inc",
+                vector,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:vector-beam-width", vector_beam_width as f64);
+            }
+            let mut vector_hot_spot = res.controllers.vector_hot_spot.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Vector hot spots",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut vector_hot_spot,
+                0.0..=4.0,
+                0.1,
+                "vector-hot-spot-dec",
+                "vector-hot-spot-inc",
+                vector,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:vector-hot-spot", vector_hot_spot as f64);
+            }
+            let mut vector_persistence = res.controllers.vector_persistence.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Vector persistence (ms)",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut vector_persistence,
+                0.0..=1000.0,
+                5.0,
+                "vector-persistence-dec",
+                "vector-persistence-inc",
+                vector,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:vector-persistence", vector_persistence as f64);
+            }
+            let mut vector_bloom = res.controllers.vector_bloom.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Vector bloom",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut vector_bloom,
+                0.0..=2.0,
+                0.05,
+                "vector-bloom-dec",
+                "vector-bloom-inc",
+                vector,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:vector-bloom", vector_bloom as f64);
+            }
+            self.selector(
+                ui,
+                "Color vision",
+                None,
+                Accent::Green,
+                &res.controllers.color_vision.value.to_string(),
+                "color-vision-dec",
+                "color-vision-inc",
+                commands,
+            );
+            let mut severity = res.controllers.color_vision_severity.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Color vision severity",
+                ("????", "Shift + ????"),
+                Accent::Green,
+                &mut severity,
+                0.0..=1.0,
+                0.1,
+                "color-vision-severity-dec",
+                "color-vision-severity-inc",
+                !matches!(res.controllers.color_vision.value, ColorVisionOptions::Normal),
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:color-vision-severity", severity as f64);
+            }
+        }
+
+        if section_header(ui, "Video Signal", &mut self.sections.signal) {
+            self.selector(
+                ui,
+                "Source color depth",
+                None,
+                Accent::Red,
+                &res.controllers.source_depth.value.to_string(),
+                "source-depth-dec",
+                "source-depth-inc",
+                commands,
+            );
+            if !matches!(res.controllers.source_depth.value, SourceDepthOptions::Off) {
+                self.selector(
+                    ui,
+                    "Source dithering",
+                    None,
+                    Accent::Red,
+                    &res.controllers.source_dither.value.to_string(),
+                    "source-dither-dec",
+                    "source-dither-inc",
+                    commands,
+                );
+            }
+            self.selector(
+                ui,
+                "Dither blending",
+                None,
+                Accent::Red,
+                &res.controllers.dither_blend.value.to_string(),
+                "dither-blend-dec",
+                "dither-blend-inc",
+                commands,
+            );
+            let dither_blending = !matches!(res.controllers.dither_blend.value, DitherBlendOptions::Off);
+            let mut tolerance = res.controllers.dither_tolerance.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Dither tolerance",
+                ("????", "Shift + ????"),
+                Accent::Red,
+                &mut tolerance,
+                0.0..=0.5,
+                0.01,
+                "dither-tolerance-dec",
+                "dither-tolerance-inc",
+                dither_blending,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:dither-tolerance", tolerance as f64);
+            }
+            let mut contrast = res.controllers.dither_contrast.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Dither min. contrast",
+                ("????", "Shift + ????"),
+                Accent::Red,
+                &mut contrast,
+                0.0..=1.0,
+                0.01,
+                "dither-contrast-dec",
+                "dither-contrast-inc",
+                dither_blending,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:dither-contrast", contrast as f64);
+            }
+            self.selector(
+                ui,
+                "Signal type",
+                None,
+                Accent::Blue,
+                &res.controllers.signal_type.value.to_string(),
+                "signal-type-dec",
+                "signal-type-inc",
+                commands,
+            );
+            self.selector(
+                ui,
+                "Color system",
+                None,
+                Accent::Blue,
+                &res.controllers.signal_standard.value.to_string(),
+                "signal-standard-dec",
+                "signal-standard-inc",
+                commands,
+            );
+            let mut crosstalk = res.controllers.signal_crosstalk.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Subcarrier crosstalk",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut crosstalk,
+                0.0..=1.0,
+                0.01,
+                "signal-crosstalk-dec",
+                "signal-crosstalk-inc",
+                !matches!(res.controllers.signal_type.value, SignalTypeOptions::Rgb | SignalTypeOptions::SVideo),
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:signal-crosstalk", crosstalk as f64);
+            }
+            self.selector(
+                ui,
+                "Tape playback",
+                None,
+                Accent::Blue,
+                &res.controllers.tape_speed.value.to_string(),
+                "tape-speed-dec",
+                "tape-speed-inc",
+                commands,
+            );
+            let tape_enabled = !matches!(res.controllers.tape_speed.value, TapeSpeedOptions::Off);
+            let mut noise = res.controllers.tape_noise.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Tape noise",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut noise,
+                0.0..=1.0,
+                0.01,
+                "tape-noise-dec",
+                "tape-noise-inc",
+                tape_enabled,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:tape-noise", noise as f64);
+            }
+            let mut tracking = res.controllers.tape_tracking.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Tracking error",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut tracking,
+                0.0..=1.0,
+                0.01,
+                "tape-tracking-dec",
+                "tape-tracking-inc",
+                tape_enabled,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:tape-tracking", tracking as f64);
+            }
+            let mut dropouts = res.controllers.tape_dropouts.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Dropouts",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut dropouts,
+                0.0..=1.0,
+                0.01,
+                "tape-dropouts-dec",
+                "tape-dropouts-inc",
+                tape_enabled,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:tape-dropouts", dropouts as f64);
+            }
+            let mut snow = res.controllers.rf_snow.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "RF snow",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut snow,
+                0.0..=1.0,
+                0.01,
+                "rf-snow-dec",
+                "rf-snow-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:rf-snow", snow as f64);
+            }
+            let mut hum_bars = res.controllers.hum_bar_strength.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Hum bars",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut hum_bars,
+                0.0..=1.0,
+                0.01,
+                "hum-bar-strength-dec",
+                "hum-bar-strength-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:hum-bar-strength", hum_bars as f64);
+            }
+            let mut hum_frequency = res.controllers.hum_bar_frequency.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Hum beat (Hz)",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut hum_frequency,
+                0.0..=5.0,
+                0.05,
+                "hum-bar-frequency-dec",
+                "hum-bar-frequency-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:hum-bar-frequency", hum_frequency as f64);
+            }
+            let mut ghost_strength = res.controllers.ghost_strength.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Ghost strength",
                 ("????", "Shift + ????"),
-                Accent::Lilac,
-                &mut gamma,
+                Accent::Blue,
+                &mut ghost_strength,
                 0.0..=1.0,
-                0.1,
-                "color-gamma-dec",
-                "color-gamma-inc",
+                0.01,
+                "ghost-strength-dec",
+                "ghost-strength-inc",
                 true,
                 commands,
             );
             if changed {
-                set(commands, "front2back:color-gamma", gamma as f64);
+                set(commands, "front2back:ghost-strength", ghost_strength as f64);
             }
-            let mut noise = res.controllers.color_noise.value;
+            let mut ghost_offset = res.controllers.ghost_offset.value;
             let (_, changed) = self.number_f32(
                 ui,
-                "Color noise",
+                "Ghost offset",
                 ("????", "Shift + ????"),
-                Accent::Yellow,
-                &mut noise,
+                Accent::Blue,
+                &mut ghost_offset,
+                0.0..=32.0,
+                0.5,
+                "ghost-offset-dec",
+                "ghost-offset-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:ghost-offset", ghost_offset as f64);
+            }
+            let mut roll = res.controllers.vertical_roll.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Vertical roll",
+                ("????", "Shift + ????"),
+                Accent::Blue,
+                &mut roll,
                 0.0..=1.0,
-                0.1,
-                "color-noise-dec",
-                "color-noise-inc",
+                0.01,
+                "vertical-roll-dec",
+                "vertical-roll-inc",
                 true,
                 commands,
             );
             if changed {
-                set(commands, "front2back:color-noise", noise as f64);
+                set(commands, "front2back:vertical-roll", roll as f64);
             }
-            let packed = res.controllers.light_color.value as u32;
-            let mut color = [((packed >> 16) & 0xff) as u8, ((packed >> 8) & 0xff) as u8, (packed & 0xff) as u8];
-            if color_row(ui, "Source light color", Accent::Blue, &mut color) {
-                let value = ((color[0] as u32) << 16) | ((color[1] as u32) << 8) | color[2] as u32;
-                set(commands, "front2back:light-color", value as f64);
+            // The knobs of the set, applied to the decoded signal.
+            let mut black_level = res.controllers.tv_black_level.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Black level",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut black_level,
+                -0.5..=0.5,
+                0.01,
+                "tv-black-level-dec",
+                "tv-black-level-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:tv-black-level", black_level as f64);
             }
-            let mut bright = res.controllers.extra_bright.value;
+            let mut picture = res.controllers.tv_picture.value;
             let (_, changed) = self.number_f32(
                 ui,
-                "Brightness",
-                ("X", "Shift + X"),
-                Accent::White,
-                &mut bright,
-                -1.0..=1.0,
-                0.001,
-                "pixel-brightness-dec",
-                "pixel-brightness-inc",
+                "Picture",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut picture,
+                0.0..=2.0,
+                0.01,
+                "tv-picture-dec",
+                "tv-picture-inc",
                 true,
                 commands,
             );
             if changed {
-                set(commands, "front2back:pixel-brightness", bright as f64);
+                set(commands, "front2back:tv-picture", picture as f64);
             }
-            let mut contrast = res.controllers.extra_contrast.value;
+            let mut color = res.controllers.tv_color.value;
             let (_, changed) = self.number_f32(
                 ui,
-                "Contrast",
-                ("Z", "Shift + Z"),
-                Accent::White,
-                &mut contrast,
-                0.0..=20.0,
-                0.001,
-                "pixel-contrast-dec",
-                "pixel-contrast-inc",
+                "Color",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut color,
+                0.0..=2.0,
+                0.01,
+                "tv-color-dec",
+                "tv-color-inc",
                 true,
                 commands,
             );
             if changed {
-                set(commands, "front2back:pixel-contrast", contrast as f64);
+                set(commands, "front2back:tv-color", color as f64);
+            }
+            let mut tint = res.controllers.tv_tint.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Tint (degrees)",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut tint,
+                -45.0..=45.0,
+                1.0,
+                "tv-tint-dec",
+                "tv-tint-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:tv-tint", tint as f64);
+            }
+            let mut sharpness = res.controllers.tv_sharpness.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Sharpness",
+                ("????", "Shift + ????"),
+                Accent::Yellow,
+                &mut sharpness,
+                0.0..=1.0,
+                0.01,
+                "tv-sharpness-dec",
+                "tv-sharpness-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:tv-sharpness", sharpness as f64);
             }
         }
 
@@ -677,6 +1573,178 @@ impl SimPanel {
                 "screen-curvature-inc",
                 commands,
             );
+            let spherical = matches!(res.controllers.screen_curvature_kind.value, ScreenCurvatureKindOptions::Spherical);
+            let curved = spherical || matches!(res.controllers.screen_curvature_kind.value, ScreenCurvatureKindOptions::Cylindrical);
+            let mut curvature_horizontal = res.controllers.curvature_horizontal.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Horizontal curvature",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut curvature_horizontal,
+                0.0..=1.0,
+                0.01,
+                "curvature-horizontal-dec",
+                "curvature-horizontal-inc",
+                curved,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:curvature-horizontal", curvature_horizontal as f64);
+            }
+            let mut curvature_vertical = res.controllers.curvature_vertical.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Vertical curvature",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut curvature_vertical,
+                0.0..=1.0,
+                0.01,
+                "curvature-vertical-dec",
+                "curvature-vertical-inc",
+                spherical,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:curvature-vertical", curvature_vertical as f64);
+            }
+            let mut barrel = res.controllers.barrel_distortion.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Barrel / pincushion",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut barrel,
+                -1.0..=1.0,
+                0.01,
+                "barrel-distortion-dec",
+                "barrel-distortion-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:barrel-distortion", barrel as f64);
+            }
+            let mut keystone = res.controllers.screen_keystone.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Keystone",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut keystone,
+                -1.0..=1.0,
+                0.01,
+                "screen-keystone-dec",
+                "screen-keystone-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:screen-keystone", keystone as f64);
+            }
+            let mut trapezoid = res.controllers.screen_trapezoid.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Trapezoid",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut trapezoid,
+                -1.0..=1.0,
+                0.01,
+                "screen-trapezoid-dec",
+                "screen-trapezoid-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:screen-trapezoid", trapezoid as f64);
+            }
+            let mut tilt = res.controllers.screen_tilt.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Tilt (degrees)",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut tilt,
+                -10.0..=10.0,
+                0.1,
+                "screen-tilt-dec",
+                "screen-tilt-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:screen-tilt", tilt as f64);
+            }
+            let mut corner_radius = res.controllers.screen_corner_radius.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Corner radius",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut corner_radius,
+                0.0..=1.0,
+                0.01,
+                "screen-corner-radius-dec",
+                "screen-corner-radius-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:screen-corner-radius", corner_radius as f64);
+            }
+            let mut edge_softness = res.controllers.screen_edge_softness.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Edge softness",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut edge_softness,
+                0.0..=1.0,
+                0.01,
+                "screen-edge-softness-dec",
+                "screen-edge-softness-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:screen-edge-softness", edge_softness as f64);
+            }
+            let mut vignette_strength = res.controllers.vignette_strength.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Vignette strength",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut vignette_strength,
+                0.0..=1.0,
+                0.01,
+                "vignette-strength-dec",
+                "vignette-strength-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:vignette-strength", vignette_strength as f64);
+            }
+            let mut vignette_shape = res.controllers.vignette_shape.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Vignette shape",
+                ("????", "Shift + ????"),
+                Accent::White,
+                &mut vignette_shape,
+                0.0..=1.0,
+                0.01,
+                "vignette-shape-dec",
+                "vignette-shape-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:vignette-shape", vignette_shape as f64);
+            }
             let mut horizontal_gap = res.controllers.cur_pixel_horizontal_gap.value;
             let (_, changed) = self.number_f32(
                 ui,
@@ -745,6 +1813,51 @@ impl SimPanel {
             if changed {
                 set(commands, "front2back:horizontal-lpp", horizontal_lpp as f64);
             }
+            self.selector(
+                ui,
+                "Scan mode",
+                None,
+                Accent::Lilac,
+                &res.controllers.scan_mode.value.to_string(),
+                "scan-mode-dec",
+                "scan-mode-inc",
+                commands,
+            );
+            let interlaced = matches!(res.controllers.scan_mode.value, ScanModeOptions::Interlaced);
+            let mut flicker = res.controllers.interlace_flicker.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Interlace flicker",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut flicker,
+                0.0..=1.0,
+                0.01,
+                "interlace-flicker-dec",
+                "interlace-flicker-inc",
+                interlaced,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:interlace-flicker", flicker as f64);
+            }
+            let mut combing = res.controllers.interlace_combing.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Interlace combing",
+                ("????", "Shift + ????"),
+                Accent::Lilac,
+                &mut combing,
+                0.0..=1.0,
+                0.01,
+                "interlace-combing-dec",
+                "interlace-combing-inc",
+                interlaced,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:interlace-combing", combing as f64);
+            }
             self.selector(
                 ui,
                 "Color channels type",
@@ -755,6 +1868,91 @@ impl SimPanel {
                 "color-representation-inc",
                 commands,
             );
+            let mut red_x = res.controllers.convergence_red_x.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Convergence red X",
+                ("????", "Shift + ????"),
+                Accent::Red,
+                &mut red_x,
+                -2.0..=2.0,
+                0.01,
+                "convergence-red-x-dec",
+                "convergence-red-x-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:convergence-red-x", red_x as f64);
+            }
+            let mut red_y = res.controllers.convergence_red_y.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Convergence red Y",
+                ("????", "Shift + ????"),
+                Accent::Red,
+                &mut red_y,
+                -2.0..=2.0,
+                0.01,
+                "convergence-red-y-dec",
+                "convergence-red-y-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:convergence-red-y", red_y as f64);
+            }
+            let mut blue_x = res.controllers.convergence_blue_x.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Convergence blue X",
+                ("????", "Shift + ????"),
+                Accent::Red,
+                &mut blue_x,
+                -2.0..=2.0,
+                0.01,
+                "convergence-blue-x-dec",
+                "convergence-blue-x-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:convergence-blue-x", blue_x as f64);
+            }
+            let mut blue_y = res.controllers.convergence_blue_y.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Convergence blue Y",
+                ("????", "Shift + ????"),
+                Accent::Red,
+                &mut blue_y,
+                -2.0..=2.0,
+                0.01,
+                "convergence-blue-y-dec",
+                "convergence-blue-y-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:convergence-blue-y", blue_y as f64);
+            }
+            let mut radial = res.controllers.convergence_radial.value;
+            let (_, changed) = self.number_f32(
+                ui,
+                "Corner misconvergence",
+                ("????", "Shift + ????"),
+                Accent::Red,
+                &mut radial,
+                0.0..=2.0,
+                0.01,
+                "convergence-radial-dec",
+                "convergence-radial-inc",
+                true,
+                commands,
+            );
+            if changed {
+                set(commands, "front2back:convergence-radial", radial as f64);
+            }
             self.selector(
                 ui,
                 "Pixel geometry type",
@@ -890,8 +2088,16 @@ impl SimPanel {
             static_selector_row(ui, "Antialias", Accent::Red, "host-managed");
         }
 
-        if section_header(ui, "Extra", &mut self.sections.extra) && action_row(ui, "Take Screenshot", Accent::Yellow).clicked() {
-            self.synthetic.pulse("capture-framebuffer", commands);
+        if section_header(ui, "Extra", &mut self.sections.extra) {
+            if action_row(ui, "Power On/Off", Accent::Yellow).clicked() {
+                self.synthetic.pulse("power-toggle", commands);
+            }
+            if action_row(ui, "Degauss", Accent::Yellow).clicked() {
+                self.synthetic.pulse("degauss", commands);
+            }
+            if action_row(ui, "Take Screenshot", Accent::Yellow).clicked() {
+                self.synthetic.pulse("capture-framebuffer", commands);
+            }
         }
 
         if exit_row(ui, "Exit Simulation").clicked() {
@@ -900,11 +2106,13 @@ impl SimPanel {
     }
 
     fn preset_grid(&mut self, ui: &mut Ui, res: &Resources, commands: &mut Vec<SimulationCommand>) {
-        let (full_rect, _) = ui.allocate_exact_size(Vec2::new(PANEL_WIDTH, 151.0), Sense::hover());
-        let rect = Rect::from_min_size(full_rect.min + Vec2::new(CATEGORY_INSET, 0.0), Vec2::new(CATEGORY_WIDTH, 151.0));
+        let rows = FilterPresetOptions::ALL.len().div_ceil(2);
+        let height = rows as f32 * 50.0;
+        let (full_rect, _) = ui.allocate_exact_size(Vec2::new(PANEL_WIDTH, height + 1.0), Sense::hover());
+        let rect = Rect::from_min_size(full_rect.min + Vec2::new(CATEGORY_INSET, 0.0), Vec2::new(CATEGORY_WIDTH, height + 1.0));
         let painter = ui.painter().clone();
         painter.rect_filled(rect, CornerRadius::ZERO, web_color(26, 26, 26));
-        painter.rect_filled(Rect::from_min_size(rect.min, Vec2::new(3.0, 150.0)), CornerRadius::ZERO, Accent::Grey.color());
+        painter.rect_filled(Rect::from_min_size(rect.min, Vec2::new(3.0, height)), CornerRadius::ZERO, Accent::Grey.color());
         painter.line_segment(
             [Pos2::new(rect.left(), rect.bottom() - 0.5), Pos2::new(rect.right(), rect.bottom() - 0.5)],
             Stroke::new(1.0, web_color(44, 44, 44)),
@@ -2091,7 +3299,7 @@ mod tests {
                 .run(raw_input(time, Vec::new()), &resources, &mut commands, &sink)
                 .drop_without_applying_deltas();
         }
-        let pointer = Pos2::new(125.0, 153.0);
+        let pointer = Pos2::new(321.5, 253.0);
         panel
             .run(
                 raw_input(
@@ -2471,7 +3679,7 @@ mod tests {
         assert!(has_shapes);
         assert_eq!(rect.left(), PANEL_X);
         assert_eq!(rect.width(), PANEL_WIDTH);
-        assert_eq!(rect.height(), 507.0);
+        assert_eq!(rect.height(), 628.0);
     }
 
     #[test]
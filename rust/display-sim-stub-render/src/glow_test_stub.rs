@@ -53,6 +53,7 @@ pub fn new_glow_stub() -> GlowSafeAdapter<Context> {
 impl<GL: HasContext> GlowSafeAdapter<GL> {
     pub fn enable(&self, _: u32) {}
     pub fn disable(&self, _: u32) {}
+    pub fn blend_func(&self, _: u32, _: u32) {}
     pub fn enable_vertex_attrib_array(&self, _: u32) {}
     pub fn create_framebuffer(&self) -> Result<GL::Framebuffer, String> {
         Ok(Default::default())
@@ -117,6 +118,7 @@ impl<GL: HasContext> GlowSafeAdapter<GL> {
     pub fn delete_framebuffer(&self, _: GL::Framebuffer) {}
     pub fn delete_renderbuffer(&self, _: GL::Renderbuffer) {}
     pub fn delete_texture(&self, _: GL::Texture) {}
+    pub fn draw_arrays(&self, _: u32, _: i32, _: i32) {}
     pub fn draw_arrays_instanced(&self, _: u32, _: i32, _: i32, _: i32) {}
     pub fn draw_elements(&self, _: u32, _: i32, _: u32, _: i32) {}
     pub fn flush(&self) {}
@@ -146,6 +148,7 @@ impl<GL: HasContext> GlowSafeAdapter<GL> {
     pub fn uniform_1_f32(&self, _: Option<GL::UniformLocation>, _: f32) {}
     pub fn uniform_2_f32_slice(&self, _: Option<GL::UniformLocation>, _: &[f32; 2]) {}
     pub fn uniform_3_f32_slice(&self, _: Option<GL::UniformLocation>, _: &[f32; 3]) {}
+    pub fn uniform_4_f32_slice(&self, _: Option<GL::UniformLocation>, _: &[f32; 4]) {}
     pub fn uniform_matrix_4_f32_slice(&self, _: Option<GL::UniformLocation>, _: bool, _: &[f32; 16]) {}
     pub fn finish(&self) {}
     pub fn bind_texture(&self, _: u32, _: Option<GL::Texture>) {}
@@ -20,8 +20,11 @@ use core::simulation_command::SimulationCommandBus;
 use core::simulation_context::{ConcreteSimulationContext, FakeRngGenerator};
 use core::simulation_core_state::{AnimationStep, Resources, VideoInputResources};
 use core::simulation_core_ticker::SimulationCoreTicker;
+use render::artwork_render::ArtworkRender;
 use render::background_render::BackgroundRender;
 use render::blur_render::BlurRender;
+use render::cabinet_render::CabinetRender;
+use render::color_vision_render::ColorVisionRender;
 use render::error::AppResult;
 use render::internal_resolution_render::InternalResolutionRender;
 use render::pixels_render::PixelsRender;
@@ -29,6 +32,7 @@ use render::render_types::TextureBufferStack;
 use render::rgb_render::RgbRender;
 use render::simulation_draw::SimulationDrawer;
 use render::simulation_render_state::{Materials, VideoInputMaterials};
+use render::vector_render::VectorRender;
 
 use render::glow_test_stub::new_glow_stub;
 use std::rc::Rc;
@@ -54,10 +58,18 @@ impl Default for FakeVideoInput {
                 last_frame_change: 0.0,
                 preset: None,
                 needs_buffer_data_load: true,
+                needs_panel_settle: false,
                 drawing_activation: true,
+                artwork: None,
+                cabinet: None,
+                color_lut: None,
+                color_lut_generation: 0,
+                burn_in: None,
+                vector: None,
             },
             VideoInputMaterials {
                 buffers: vec![Box::new([0; 256 * 224 * 4 * 4])],
+                artwork: Vec::new(),
             },
         )
     }
@@ -71,11 +83,16 @@ impl FakeVideoInput {
         let mut materials = Materials {
             main_buffer_stack: TextureBufferStack::new(gl.clone()),
             bg_buffer_stack: TextureBufferStack::new(gl.clone()),
+            cabinet_buffer_stack: TextureBufferStack::new(gl.clone()),
             pixels_render: PixelsRender::new(gl.clone(), self.1)?,
             blur_render: BlurRender::new(gl.clone())?,
             internal_resolution_render: InternalResolutionRender::new(gl.clone())?,
             rgb_render: RgbRender::new(gl.clone())?,
             background_render: BackgroundRender::new(gl.clone())?,
+            artwork_render: ArtworkRender::new(gl.clone(), &[])?,
+            cabinet_render: CabinetRender::new(gl.clone())?,
+            color_vision_render: ColorVisionRender::new(gl.clone())?,
+            vector_render: VectorRender::new(gl.clone())?,
             screenshot_pixels: None,
             gl,
         };
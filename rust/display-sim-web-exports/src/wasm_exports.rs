@@ -20,14 +20,19 @@ use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
 
 use crate::console;
 use crate::web_entrypoint::{
-    print_error, web_load, web_run_frame, web_set_panel_enabled, web_set_ui_metrics, web_ui_captures_pointer, web_ui_event, web_ui_message,
+    print_error, web_burn_in_map, web_load, web_run_frame, web_set_panel_enabled, web_set_ui_metrics, web_ui_captures_pointer, web_ui_event, web_ui_message,
     web_ui_wants_keyboard, web_unload, InputOutput,
 };
 use app_util::AppResult;
+use core::artwork_layout::ArtworkLayout;
+use core::cabinet_model::CabinetModel;
+use core::color_lut::ColorLut;
 use core::general_types::Size2D;
 use core::simulation_core_state::{AnimationStep, Resources, VideoInputResources};
+use core::tube_aging::BurnInMap;
 use core::ui_controller::filter_preset::FilterPresetOptions;
-use render::simulation_render_state::VideoInputMaterials;
+use core::vector_display::VectorScene;
+use render::simulation_render_state::{ArtworkImage, VideoInputMaterials};
 use std::str::FromStr;
 
 #[wasm_bindgen]
@@ -122,6 +127,13 @@ impl WasmApp {
         }
     }
 
+    /// The wear of the tube so far as RGBA pixels at the size of the source,
+    /// the same layout `VideoInputConfig::set_burn_in_map` takes back.
+    #[wasm_bindgen]
+    pub fn burn_in_map(&self) -> Option<Uint8Array> {
+        self.io.as_ref().and_then(web_burn_in_map).map(|pixels| Uint8Array::from(&pixels[..]))
+    }
+
     #[wasm_bindgen]
     pub fn unload(&mut self) {
         if let Some(io) = self.io.take() {
@@ -166,7 +178,14 @@ impl VideoInputConfig {
                 current_frame: 0,
                 last_frame_change: -1000.0,
                 needs_buffer_data_load: true,
+                needs_panel_settle: false,
                 drawing_activation: true,
+                artwork: None,
+                cabinet: None,
+                color_lut: None,
+                color_lut_generation: 0,
+                burn_in: None,
+                vector: None,
             },
             materials: VideoInputMaterials::default(),
         }
@@ -186,6 +205,95 @@ impl VideoInputConfig {
         self.materials.buffers.push(pixels);
     }
 
+    /// Artwork images have to be added afterwards, in the order of the layout layers.
+    #[wasm_bindgen]
+    pub fn set_artwork_layout(&mut self, layout: String) -> bool {
+        match ArtworkLayout::parse(&layout) {
+            Ok(layout) => {
+                self.resources.artwork = Some(layout);
+                true
+            }
+            Err(e) => {
+                console!(error. "Input artwork layout is not valid.", e);
+                false
+            }
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn add_artwork_image(&mut self, buffer: Uint8Array, width: u32, height: u32) {
+        let mut pixels = vec![0; (width * height * 4) as usize].into_boxed_slice();
+        buffer.copy_to(&mut *pixels);
+        self.materials.artwork.push(ArtworkImage { width, height, pixels });
+    }
+
+    /// Takes the text of a Wavefront OBJ model.
+    #[wasm_bindgen]
+    pub fn set_cabinet_model(&mut self, model: String) -> bool {
+        match CabinetModel::parse_obj(&model) {
+            Ok(model) => {
+                self.resources.cabinet = Some(model);
+                true
+            }
+            Err(e) => {
+                console!(error. "Input cabinet model is not valid.", e);
+                false
+            }
+        }
+    }
+
+    /// Takes the text of a `.cube` 3D LUT.
+    #[wasm_bindgen]
+    pub fn set_color_lut(&mut self, lut: String) -> bool {
+        match ColorLut::parse(&lut) {
+            Ok(lut) => {
+                self.resources.set_color_lut(Some(lut));
+                true
+            }
+            Err(e) => {
+                console!(error. "Input LUT is not valid.", e);
+                false
+            }
+        }
+    }
+
+    /// Drops the LUT set before, if any.
+    #[wasm_bindgen]
+    pub fn clear_color_lut(&mut self) {
+        self.resources.set_color_lut(None);
+    }
+
+    /// Takes a vector scene, which replaces the picture frames and sets the image size.
+    #[wasm_bindgen]
+    pub fn set_vector_scene(&mut self, buffer: Uint8Array) -> bool {
+        match VectorScene::parse(&buffer.to_vec()) {
+            Ok(scene) => {
+                let size = Size2D {
+                    width: scene.width,
+                    height: scene.height,
+                };
+                self.resources.image_size = size;
+                self.resources.background_size = size;
+                self.resources.steps = scene.animation_steps();
+                self.resources.vector = Some(scene);
+                self.materials.buffers.clear();
+                true
+            }
+            Err(e) => {
+                console!(error. "Input vector scene is not valid.", e);
+                false
+            }
+        }
+    }
+
+    /// Takes the pixels of a burn-in map, brighter where the tube is more worn.
+    #[wasm_bindgen]
+    pub fn set_burn_in_map(&mut self, buffer: Uint8Array, width: u32, height: u32) {
+        let mut pixels = vec![0; (width * height * 4) as usize];
+        buffer.copy_to(&mut pixels);
+        self.resources.burn_in = Some(BurnInMap::from_rgba8(width, height, &pixels));
+    }
+
     #[wasm_bindgen]
     pub fn set_preset(&mut self, preset: JsValue) {
         match preset.as_string() {
@@ -215,6 +215,10 @@ pub(crate) fn web_ui_wants_keyboard(io: &InputOutput) -> bool {
     io.panel_enabled && io.panel.context().egui_wants_keyboard_input()
 }
 
+pub(crate) fn web_burn_in_map(io: &InputOutput) -> Option<Vec<u8>> {
+    io.materials.pixels_render.burn_in().map(|burn_in| burn_in.to_rgba8())
+}
+
 pub(crate) fn web_ui_message(io: &mut InputOutput, message: &str) {
     io.panel_events.borrow_mut().push_message(message);
 }
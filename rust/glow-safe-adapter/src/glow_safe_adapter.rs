@@ -41,6 +41,10 @@ impl<GL: HasContext> GlowSafeAdapter<GL> {
         unsafe { self.gl.disable(parameter) }
     }
 
+    pub fn blend_func(&self, src: u32, dst: u32) {
+        unsafe { self.gl.blend_func(src, dst) }
+    }
+
     pub fn enable_vertex_attrib_array(&self, index: Option<u32>) {
         unsafe { self.gl.enable_vertex_attrib_array(index.unwrap()) }
     }
@@ -193,6 +197,10 @@ impl<GL: HasContext> GlowSafeAdapter<GL> {
         unsafe { self.gl.delete_texture(texture) }
     }
 
+    pub fn draw_arrays(&self, mode: u32, first: i32, count: i32) {
+        unsafe { self.gl.draw_arrays(mode, first, count) }
+    }
+
     pub fn draw_arrays_instanced(&self, mode: u32, first: i32, count: i32, instance_count: i32) {
         unsafe { self.gl.draw_arrays_instanced(mode, first, count, instance_count) }
     }
@@ -296,6 +304,10 @@ impl<GL: HasContext> GlowSafeAdapter<GL> {
         unsafe { self.gl.uniform_3_f32_slice(location.as_ref(), v) }
     }
 
+    pub fn uniform_4_f32_slice(&self, location: Option<GL::UniformLocation>, v: &[f32; 4]) {
+        unsafe { self.gl.uniform_4_f32_slice(location.as_ref(), v) }
+    }
+
     pub fn uniform_matrix_4_f32_slice(&self, location: Option<GL::UniformLocation>, transpose: bool, v: &[f32; 16]) {
         unsafe { self.gl.uniform_matrix_4_f32_slice(location.as_ref(), transpose, v) }
     }
@@ -176,9 +176,38 @@ async function show (template: SimTemplate, view_model: SimViewModel, model: Sim
         case 'back2front:change_pixel_brightness': return view_model.changePixelBrightness(msg);
         case 'back2front:change_pixel_contrast': return view_model.changePixelContrast(msg);
         case 'back2front:change_blur_level': return view_model.changeBlurLevel(msg);
+        case 'back2front:scan_output': return view_model.changeScanOutput(msg);
+        case 'back2front:display_refresh': return view_model.changeDisplayRefresh(msg);
+        case 'back2front:source_refresh': return view_model.changeSourceRefresh(msg);
         case 'back2front:change_vertical_lpp': return view_model.changeVerticalLpp(msg);
         case 'back2front:change_horizontal_lpp': return view_model.changeHorizontalLpp(msg);
+        case 'back2front:scan_mode': return view_model.changeScanMode(msg);
+        case 'back2front:interlace_flicker': return view_model.changeInterlaceFlicker(msg);
+        case 'back2front:interlace_combing': return view_model.changeInterlaceCombing(msg);
         case 'back2front:change_light_color': return view_model.changeLightColor(msg);
+        case 'back2front:change_lcd_substrate': return view_model.changeLcdSubstrate(msg);
+        case 'back2front:tube_uniformity': return view_model.changeTubeUniformity(msg);
+        case 'back2front:phosphor_wear': return view_model.changePhosphorWear(msg);
+        case 'back2front:color_drift': return view_model.changeColorDrift(msg);
+        case 'back2front:burn_in_speed': return view_model.changeBurnInSpeed(msg);
+        case 'back2front:vector_beam_width': return view_model.changeVectorBeamWidth(msg);
+        case 'back2front:vector_hot_spot': return view_model.changeVectorHotSpot(msg);
+        case 'back2front:vector_persistence': return view_model.changeVectorPersistence(msg);
+        case 'back2front:vector_bloom': return view_model.changeVectorBloom(msg);
+        case 'back2front:color_vision': return view_model.changeColorVision(msg);
+        case 'back2front:color_vision_severity': return view_model.changeColorVisionSeverity(msg);
+        case 'back2front:monochrome_luma': return view_model.changeMonochromeLuma(msg);
+        case 'back2front:phosphor_type': return view_model.changePhosphorType(msg);
+        case 'back2front:phosphor_bloom': return view_model.changePhosphorBloom(msg);
+        case 'back2front:lcd_palette': return view_model.changeLcdPalette(msg);
+        case 'back2front:lcd_response_rise': return view_model.changeLcdResponseRise(msg);
+        case 'back2front:lcd_response_fall': return view_model.changeLcdResponseFall(msg);
+        case 'back2front:reflective_panel': return view_model.changeReflectivePanel(msg);
+        case 'back2front:ambient_light': return view_model.changeAmbientLight(msg);
+        case 'back2front:ambient_elevation': return view_model.changeAmbientElevation(msg);
+        case 'back2front:ambient_azimuth': return view_model.changeAmbientAzimuth(msg);
+        case 'back2front:epaper_gray_levels': return view_model.changeEpaperGrayLevels(msg);
+        case 'back2front:epaper_ghosting': return view_model.changeEpaperGhosting(msg);
         case 'back2front:change_brightness_color': return view_model.changeBrightnessColor(msg);
         case 'back2front:change_movement_speed': return view_model.changeMovementSpeed(msg);
         case 'back2front:change_pixel_speed': return view_model.changePixelSpeed(msg);
@@ -197,8 +226,56 @@ async function show (template: SimTemplate, view_model: SimViewModel, model: Sim
         case 'back2front:internal_resolution': return view_model.changeInternalResolution(msg);
         case 'back2front:texture_interpolation': return view_model.changeTextureInterpolation(msg);
         case 'back2front:screen_curvature': return view_model.changeScreenCurvature(msg);
+        case 'back2front:curvature_horizontal': return view_model.changeCurvatureHorizontal(msg);
+        case 'back2front:curvature_vertical': return view_model.changeCurvatureVertical(msg);
+        case 'back2front:barrel_distortion': return view_model.changeBarrelDistortion(msg);
+        case 'back2front:screen_keystone': return view_model.changeScreenKeystone(msg);
+        case 'back2front:screen_trapezoid': return view_model.changeScreenTrapezoid(msg);
+        case 'back2front:screen_tilt': return view_model.changeScreenTilt(msg);
+        case 'back2front:screen_corner_radius': return view_model.changeScreenCornerRadius(msg);
+        case 'back2front:screen_edge_softness': return view_model.changeScreenEdgeSoftness(msg);
+        case 'back2front:vignette_strength': return view_model.changeVignetteStrength(msg);
+        case 'back2front:vignette_shape': return view_model.changeVignetteShape(msg);
         case 'back2front:color_gamma': return view_model.changeColorGamma(msg);
+        case 'back2front:source_primaries': return view_model.changeSourcePrimaries(msg);
+        case 'back2front:display_primaries': return view_model.changeDisplayPrimaries(msg);
+        case 'back2front:white_point': return view_model.changeWhitePoint(msg);
+        case 'back2front:color_temperature': return view_model.changeColorTemperature(msg);
+        case 'back2front:lut_stage': return view_model.changeLutStage(msg);
+        case 'back2front:lut_interpolation': return view_model.changeLutInterpolation(msg);
+        case 'back2front:lut_strength': return view_model.changeLutStrength(msg);
+        case 'back2front:source_transfer': return view_model.changeSourceTransfer(msg);
+        case 'back2front:display_transfer': return view_model.changeDisplayTransfer(msg);
+        case 'back2front:transfer_power': return view_model.changeTransferPower(msg);
         case 'back2front:color_noise': return view_model.changeColorNoise(msg);
+        case 'back2front:source_depth': return view_model.changeSourceDepth(msg);
+        case 'back2front:source_dither': return view_model.changeSourceDither(msg);
+        case 'back2front:dither_blend': return view_model.changeDitherBlend(msg);
+        case 'back2front:dither_tolerance': return view_model.changeDitherTolerance(msg);
+        case 'back2front:dither_contrast': return view_model.changeDitherContrast(msg);
+        case 'back2front:signal_type': return view_model.changeSignalType(msg);
+        case 'back2front:signal_standard': return view_model.changeSignalStandard(msg);
+        case 'back2front:signal_crosstalk': return view_model.changeSignalCrosstalk(msg);
+        case 'back2front:tape_speed': return view_model.changeTapeSpeed(msg);
+        case 'back2front:tape_noise': return view_model.changeTapeNoise(msg);
+        case 'back2front:tape_tracking': return view_model.changeTapeTracking(msg);
+        case 'back2front:tape_dropouts': return view_model.changeTapeDropouts(msg);
+        case 'back2front:rf_snow': return view_model.changeRfSnow(msg);
+        case 'back2front:hum_bar_strength': return view_model.changeHumBarStrength(msg);
+        case 'back2front:hum_bar_frequency': return view_model.changeHumBarFrequency(msg);
+        case 'back2front:ghost_strength': return view_model.changeGhostStrength(msg);
+        case 'back2front:ghost_offset': return view_model.changeGhostOffset(msg);
+        case 'back2front:vertical_roll': return view_model.changeVerticalRoll(msg);
+        case 'back2front:tv_black_level': return view_model.changeTvBlackLevel(msg);
+        case 'back2front:tv_picture': return view_model.changeTvPicture(msg);
+        case 'back2front:tv_color': return view_model.changeTvColor(msg);
+        case 'back2front:tv_tint': return view_model.changeTvTint(msg);
+        case 'back2front:tv_sharpness': return view_model.changeTvSharpness(msg);
+        case 'back2front:convergence_red_x': return view_model.changeConvergenceRedX(msg);
+        case 'back2front:convergence_red_y': return view_model.changeConvergenceRedY(msg);
+        case 'back2front:convergence_blue_x': return view_model.changeConvergenceBlueX(msg);
+        case 'back2front:convergence_blue_y': return view_model.changeConvergenceBlueY(msg);
+        case 'back2front:convergence_radial': return view_model.changeConvergenceRadial(msg);
         case 'back2front:rgb_red_r': return view_model.changeColorRgb(msg, 'red', 'r');
         case 'back2front:rgb_red_g': return view_model.changeColorRgb(msg, 'red', 'g');
         case 'back2front:rgb_red_b': return view_model.changeColorRgb(msg, 'red', 'b');
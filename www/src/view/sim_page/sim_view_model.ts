@@ -120,21 +120,103 @@ export function data () {
                 { preset: 'crt-shadow-mask-1', text: 'CRT Shadow Mask 1' },
                 { preset: 'crt-shadow-mask-2', text: 'CRT Shadow Mask 2' },
                 { preset: 'sharp-1', text: 'CRT Sharp Pixels' },
+                { preset: 'lcd-dmg', text: 'LCD Game Boy' },
+                { preset: 'lcd-gba', text: 'LCD Game Boy Advance' },
+                { preset: 'lcd-game-gear', text: 'LCD Game Gear' },
+                { preset: 'vector-bw', text: 'Vector Black & White' },
+                { preset: 'vector-color', text: 'Vector Color' },
                 { preset: 'demo-1', text: 'Flight Demo' },
                 { preset: Constants.PRESET_KIND_CUSTOM, text: 'Custom' }
             ]
         },
         internal_resolution: { value: null as number | null, eventKind: 'internal-resolution' },
         screen_curvature: { value: null as number | null, eventKind: 'screen-curvature' },
+        curvature_horizontal: { value: 0.3, eventKind: 'curvature-horizontal' },
+        curvature_vertical: { value: 0.3, eventKind: 'curvature-vertical' },
+        barrel_distortion: { value: 0.0, eventKind: 'barrel-distortion' },
+        screen_keystone: { value: 0.0, eventKind: 'screen-keystone' },
+        screen_trapezoid: { value: 0.0, eventKind: 'screen-trapezoid' },
+        screen_tilt: { value: 0.0, eventKind: 'screen-tilt' },
+        screen_corner_radius: { value: 0.0, eventKind: 'screen-corner-radius' },
+        screen_edge_softness: { value: 0.0, eventKind: 'screen-edge-softness' },
+        vignette_strength: { value: 0.0, eventKind: 'vignette-strength' },
+        vignette_shape: { value: 0.0, eventKind: 'vignette-shape' },
         blur_level: { value: null as number | null, eventKind: 'blur-level' },
+        scan_output: { value: null as number | null, eventKind: 'scan-output' },
+        display_refresh: { value: 120, eventKind: 'display-refresh' },
+        source_refresh: { value: 60, eventKind: 'source-refresh' },
         horizontal_gap: { value: null as number | null, eventKind: 'pixel-horizontal-gap' },
         vertical_gap: { value: null as number | null, eventKind: 'pixel-vertical-gap' },
         pixel_width: { value: null as number | null, eventKind: 'pixel-width' },
         vertical_lpp: { value: null as number | null, eventKind: 'vertical-lpp' },
         horizontal_lpp: { value: null as number | null, eventKind: 'horizontal-lpp' },
+        scan_mode: { value: null as number | null, eventKind: 'scan-mode' },
+        interlace_flicker: { value: 0.5, eventKind: 'interlace-flicker' },
+        interlace_combing: { value: 0.0, eventKind: 'interlace-combing' },
         color_gamma: { value: 1.0, eventKind: 'color-gamma' },
+        source_primaries: { value: null as number | null, eventKind: 'source-primaries' },
+        display_primaries: { value: null as number | null, eventKind: 'display-primaries' },
+        white_point: { value: null as number | null, eventKind: 'white-point' },
+        color_temperature: { value: 6500, eventKind: 'color-temperature' },
+        lut_stage: { value: null as number | null, eventKind: 'lut-stage' },
+        lut_interpolation: { value: null as number | null, eventKind: 'lut-interpolation' },
+        lut_strength: { value: 1.0, eventKind: 'lut-strength' },
+        source_transfer: { value: null as number | null, eventKind: 'source-transfer' },
+        display_transfer: { value: null as number | null, eventKind: 'display-transfer' },
+        transfer_power: { value: 2.2, eventKind: 'transfer-power' },
         color_noise: { value: 0.0, eventKind: 'color-noise' },
+        source_depth: { value: null as number | null, eventKind: 'source-depth' },
+        source_dither: { value: null as number | null, eventKind: 'source-dither' },
+        dither_blend: { value: null as number | null, eventKind: 'dither-blend' },
+        dither_tolerance: { value: 0.05, eventKind: 'dither-tolerance' },
+        dither_contrast: { value: 0.1, eventKind: 'dither-contrast' },
+        signal_type: { value: null as number | null, eventKind: 'signal-type' },
+        signal_standard: { value: null as number | null, eventKind: 'signal-standard' },
+        signal_crosstalk: { value: 0.5, eventKind: 'signal-crosstalk' },
+        tape_speed: { value: null as number | null, eventKind: 'tape-speed' },
+        tape_noise: { value: 0.3, eventKind: 'tape-noise' },
+        tape_tracking: { value: 0.2, eventKind: 'tape-tracking' },
+        tape_dropouts: { value: 0.2, eventKind: 'tape-dropouts' },
+        rf_snow: { value: 0.0, eventKind: 'rf-snow' },
+        hum_bar_strength: { value: 0.0, eventKind: 'hum-bar-strength' },
+        hum_bar_frequency: { value: 0.5, eventKind: 'hum-bar-frequency' },
+        ghost_strength: { value: 0.0, eventKind: 'ghost-strength' },
+        ghost_offset: { value: 4.0, eventKind: 'ghost-offset' },
+        vertical_roll: { value: 0.0, eventKind: 'vertical-roll' },
+        tv_black_level: { value: 0.0, eventKind: 'tv-black-level' },
+        tv_picture: { value: 1.0, eventKind: 'tv-picture' },
+        tv_color: { value: 1.0, eventKind: 'tv-color' },
+        tv_tint: { value: 0.0, eventKind: 'tv-tint' },
+        tv_sharpness: { value: 0.0, eventKind: 'tv-sharpness' },
+        convergence_red_x: { value: 0.0, eventKind: 'convergence-red-x' },
+        convergence_red_y: { value: 0.0, eventKind: 'convergence-red-y' },
+        convergence_blue_x: { value: 0.0, eventKind: 'convergence-blue-x' },
+        convergence_blue_y: { value: 0.0, eventKind: 'convergence-blue-y' },
+        convergence_radial: { value: 0.0, eventKind: 'convergence-radial' },
         light_color: { value: '#FFFFFF', eventKind: 'light-color' },
+        monochrome_luma: { value: null as number | null, eventKind: 'monochrome-luma' },
+        phosphor_type: { value: null as number | null, eventKind: 'phosphor-type' },
+        phosphor_bloom: { value: 0.3, eventKind: 'phosphor-bloom' },
+        lcd_palette: { value: null as number | null, eventKind: 'lcd-palette' },
+        lcd_response_rise: { value: 0.0, eventKind: 'lcd-response-rise' },
+        lcd_response_fall: { value: 0.0, eventKind: 'lcd-response-fall' },
+        reflective_panel: { value: null as number | null, eventKind: 'reflective-panel' },
+        ambient_light: { value: 1, eventKind: 'ambient-light' },
+        ambient_elevation: { value: 60, eventKind: 'ambient-elevation' },
+        ambient_azimuth: { value: 0, eventKind: 'ambient-azimuth' },
+        epaper_gray_levels: { value: 16, eventKind: 'epaper-gray-levels' },
+        epaper_ghosting: { value: 0.1, eventKind: 'epaper-ghosting' },
+        lcd_substrate: { value: '#000000', eventKind: 'lcd-substrate' },
+        tube_uniformity: { value: 0.0, eventKind: 'tube-uniformity' },
+        phosphor_wear: { value: 0.0, eventKind: 'phosphor-wear' },
+        color_drift: { value: 0.0, eventKind: 'color-drift' },
+        burn_in_speed: { value: 0.0, eventKind: 'burn-in-speed' },
+        vector_beam_width: { value: 1.5, eventKind: 'vector-beam-width' },
+        vector_hot_spot: { value: 1, eventKind: 'vector-hot-spot' },
+        vector_persistence: { value: 40, eventKind: 'vector-persistence' },
+        vector_bloom: { value: 0.6, eventKind: 'vector-bloom' },
+        color_vision: { value: null as number | null, eventKind: 'color-vision' },
+        color_vision_severity: { value: 1.0, eventKind: 'color-vision-severity' },
         pixel_brightness: { value: null as number | null, eventKind: 'pixel-brightness' },
         pixel_contrast: { value: null as number | null, eventKind: 'pixel-contrast' },
         color_representation: { value: null as number | null, eventKind: 'color-representation' },
@@ -164,6 +246,8 @@ export function data () {
         reset_filters: { eventKind: 'reset-filters' },
         reset_camera: { eventKind: 'reset-camera' },
         reset_speeds: { eventKind: 'reset-speeds' },
+        power_toggle: { eventKind: 'power-toggle' },
+        degauss: { eventKind: 'degauss' },
         capture_framebuffer: { eventKind: 'capture-framebuffer' },
         webgl_performance: { value: null as string | null, eventKind: 'webgl:performance' },
         webgl_antialias: { value: null as boolean | null, eventKind: 'webgl:antialias' },
@@ -230,7 +314,10 @@ export function data () {
                     open: true,
                     entries: [
                         { type: 'selectors-input', class: 'menu-2 menu-blc-white', text: 'Internal Resolution', hk: { inc: 'Y', dec: 'Shift + Y' }, ref: options.internal_resolution },
-                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Blur passes', hk: { inc: 'J', dec: 'Shift + J' }, step: 1, min: 0, max: 100, value: 0, placeholder: 0, ref: options.blur_level } as NumberInputEntry
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Blur passes', hk: { inc: 'J', dec: 'Shift + J' }, step: 1, min: 0, max: 100, value: 0, placeholder: 0, ref: options.blur_level } as NumberInputEntry,
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-yellow', text: 'Scan output', ref: options.scan_output },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Display refresh (Hz)', hk: { inc: '????', dec: 'Shift + ????' }, step: 1, min: 24, max: 480, value: 120, placeholder: 120, ref: options.display_refresh },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Source refresh (Hz)', hk: { inc: '????', dec: 'Shift + ????' }, step: 1, min: 24, max: 120, value: 60, placeholder: 60, ref: options.source_refresh }
                     ]
                 } as MenuEntry,
                 {
@@ -239,25 +326,106 @@ export function data () {
                     open: false,
                     entries: [
                         { type: 'rgb-input', class: 'menu-blc-red', ref: options.rgb_values },
-                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'Gamma correction', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.1, min: 0, max: 1, value: 0, placeholder: 0, ref: options.color_gamma },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'Gamma correction', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.1, min: 0, max: 3, value: 0, placeholder: 0, ref: options.color_gamma },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-red', text: 'Source primaries', ref: options.source_primaries },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-red', text: 'Display primaries', ref: options.display_primaries },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-red', text: 'White point', ref: options.white_point },
+                        { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Color temperature (K)', hk: { inc: '????', dec: 'Shift + ????' }, step: 100, min: 4000, max: 12000, value: 6500, placeholder: 6500, ref: options.color_temperature },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-red', text: 'LUT stage', ref: options.lut_stage },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-red', text: 'LUT interpolation', ref: options.lut_interpolation },
+                        { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'LUT strength', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.1, min: 0, max: 1, value: 1, placeholder: 1, ref: options.lut_strength },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-lila', text: 'Source EOTF', ref: options.source_transfer },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-lila', text: 'Display transfer', ref: options.display_transfer },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'Transfer power', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.1, min: 1, max: 3, value: 2.2, placeholder: 2.2, ref: options.transfer_power },
                         { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Color noise', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.1, min: 0, max: 1, value: 0, placeholder: 0, ref: options.color_noise },
                         { type: 'color-input', class: 'menu-2 menu-blc-blue', text: 'Source light color', value: '#ffffff', ref: options.light_color },
                         { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Brightness', hk: { inc: 'X', dec: 'Shift + X' }, step: 0.001, min: -1, max: 1, value: 0, placeholder: 0, ref: options.pixel_brightness },
                         { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Contrast', hk: { inc: 'Z', dec: 'Shift + Z' }, step: 0.001, min: 0, max: 20, value: 1, placeholder: 0, ref: options.pixel_contrast },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-green', text: 'Monochrome', ref: options.monochrome_luma },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-green', text: 'Phosphor', ref: options.phosphor_type },
+                        { type: 'number-input', class: 'menu-2 menu-blc-green', text: 'Phosphor bloom', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0.3, placeholder: 0, ref: options.phosphor_bloom },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-lila', text: 'LCD palette', ref: options.lcd_palette },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'LCD rise time (ms)', hk: { inc: '????', dec: 'Shift + ????' }, step: 1, min: 0, max: 250, value: 0, placeholder: 0, ref: options.lcd_response_rise },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'LCD fall time (ms)', hk: { inc: '????', dec: 'Shift + ????' }, step: 1, min: 0, max: 250, value: 0, placeholder: 0, ref: options.lcd_response_fall },
+                        { type: 'color-input', class: 'menu-2 menu-blc-lila', text: 'LCD substrate', value: '#000000', ref: options.lcd_substrate },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-lila', text: 'Reflective panel', ref: options.reflective_panel },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'Ambient light', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.05, min: 0, max: 2, value: 1, placeholder: 1, ref: options.ambient_light },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'Light elevation (°)', hk: { inc: '????', dec: 'Shift + ????' }, step: 1, min: 0, max: 90, value: 60, placeholder: 60, ref: options.ambient_elevation },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'Light azimuth (°)', hk: { inc: '????', dec: 'Shift + ????' }, step: 1, min: -180, max: 180, value: 0, placeholder: 0, ref: options.ambient_azimuth },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'E-paper gray levels', hk: { inc: '????', dec: 'Shift + ????' }, step: 1, min: 2, max: 16, value: 16, placeholder: 16, ref: options.epaper_gray_levels },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'E-paper ghosting', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 0.5, value: 0.1, placeholder: 0.1, ref: options.epaper_ghosting },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Tube uniformity', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.05, min: 0, max: 1, value: 0, placeholder: 0, ref: options.tube_uniformity },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Phosphor wear', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.05, min: 0, max: 1, value: 0, placeholder: 0, ref: options.phosphor_wear },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Color drift', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.05, min: 0, max: 1, value: 0, placeholder: 0, ref: options.color_drift },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Burn-in speed (hours/s)', hk: { inc: '????', dec: 'Shift + ????' }, step: 100, min: 0, max: 10000, value: 0, placeholder: 0, ref: options.burn_in_speed },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Vector beam width', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.05, min: 0.25, max: 8, value: 1.5, placeholder: 1.5, ref: options.vector_beam_width },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Vector hot spots', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.1, min: 0, max: 4, value: 1, placeholder: 1, ref: options.vector_hot_spot },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Vector persistence (ms)', hk: { inc: '????', dec: 'Shift + ????' }, step: 5, min: 0, max: 1000, value: 40, placeholder: 40, ref: options.vector_persistence },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Vector bloom', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.05, min: 0, max: 2, value: 0.6, placeholder: 0.6, ref: options.vector_bloom },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-green', text: 'Color vision', ref: options.color_vision },
+                        { type: 'number-input', class: 'menu-2 menu-blc-green', text: 'Color vision severity', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.1, min: 0, max: 1, value: 1, placeholder: 1, ref: options.color_vision_severity },
                         { type: 'color-input', class: 'display-none', text: 'Brightness color', value: '#ffffff', ref: options.brightness_color }
                     ]
                 } as MenuEntry,
+                {
+                    type: 'menu',
+                    text: 'Video Signal',
+                    open: false,
+                    entries: [
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-red', text: 'Source color depth', ref: options.source_depth },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-red', text: 'Source dithering', ref: options.source_dither },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-red', text: 'Dither blending', ref: options.dither_blend },
+                        { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Dither tolerance', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 0.5, value: 0.05, placeholder: 0.05, ref: options.dither_tolerance },
+                        { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Dither min. contrast', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0.1, placeholder: 0.1, ref: options.dither_contrast },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-blue', text: 'Signal type', ref: options.signal_type },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-blue', text: 'Color system', ref: options.signal_standard },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Subcarrier crosstalk', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0.5, placeholder: 0.5, ref: options.signal_crosstalk },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-blue', text: 'Tape playback', ref: options.tape_speed },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Tape noise', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0.3, placeholder: 0.3, ref: options.tape_noise },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Tracking error', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0.2, placeholder: 0.2, ref: options.tape_tracking },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Dropouts', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0.2, placeholder: 0.2, ref: options.tape_dropouts },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'RF snow', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.rf_snow },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Hum bars', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.hum_bar_strength },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Hum beat (Hz)', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.05, min: 0, max: 5, value: 0.5, placeholder: 0.5, ref: options.hum_bar_frequency },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Ghost strength', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.ghost_strength },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Ghost offset', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.5, min: 0, max: 32, value: 4, placeholder: 4, ref: options.ghost_offset },
+                        { type: 'number-input', class: 'menu-2 menu-blc-blue', text: 'Vertical roll', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.vertical_roll },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Black level', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: -0.5, max: 0.5, value: 0, placeholder: 0, ref: options.tv_black_level },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Picture', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 2, value: 1, placeholder: 1, ref: options.tv_picture },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Color', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 2, value: 1, placeholder: 1, ref: options.tv_color },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Tint (degrees)', hk: { inc: '????', dec: 'Shift + ????' }, step: 1, min: -45, max: 45, value: 0, placeholder: 0, ref: options.tv_tint },
+                        { type: 'number-input', class: 'menu-2 menu-blc-yellow', text: 'Sharpness', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.tv_sharpness }
+                    ]
+                } as MenuEntry,
                 {
                     type: 'menu',
                     text: 'Geometry & Textures',
                     open: false,
                     entries: [
                         { type: 'selectors-input', class: 'menu-2 menu-blc-white', text: 'Screen curvature type', hk: { inc: 'B', dec: 'Shift + B' }, ref: options.screen_curvature },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Horizontal curvature', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0.3, placeholder: 0.3, ref: options.curvature_horizontal },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Vertical curvature', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0.3, placeholder: 0.3, ref: options.curvature_vertical },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Barrel / pincushion', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: -1, max: 1, value: 0, placeholder: 0, ref: options.barrel_distortion },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Keystone', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: -1, max: 1, value: 0, placeholder: 0, ref: options.screen_keystone },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Trapezoid', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: -1, max: 1, value: 0, placeholder: 0, ref: options.screen_trapezoid },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Tilt (degrees)', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.1, min: -10, max: 10, value: 0, placeholder: 0, ref: options.screen_tilt },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Corner radius', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.screen_corner_radius },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Edge softness', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.screen_edge_softness },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Vignette strength', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.vignette_strength },
+                        { type: 'number-input', class: 'menu-2 menu-blc-white', text: 'Vignette shape', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.vignette_shape },
                         { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Horizontal gap', hk: { inc: 'U', dec: 'Shift + U' }, step: 0.001, min: 0, max: 10, value: 0, placeholder: 0, ref: options.horizontal_gap },
                         { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Vertical gap', hk: { inc: 'I', dec: 'Shift + I' }, step: 0.001, min: 0, max: 10, value: 0, placeholder: 0, ref: options.vertical_gap },
                         { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'V. lines per pixel', hk: { inc: 'K', dec: 'Shift + K' }, step: 1, min: 0, max: 100, value: 0, placeholder: 0, ref: options.vertical_lpp },
                         { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'H. lines per pixel', hk: { inc: 'L', dec: 'Shift + L' }, step: 1, min: 0, max: 100, value: 0, placeholder: 0, ref: options.horizontal_lpp },
+                        { type: 'selectors-input', class: 'menu-2 menu-blc-lila', text: 'Scan mode', ref: options.scan_mode },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'Interlace flicker', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0.5, placeholder: 0.5, ref: options.interlace_flicker },
+                        { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'Interlace combing', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 1, value: 0, placeholder: 0, ref: options.interlace_combing },
                         { type: 'selectors-input', class: 'menu-2 menu-blc-red', text: 'Color channels type', hk: { inc: 'C', dec: 'Shift + C' }, ref: options.color_representation },
+                        { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Convergence red X', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: -2, max: 2, value: 0, placeholder: 0, ref: options.convergence_red_x },
+                        { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Convergence red Y', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: -2, max: 2, value: 0, placeholder: 0, ref: options.convergence_red_y },
+                        { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Convergence blue X', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: -2, max: 2, value: 0, placeholder: 0, ref: options.convergence_blue_x },
+                        { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Convergence blue Y', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: -2, max: 2, value: 0, placeholder: 0, ref: options.convergence_blue_y },
+                        { type: 'number-input', class: 'menu-2 menu-blc-red', text: 'Corner misconvergence', hk: { inc: '????', dec: 'Shift + ????' }, step: 0.01, min: 0, max: 2, value: 0, placeholder: 0, ref: options.convergence_radial },
                         { type: 'selectors-input', class: 'menu-2 menu-blc-yellow', text: 'Pixel geometry type', hk: { inc: 'V', dec: 'Shift + V' }, ref: options.pixel_geometry },
                         { type: 'selectors-input', class: 'menu-2 menu-blc-blue', text: 'Pixel texture', hk: { inc: 'N', dec: 'Shift + N' }, ref: options.pixel_shadow_shape },
                         { type: 'number-input', class: 'menu-2 menu-blc-lila', text: 'Pixel variable height', hk: { inc: 'M', dec: 'Shift + M' }, step: 0.001, min: 0, max: 1, value: 0, placeholder: 0, ref: options.pixel_shadow_height },
@@ -303,6 +471,8 @@ export function data () {
                     text: 'Extra',
                     open: false,
                     entries: [
+                        { type: 'button-input', class: 'menu-2 menu-blc-yellow', text: 'Power On/Off', ref: options.power_toggle },
+                        { type: 'button-input', class: 'menu-2 menu-blc-yellow', text: 'Degauss', ref: options.degauss },
                         { type: 'button-input', class: 'menu-2 menu-blc-yellow', text: 'Take Screenshot', ref: options.capture_framebuffer }
                     ]
                 } as MenuEntry,
@@ -539,6 +709,18 @@ export class SimViewModel {
         this._state.options.blur_level.value = msg;
         this._isDirty = true;
     }
+    changeScanOutput (msg: number) {
+        this._state.options.scan_output.value = msg;
+        this._isDirty = true;
+    }
+    changeDisplayRefresh (msg: number) {
+        this._state.options.display_refresh.value = msg;
+        this._isDirty = true;
+    }
+    changeSourceRefresh (msg: number) {
+        this._state.options.source_refresh.value = msg;
+        this._isDirty = true;
+    }
     changeVerticalLpp (msg: number) {
         this._state.options.vertical_lpp.value = msg;
         this._isDirty = true;
@@ -547,10 +729,114 @@ export class SimViewModel {
         this._state.options.horizontal_lpp.value = msg;
         this._isDirty = true;
     }
+    changeScanMode (msg: number) {
+        this._state.options.scan_mode.value = msg;
+        this._isDirty = true;
+    }
+    changeInterlaceFlicker (msg: number) {
+        this._state.options.interlace_flicker.value = msg;
+        this._isDirty = true;
+    }
+    changeInterlaceCombing (msg: number) {
+        this._state.options.interlace_combing.value = msg;
+        this._isDirty = true;
+    }
     changeLightColor (msg: string) {
         this._state.options.light_color.value = msg;
         this._isDirty = true;
     }
+    changeMonochromeLuma (msg: number) {
+        this._state.options.monochrome_luma.value = msg;
+        this._isDirty = true;
+    }
+    changePhosphorType (msg: number) {
+        this._state.options.phosphor_type.value = msg;
+        this._isDirty = true;
+    }
+    changePhosphorBloom (msg: number) {
+        this._state.options.phosphor_bloom.value = msg;
+        this._isDirty = true;
+    }
+    changeLcdPalette (msg: number) {
+        this._state.options.lcd_palette.value = msg;
+        this._isDirty = true;
+    }
+    changeLcdResponseRise (msg: number) {
+        this._state.options.lcd_response_rise.value = msg;
+        this._isDirty = true;
+    }
+    changeLcdResponseFall (msg: number) {
+        this._state.options.lcd_response_fall.value = msg;
+        this._isDirty = true;
+    }
+    changeReflectivePanel (msg: number) {
+        this._state.options.reflective_panel.value = msg;
+        this._isDirty = true;
+    }
+    changeAmbientLight (msg: number) {
+        this._state.options.ambient_light.value = msg;
+        this._isDirty = true;
+    }
+    changeAmbientElevation (msg: number) {
+        this._state.options.ambient_elevation.value = msg;
+        this._isDirty = true;
+    }
+    changeAmbientAzimuth (msg: number) {
+        this._state.options.ambient_azimuth.value = msg;
+        this._isDirty = true;
+    }
+    changeEpaperGrayLevels (msg: number) {
+        this._state.options.epaper_gray_levels.value = msg;
+        this._isDirty = true;
+    }
+    changeEpaperGhosting (msg: number) {
+        this._state.options.epaper_ghosting.value = msg;
+        this._isDirty = true;
+    }
+    changeLcdSubstrate (msg: string) {
+        this._state.options.lcd_substrate.value = msg;
+        this._isDirty = true;
+    }
+    changeTubeUniformity (msg: number) {
+        this._state.options.tube_uniformity.value = msg;
+        this._isDirty = true;
+    }
+    changePhosphorWear (msg: number) {
+        this._state.options.phosphor_wear.value = msg;
+        this._isDirty = true;
+    }
+    changeColorDrift (msg: number) {
+        this._state.options.color_drift.value = msg;
+        this._isDirty = true;
+    }
+    changeBurnInSpeed (msg: number) {
+        this._state.options.burn_in_speed.value = msg;
+        this._isDirty = true;
+    }
+    changeVectorBeamWidth (msg: number) {
+        this._state.options.vector_beam_width.value = msg;
+        this._isDirty = true;
+    }
+    changeVectorHotSpot (msg: number) {
+        this._state.options.vector_hot_spot.value = msg;
+        this._isDirty = true;
+    }
+    changeVectorPersistence (msg: number) {
+        this._state.options.vector_persistence.value = msg;
+        this._isDirty = true;
+    }
+    changeVectorBloom (msg: number) {
+        this._state.options.vector_bloom.value = msg;
+        this._isDirty = true;
+    }
+    changeColorVision (msg: number) {
+        this._state.options.color_vision.value = msg;
+        this._isDirty = true;
+    }
+    changeColorVisionSeverity (msg: number) {
+        this._state.options.color_vision_severity.value = msg;
+        this._isDirty = true;
+    }
     changeBrightnessColor (msg: string) {
         this._state.options.brightness_color.value = msg;
         this._isDirty = true;
@@ -599,6 +885,46 @@ export class SimViewModel {
         this._state.options.screen_curvature.value = msg;
         this._isDirty = true;
     }
+    changeCurvatureHorizontal (msg: number) {
+        this._state.options.curvature_horizontal.value = msg;
+        this._isDirty = true;
+    }
+    changeCurvatureVertical (msg: number) {
+        this._state.options.curvature_vertical.value = msg;
+        this._isDirty = true;
+    }
+    changeBarrelDistortion (msg: number) {
+        this._state.options.barrel_distortion.value = msg;
+        this._isDirty = true;
+    }
+    changeScreenKeystone (msg: number) {
+        this._state.options.screen_keystone.value = msg;
+        this._isDirty = true;
+    }
+    changeScreenTrapezoid (msg: number) {
+        this._state.options.screen_trapezoid.value = msg;
+        this._isDirty = true;
+    }
+    changeScreenTilt (msg: number) {
+        this._state.options.screen_tilt.value = msg;
+        this._isDirty = true;
+    }
+    changeScreenCornerRadius (msg: number) {
+        this._state.options.screen_corner_radius.value = msg;
+        this._isDirty = true;
+    }
+    changeScreenEdgeSoftness (msg: number) {
+        this._state.options.screen_edge_softness.value = msg;
+        this._isDirty = true;
+    }
+    changeVignetteStrength (msg: number) {
+        this._state.options.vignette_strength.value = msg;
+        this._isDirty = true;
+    }
+    changeVignetteShape (msg: number) {
+        this._state.options.vignette_shape.value = msg;
+        this._isDirty = true;
+    }
     changeScalingMethod (msg: string) {
         this._state.options.scaling_method.value = msg;
         this._isDirty = true;
@@ -637,10 +963,162 @@ export class SimViewModel {
         this._state.options.color_gamma.value = gamma;
         this._isDirty = true;
     }
+    changeSourcePrimaries (msg: number) {
+        this._state.options.source_primaries.value = msg;
+        this._isDirty = true;
+    }
+    changeDisplayPrimaries (msg: number) {
+        this._state.options.display_primaries.value = msg;
+        this._isDirty = true;
+    }
+    changeWhitePoint (msg: number) {
+        this._state.options.white_point.value = msg;
+        this._isDirty = true;
+    }
+    changeColorTemperature (msg: number) {
+        this._state.options.color_temperature.value = msg;
+        this._isDirty = true;
+    }
+    changeLutStage (msg: number) {
+        this._state.options.lut_stage.value = msg;
+        this._isDirty = true;
+    }
+    changeLutInterpolation (msg: number) {
+        this._state.options.lut_interpolation.value = msg;
+        this._isDirty = true;
+    }
+    changeLutStrength (msg: number) {
+        this._state.options.lut_strength.value = msg;
+        this._isDirty = true;
+    }
+    changeSourceTransfer (msg: number) {
+        this._state.options.source_transfer.value = msg;
+        this._isDirty = true;
+    }
+    changeDisplayTransfer (msg: number) {
+        this._state.options.display_transfer.value = msg;
+        this._isDirty = true;
+    }
+    changeTransferPower (msg: number) {
+        this._state.options.transfer_power.value = msg;
+        this._isDirty = true;
+    }
     changeColorNoise (noise: number) {
         this._state.options.color_noise.value = noise;
         this._isDirty = true;
     }
+    changeSourceDepth (msg: number) {
+        this._state.options.source_depth.value = msg;
+        this._isDirty = true;
+    }
+    changeSourceDither (msg: number) {
+        this._state.options.source_dither.value = msg;
+        this._isDirty = true;
+    }
+    changeDitherBlend (msg: number) {
+        this._state.options.dither_blend.value = msg;
+        this._isDirty = true;
+    }
+    changeDitherTolerance (msg: number) {
+        this._state.options.dither_tolerance.value = msg;
+        this._isDirty = true;
+    }
+    changeDitherContrast (msg: number) {
+        this._state.options.dither_contrast.value = msg;
+        this._isDirty = true;
+    }
+    changeSignalType (msg: number) {
+        this._state.options.signal_type.value = msg;
+        this._isDirty = true;
+    }
+    changeSignalStandard (msg: number) {
+        this._state.options.signal_standard.value = msg;
+        this._isDirty = true;
+    }
+    changeSignalCrosstalk (msg: number) {
+        this._state.options.signal_crosstalk.value = msg;
+        this._isDirty = true;
+    }
+    changeTapeSpeed (msg: number) {
+        this._state.options.tape_speed.value = msg;
+        this._isDirty = true;
+    }
+    changeTapeNoise (msg: number) {
+        this._state.options.tape_noise.value = msg;
+        this._isDirty = true;
+    }
+    changeTapeTracking (msg: number) {
+        this._state.options.tape_tracking.value = msg;
+        this._isDirty = true;
+    }
+    changeTapeDropouts (msg: number) {
+        this._state.options.tape_dropouts.value = msg;
+        this._isDirty = true;
+    }
+    changeRfSnow (msg: number) {
+        this._state.options.rf_snow.value = msg;
+        this._isDirty = true;
+    }
+    changeHumBarStrength (msg: number) {
+        this._state.options.hum_bar_strength.value = msg;
+        this._isDirty = true;
+    }
+    changeHumBarFrequency (msg: number) {
+        this._state.options.hum_bar_frequency.value = msg;
+        this._isDirty = true;
+    }
+    changeGhostStrength (msg: number) {
+        this._state.options.ghost_strength.value = msg;
+        this._isDirty = true;
+    }
+    changeGhostOffset (msg: number) {
+        this._state.options.ghost_offset.value = msg;
+        this._isDirty = true;
+    }
+    changeVerticalRoll (msg: number) {
+        this._state.options.vertical_roll.value = msg;
+        this._isDirty = true;
+    }
+    changeTvBlackLevel (msg: number) {
+        this._state.options.tv_black_level.value = msg;
+        this._isDirty = true;
+    }
+    changeTvPicture (msg: number) {
+        this._state.options.tv_picture.value = msg;
+        this._isDirty = true;
+    }
+    changeTvColor (msg: number) {
+        this._state.options.tv_color.value = msg;
+        this._isDirty = true;
+    }
+    changeTvTint (msg: number) {
+        this._state.options.tv_tint.value = msg;
+        this._isDirty = true;
+    }
+    changeTvSharpness (msg: number) {
+        this._state.options.tv_sharpness.value = msg;
+        this._isDirty = true;
+    }
+    changeConvergenceRedX (msg: number) {
+        this._state.options.convergence_red_x.value = msg;
+        this._isDirty = true;
+    }
+    changeConvergenceRedY (msg: number) {
+        this._state.options.convergence_red_y.value = msg;
+        this._isDirty = true;
+    }
+    changeConvergenceBlueX (msg: number) {
+        this._state.options.convergence_blue_x.value = msg;
+        this._isDirty = true;
+    }
+    changeConvergenceBlueY (msg: number) {
+        this._state.options.convergence_blue_y.value = msg;
+        this._isDirty = true;
+    }
+    changeConvergenceRadial (msg: number) {
+        this._state.options.convergence_radial.value = msg;
+        this._isDirty = true;
+    }
     changeColorRgb (value: number, rgbRow: 'red' | 'green' | 'blue', rgbColumn: 'r' | 'g' | 'b') {
         this._state.options.rgb_values[rgbRow][rgbColumn].value = value;
         this._isDirty = true;
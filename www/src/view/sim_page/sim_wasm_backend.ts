@@ -20,6 +20,7 @@ export class SimWasmBackend {
     private _app: any;
     private _canvas: HTMLCanvasElement | null;
     private _webglUiEnabled: boolean;
+    private _imageSize: { width: number, height: number } | null;
 
     private static _instance: Lazy<SimWasmBackend> = Lazy.from(() => new SimWasmBackend());
     static getInstance (): SimWasmBackend { return this._instance.get(); }
@@ -27,6 +28,7 @@ export class SimWasmBackend {
         this._app = null;
         this._canvas = null;
         this._webglUiEnabled = true;
+        this._imageSize = null;
     }
 
     async load (canvas: HTMLCanvasElement, eventBus: any, params: any) {
@@ -42,6 +44,7 @@ export class SimWasmBackend {
 
         Logger.log('resolutions:', canvas.width, canvas.height, params.imageWidth, params.imageHeight);
 
+        this._imageSize = { width: params.imageWidth, height: params.imageHeight };
         const config = new VideoInputConfig(
             params.imageWidth, params.imageHeight, // to read the image pixels
             canvas.width, canvas.height // gl.viewport
@@ -56,6 +59,29 @@ export class SimWasmBackend {
             config.add_picture_frame(new Uint8Array(rawImg.raw.data.buffer), rawImg.delay);
         }
 
+        if (params.artwork && config.set_artwork_layout(params.artwork.layout)) {
+            // Images follow the order of the layers in the layout.
+            for (const image of params.artwork.images) {
+                config.add_artwork_image(new Uint8Array(image.data.buffer), image.width, image.height);
+            }
+        }
+
+        if (params.cabinetModel) {
+            config.set_cabinet_model(params.cabinetModel);
+        }
+
+        if (params.colorLut) {
+            config.set_color_lut(params.colorLut);
+        }
+
+        if (params.burnInMap) {
+            config.set_burn_in_map(new Uint8Array(params.burnInMap.data.buffer), params.burnInMap.width, params.burnInMap.height);
+        }
+
+        if (params.vectorScene) {
+            config.set_vector_scene(new Uint8Array(params.vectorScene));
+        }
+
         if (params.activePreset) {
             config.set_preset(params.activePreset);
         }
@@ -115,6 +141,15 @@ export class SimWasmBackend {
         }
     }
 
+    // Same shape as params.burnInMap, so a worn tube can be saved and loaded again.
+    burnInMap (): ImageData | null {
+        const pixels = this._app && this._app.burn_in_map();
+        if (!pixels || !this._imageSize) {
+            return null;
+        }
+        return new ImageData(new Uint8ClampedArray(pixels.buffer), this._imageSize.width, this._imageSize.height);
+    }
+
     unload () {
         this._canvas = null;
         return this._app.unload();